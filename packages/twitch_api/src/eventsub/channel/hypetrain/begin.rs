@@ -67,7 +67,7 @@ pub struct ChannelHypeTrainBeginV1Payload {
 
 #[cfg(test)]
 #[test]
-fn parse_payload() {
+fn parse_payload_v1() {
     let payload = r##"
     {
         "subscription": {
@@ -108,3 +108,100 @@ fn parse_payload() {
     let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
     crate::tests::roundtrip(&val)
 }
+
+/// [`channel.hype_train.begin`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainbegin-v2): a hype train begins on the specified channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ChannelHypeTrainBeginV2 {
+    /// The broadcaster user ID for the channel you want hype train begin notifications for.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    pub broadcaster_user_id: types::UserId,
+}
+
+impl ChannelHypeTrainBeginV2 {
+    /// The broadcaster user ID for the channel you want hype train begin notifications for.
+    pub fn broadcaster_user_id(broadcaster_user_id: impl Into<types::UserId>) -> Self {
+        Self {
+            broadcaster_user_id: broadcaster_user_id.into(),
+        }
+    }
+}
+
+impl EventSubscription for ChannelHypeTrainBeginV2 {
+    type Payload = ChannelHypeTrainBeginV2Payload;
+
+    const EVENT_TYPE: EventType = EventType::ChannelHypeTrainBegin;
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator =
+        twitch_oauth2::validator![twitch_oauth2::Scope::ChannelReadHypeTrain];
+    const VERSION: &'static str = "2";
+}
+
+/// [`channel.hype_train.begin`](ChannelHypeTrainBeginV2) response payload.
+///
+/// The [all-time high](types::HypeTrain::all_time_high_level) fields are set for this event.
+pub type ChannelHypeTrainBeginV2Payload = types::HypeTrain;
+
+#[cfg(test)]
+#[test]
+fn parse_payload_v2() {
+    let payload = r##"
+    {
+        "subscription": {
+            "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+            "type": "channel.hype_train.begin",
+            "version": "2",
+            "status": "enabled",
+            "cost": 0,
+            "condition": {
+                "broadcaster_user_id": "1337"
+            },
+            "transport": {
+                "method": "webhook",
+                "callback": "https://example.com/webhooks/callback"
+            },
+            "created_at": "2019-11-16T10:11:12.123Z"
+        },
+        "event": {
+            "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+            "broadcaster_user_id": "1337",
+            "broadcaster_user_login": "cool_user",
+            "broadcaster_user_name": "Cool_User",
+            "total": 137,
+            "progress": 137,
+            "goal": 500,
+            "top_contributions": [
+                { "user_id": "123", "user_login": "pogchamp", "user_name": "PogChamp", "type": "bits", "total": 50 },
+                { "user_id": "456", "user_login": "kappa", "user_name": "Kappa", "type": "subscription", "total": 45 }
+            ],
+            "level": 2,
+            "all_time_high_level": 4,
+            "all_time_high_total": 2845,
+            "shared_train_participants": [
+                { "broadcaster_user_id": "1337", "broadcaster_user_login": "cool_user", "broadcaster_user_name": "Cool_User" },
+                { "broadcaster_user_id": "456", "broadcaster_user_login": "pogchamp", "broadcaster_user_name": "PogChamp" }
+            ],
+            "started_at": "2020-07-15T17:16:03.17106713Z",
+            "expires_at": "2020-07-15T17:16:11.17106713Z",
+            "type": "golden_kappa",
+            "is_shared_train": true
+        }
+    }
+    "##;
+
+    let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
+    crate::tests::roundtrip(&val);
+
+    let crate::eventsub::Event::ChannelHypeTrainBeginV2(val) = val else {
+        panic!("invalid event type");
+    };
+    let crate::eventsub::Message::Notification(notif) = val.message else {
+        panic!("invalid message type");
+    };
+
+    assert_eq!(notif.type_, types::HypeTrainType::GoldenKappa);
+    assert_eq!(notif.all_time_high_level, Some(4));
+    assert_eq!(notif.shared_train_participants.as_ref().unwrap().len(), 2);
+}
@@ -62,7 +62,7 @@ pub struct ChannelHypeTrainEndV1Payload {
 
 #[cfg(test)]
 #[test]
-fn parse_payload() {
+fn parse_payload_v1() {
     let payload = r##"
     {
         "subscription": {
@@ -101,3 +101,126 @@ fn parse_payload() {
     let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
     crate::tests::roundtrip(&val)
 }
+
+/// [`channel.hype_train.end`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainend-v2): a hype train ends on the specified channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ChannelHypeTrainEndV2 {
+    /// The broadcaster user ID for the channel you want hype train end notifications for.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    pub broadcaster_user_id: types::UserId,
+}
+
+impl ChannelHypeTrainEndV2 {
+    /// The broadcaster user ID for the channel you want hype train end notifications for.
+    pub fn broadcaster_user_id(broadcaster_user_id: impl Into<types::UserId>) -> Self {
+        Self {
+            broadcaster_user_id: broadcaster_user_id.into(),
+        }
+    }
+}
+
+impl EventSubscription for ChannelHypeTrainEndV2 {
+    type Payload = ChannelHypeTrainEndV2Payload;
+
+    const EVENT_TYPE: EventType = EventType::ChannelHypeTrainEnd;
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator =
+        twitch_oauth2::validator![twitch_oauth2::Scope::ChannelReadHypeTrain];
+    const VERSION: &'static str = "2";
+}
+
+/// [`channel.hype_train.end`](ChannelHypeTrainEndV2) response payload.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ChannelHypeTrainEndV2Payload {
+    /// The Hype Train ID.
+    pub id: types::HypeTrainId,
+    /// The requested broadcaster ID.
+    pub broadcaster_user_id: types::UserId,
+    /// The requested broadcaster login.
+    pub broadcaster_user_login: types::UserName,
+    /// The requested broadcaster display name.
+    pub broadcaster_user_name: types::DisplayName,
+    /// The final level of the Hype Train.
+    pub level: i64,
+    /// Total points contributed to the Hype Train.
+    pub total: i64,
+    /// The contributors with the most points contributed.
+    pub top_contributions: Vec<types::HypeTrainContribution>,
+    /// The broadcasters that participated in the shared Hype Train. [`None`] if the Hype Train was not shared.
+    #[serde(default)]
+    pub shared_train_participants: Option<Vec<types::SharedTrainParticipant>>,
+    /// The timestamp at which the Hype Train started.
+    pub started_at: types::Timestamp,
+    /// The timestamp at which the Hype Train ended.
+    pub ended_at: types::Timestamp,
+    /// The timestamp at which the Hype Train cooldown ends so that the next Hype Train can start.
+    pub cooldown_ends_at: types::Timestamp,
+    /// The type of the Hype Train.
+    #[serde(rename = "type")]
+    pub type_: types::HypeTrainType,
+    /// Indicates if the Hype Train was shared between multiple channels.
+    pub is_shared_train: bool,
+}
+
+#[cfg(test)]
+#[test]
+fn parse_payload_v2() {
+    let payload = r##"
+    {
+        "subscription": {
+            "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+            "type": "channel.hype_train.end",
+            "version": "2",
+            "status": "enabled",
+            "cost": 0,
+            "condition": {
+                "broadcaster_user_id": "1337"
+            },
+            "transport": {
+                "method": "webhook",
+                "callback": "https://example.com/webhooks/callback"
+            },
+            "created_at": "2019-11-16T10:11:12.123Z"
+        },
+        "event": {
+            "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+            "broadcaster_user_id": "1337",
+            "broadcaster_user_login": "cool_user",
+            "broadcaster_user_name": "Cool_User",
+            "total": 137,
+            "top_contributions": [
+                { "user_id": "123", "user_login": "pogchamp", "user_name": "PogChamp", "type": "bits", "total": 50 },
+                { "user_id": "456", "user_login": "kappa", "user_name": "Kappa", "type": "subscription", "total": 45 }
+            ],
+            "level": 2,
+            "shared_train_participants": [
+                { "broadcaster_user_id": "1337", "broadcaster_user_login": "cool_user", "broadcaster_user_name": "Cool_User" },
+                { "broadcaster_user_id": "456", "broadcaster_user_login": "pogchamp", "broadcaster_user_name": "PogChamp" }
+            ],
+            "started_at": "2020-07-15T17:16:03.17106713Z",
+            "ended_at": "2020-07-15T17:16:11.17106713Z",
+            "cooldown_ends_at": "2020-07-15T18:16:11.17106713Z",
+            "type": "treasure",
+            "is_shared_train": true
+        }
+    }
+    "##;
+
+    let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
+    crate::tests::roundtrip(&val);
+
+    let crate::eventsub::Event::ChannelHypeTrainEndV2(val) = val else {
+        panic!("invalid event type");
+    };
+    let crate::eventsub::Message::Notification(notif) = val.message else {
+        panic!("invalid message type");
+    };
+
+    assert_eq!(notif.type_, types::HypeTrainType::Treasure);
+    assert_eq!(notif.top_contributions.len(), 2);
+}
@@ -11,9 +11,15 @@ pub mod progress;
 #[doc(inline)]
 pub use begin::{ChannelHypeTrainBeginV1, ChannelHypeTrainBeginV1Payload};
 #[doc(inline)]
+pub use begin::{ChannelHypeTrainBeginV2, ChannelHypeTrainBeginV2Payload};
+#[doc(inline)]
 pub use end::{ChannelHypeTrainEndV1, ChannelHypeTrainEndV1Payload};
 #[doc(inline)]
+pub use end::{ChannelHypeTrainEndV2, ChannelHypeTrainEndV2Payload};
+#[doc(inline)]
 pub use progress::{ChannelHypeTrainProgressV1, ChannelHypeTrainProgressV1Payload};
+#[doc(inline)]
+pub use progress::{ChannelHypeTrainProgressV2, ChannelHypeTrainProgressV2Payload};
 
 // FIXME: Is this always the same as helix::endpoints::hypetrain::ContributionType?
 /// Type of contribution
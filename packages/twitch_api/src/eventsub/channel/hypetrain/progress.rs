@@ -67,7 +67,7 @@ pub struct ChannelHypeTrainProgressV1Payload {
 
 #[cfg(test)]
 #[test]
-fn parse_payload() {
+fn parse_payload_v1() {
     let payload = r##"
     {
         "subscription": {
@@ -108,3 +108,92 @@ fn parse_payload() {
     let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
     crate::tests::roundtrip(&val)
 }
+
+/// [`channel.hype_train.progress`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainprogress-v2): a hype train makes progress on the specified channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ChannelHypeTrainProgressV2 {
+    /// The broadcaster user ID for the channel you want hype train progress notifications for.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    pub broadcaster_user_id: types::UserId,
+}
+
+impl ChannelHypeTrainProgressV2 {
+    /// The broadcaster user ID for the channel you want hype train progress notifications for.
+    pub fn broadcaster_user_id(broadcaster_user_id: impl Into<types::UserId>) -> Self {
+        Self {
+            broadcaster_user_id: broadcaster_user_id.into(),
+        }
+    }
+}
+
+impl EventSubscription for ChannelHypeTrainProgressV2 {
+    type Payload = ChannelHypeTrainProgressV2Payload;
+
+    const EVENT_TYPE: EventType = EventType::ChannelHypeTrainProgress;
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator =
+        twitch_oauth2::validator![twitch_oauth2::Scope::ChannelReadHypeTrain];
+    const VERSION: &'static str = "2";
+}
+
+/// [`channel.hype_train.progress`](ChannelHypeTrainProgressV2) response payload.
+pub type ChannelHypeTrainProgressV2Payload = types::HypeTrain;
+
+#[cfg(test)]
+#[test]
+fn parse_payload_v2() {
+    let payload = r##"
+    {
+        "subscription": {
+            "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+            "type": "channel.hype_train.progress",
+            "version": "2",
+            "status": "enabled",
+            "cost": 0,
+            "condition": {
+                "broadcaster_user_id": "1337"
+            },
+            "transport": {
+                "method": "webhook",
+                "callback": "https://example.com/webhooks/callback"
+            },
+            "created_at": "2019-11-16T10:11:12.123Z"
+        },
+        "event": {
+            "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+            "broadcaster_user_id": "1337",
+            "broadcaster_user_login": "cool_user",
+            "broadcaster_user_name": "Cool_User",
+            "total": 700,
+            "progress": 200,
+            "goal": 1000,
+            "top_contributions": [
+                { "user_id": "123", "user_login": "pogchamp", "user_name": "PogChamp", "type": "bits", "total": 50 },
+                { "user_id": "456", "user_login": "kappa", "user_name": "Kappa", "type": "subscription", "total": 45 }
+            ],
+            "level": 2,
+            "shared_train_participants": null,
+            "started_at": "2020-07-15T17:16:03.17106713Z",
+            "expires_at": "2020-07-15T17:16:11.17106713Z",
+            "type": "regular",
+            "is_shared_train": false
+        }
+    }
+    "##;
+
+    let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
+    crate::tests::roundtrip(&val);
+
+    let crate::eventsub::Event::ChannelHypeTrainProgressV2(val) = val else {
+        panic!("invalid event type");
+    };
+    let crate::eventsub::Message::Notification(notif) = val.message else {
+        panic!("invalid message type");
+    };
+
+    assert_eq!(notif.type_, types::HypeTrainType::Regular);
+    assert!(notif.shared_train_participants.is_none());
+}
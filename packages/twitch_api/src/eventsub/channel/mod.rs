@@ -132,10 +132,16 @@ pub use guest_star_settings::{
 #[doc(inline)]
 pub use hypetrain::{ChannelHypeTrainBeginV1, ChannelHypeTrainBeginV1Payload};
 #[doc(inline)]
+pub use hypetrain::{ChannelHypeTrainBeginV2, ChannelHypeTrainBeginV2Payload};
+#[doc(inline)]
 pub use hypetrain::{ChannelHypeTrainEndV1, ChannelHypeTrainEndV1Payload};
 #[doc(inline)]
+pub use hypetrain::{ChannelHypeTrainEndV2, ChannelHypeTrainEndV2Payload};
+#[doc(inline)]
 pub use hypetrain::{ChannelHypeTrainProgressV1, ChannelHypeTrainProgressV1Payload};
 #[doc(inline)]
+pub use hypetrain::{ChannelHypeTrainProgressV2, ChannelHypeTrainProgressV2Payload};
+#[doc(inline)]
 pub use moderate::{ChannelModerateV1, ChannelModerateV1Payload};
 #[doc(inline)]
 pub use moderate::{ChannelModerateV2, ChannelModerateV2Payload};
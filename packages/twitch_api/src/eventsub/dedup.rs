@@ -0,0 +1,326 @@
+//! Deduplication of EventSub messages
+//!
+//! Twitch may deliver a message more than once, for example when a webhook response was too slow or a WebSocket session was moved.
+//! Every delivery of a message has the same message id, a [`Deduplicator`] remembers the ids it has seen so duplicates can be dropped.
+//! Messages older than [`Deduplicator::max_age`] are dropped as well, as Twitch recommends, since their ids may already be forgotten.
+//!
+//! The ids are kept in a [`MessageIdStore`]. [`TtlCache`] keeps them in memory, other backends, like a store shared between multiple servers receiving the same subscriptions, can implement [`MessageIdStore`].
+//!
+//! The [webhook service](super::webhook) and the [WebSocket client](super::websocket) deduplicate messages by themselves. To deduplicate messages parsed with [`Event::parse_http`] or [`Event::parse_websocket`], use [`Deduplicator::parse_http`] and [`Deduplicator::parse_websocket`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use twitch_api::eventsub::dedup::Deduplicator;
+//!
+//! let dedup = Deduplicator::default();
+//! assert!(dedup.first_seen("befa7b53-d79d-478f-86b9-120f112b044e"));
+//! assert!(!dedup.first_seen("befa7b53-d79d-478f-86b9-120f112b044e"));
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{Event, EventsubWebsocketData, PayloadParseError};
+use crate::types;
+
+/// Storage for the ids of received messages
+pub trait MessageIdStore: Send + Sync {
+    /// Remember `id`, returns `false` if it has been seen before.
+    ///
+    /// Ids only need to be remembered for as long as Twitch may redeliver a message, which is 10 minutes.
+    fn insert(&self, id: &str) -> bool;
+    /// Forget `id`, so that the next delivery of the message is accepted
+    fn remove(&self, id: &str);
+}
+
+/// A [`MessageIdStore`] that keeps ids in memory for a fixed time
+///
+/// The cache is bounded, when it's full the oldest ids are forgotten first.
+#[derive(Debug)]
+pub struct TtlCache {
+    ttl: Duration,
+    capacity: usize,
+    inner: Mutex<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    ids: HashMap<String, Instant>,
+    /// Ids in the order they were inserted
+    order: VecDeque<(Instant, String)>,
+}
+
+impl Entries {
+    /// Forget the oldest id
+    fn pop(&mut self) -> Option<Instant> {
+        let (inserted, id) = self.order.pop_front()?;
+        self.ids.remove(&id);
+        Some(inserted)
+    }
+
+    /// Forget `id`
+    fn remove(&mut self, id: &str) {
+        if self.ids.remove(id).is_none() {
+            return;
+        }
+        // usually the id was inserted recently
+        if let Some(position) = self.order.iter().rposition(|(_, i)| i == id) {
+            self.order.remove(position);
+        }
+    }
+}
+
+impl TtlCache {
+    /// Create a cache remembering ids for `ttl`, holding at most `capacity` ids.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Amount of ids in the cache
+    pub fn len(&self) -> usize { self.entries().ids.len() }
+
+    /// Returns `true` if the cache holds no ids
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.inner
+            .lock()
+            .expect("message ids should not be poisoned")
+    }
+}
+
+impl Default for TtlCache {
+    /// A cache remembering ids for 10 minutes, holding at most 100000 ids.
+    fn default() -> Self { Self::new(Duration::from_secs(10 * 60), 100_000) }
+}
+
+impl MessageIdStore for TtlCache {
+    fn insert(&self, id: &str) -> bool {
+        let now = Instant::now();
+        let mut entries = self.entries();
+        while let Some((inserted, _)) = entries.order.front() {
+            if now.duration_since(*inserted) < self.ttl {
+                break;
+            }
+            entries.pop();
+        }
+        if entries.ids.contains_key(id) {
+            return false;
+        }
+        while entries.ids.len() >= self.capacity && entries.pop().is_some() {}
+        entries.ids.insert(id.to_owned(), now);
+        entries.order.push_back((now, id.to_owned()));
+        true
+    }
+
+    fn remove(&self, id: &str) { self.entries().remove(id); }
+}
+
+/// Returns `true` if `timestamp` is within `max_age` of now
+pub(crate) fn is_recent(timestamp: &types::TimestampRef, max_age: Duration) -> bool {
+    let timestamp = timestamp.to_utc().unix_timestamp_nanos();
+    let now = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(now) => now.as_nanos() as i128,
+        Err(_) => return false,
+    };
+    (now - timestamp).unsigned_abs() <= max_age.as_nanos()
+}
+
+/// Drops messages that have already been received or are too old, see the [module documentation](self).
+///
+/// Clones share the same store.
+#[derive(Clone)]
+pub struct Deduplicator {
+    store: Arc<dyn MessageIdStore>,
+    max_age: Duration,
+}
+
+impl std::fmt::Debug for Deduplicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deduplicator")
+            .field("max_age", &self.max_age)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Deduplicator {
+    fn default() -> Self { Self::new(TtlCache::default()) }
+}
+
+impl Deduplicator {
+    /// Create a deduplicator remembering ids in `store`
+    pub fn new(store: impl MessageIdStore + 'static) -> Self { Self::from_arc(Arc::new(store)) }
+
+    /// Create a deduplicator from a shared store
+    pub fn from_arc(store: Arc<dyn MessageIdStore>) -> Self {
+        Self {
+            store,
+            max_age: Duration::from_secs(10 * 60),
+        }
+    }
+
+    /// Set how old a message can be before it's dropped. Defaults to 10 minutes, as recommended by Twitch.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns `true` if a message sent at `timestamp` is within [`max_age`](Self::max_age) of now
+    pub fn is_recent(&self, timestamp: &types::TimestampRef) -> bool {
+        is_recent(timestamp, self.max_age)
+    }
+
+    /// Returns `true` if the message with this id has not been seen before, and remembers it.
+    pub fn first_seen(&self, message_id: &str) -> bool { self.store.insert(message_id) }
+
+    /// Forget a message id, so that the message is accepted again. Use this when a message could not be handled and should be redelivered.
+    pub fn forget(&self, message_id: &str) { self.store.remove(message_id) }
+
+    /// Returns `true` if the websocket message should be handled.
+    ///
+    /// Only notifications and revocations are deduplicated or dropped for their age, other messages are always handled.
+    pub fn first_seen_websocket(&self, data: &EventsubWebsocketData<'_>) -> bool {
+        match data {
+            EventsubWebsocketData::Notification { metadata, .. } => {
+                self.is_recent(&metadata.message_timestamp) && self.first_seen(&metadata.message_id)
+            }
+            EventsubWebsocketData::Revocation { metadata, .. } => {
+                self.is_recent(&metadata.message_timestamp) && self.first_seen(&metadata.message_id)
+            }
+            _ => true,
+        }
+    }
+
+    /// Parse a http payload with [`Event::parse_http`], returning `None` if the message has already been received or is older than [`max_age`](Self::max_age).
+    ///
+    /// Requests without a `Twitch-Eventsub-Message-Id` header are not deduplicated, requests without a `Twitch-Eventsub-Message-Timestamp` header are not checked for their age.
+    pub fn parse_http<B>(
+        &self,
+        request: &http::Request<B>,
+    ) -> Result<Option<Event>, PayloadParseError>
+    where
+        B: AsRef<[u8]>,
+    {
+        let event = Event::parse_http(request)?;
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        if let Some(timestamp) = header("Twitch-Eventsub-Message-Timestamp") {
+            match types::TimestampRef::from_str(timestamp) {
+                Ok(timestamp) if self.is_recent(timestamp) => {}
+                _ => return Ok(None),
+            }
+        }
+        match header("Twitch-Eventsub-Message-Id") {
+            Some(id) if !self.first_seen(id) => Ok(None),
+            _ => Ok(Some(event)),
+        }
+    }
+
+    /// Parse a websocket frame with [`Event::parse_websocket`], returning `None` if the message has already been received.
+    pub fn parse_websocket<'a>(
+        &self,
+        frame: &'a str,
+    ) -> Result<Option<EventsubWebsocketData<'a>>, PayloadParseError> {
+        let data = Event::parse_websocket(frame)?;
+        if self.first_seen_websocket(&data) {
+            Ok(Some(data))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttl_cache() {
+        let cache = TtlCache::new(Duration::from_secs(60), 2);
+        assert!(cache.insert("1"));
+        assert!(!cache.insert("1"));
+        cache.remove("1");
+        assert!(cache.insert("1"));
+        assert_eq!(cache.entries().order.len(), 1);
+        assert!(cache.insert("2"));
+        // full, "1" is forgotten
+        assert!(cache.insert("3"));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.insert("1"));
+        assert!(!cache.insert("3"));
+
+        let cache = TtlCache::new(Duration::ZERO, 2);
+        assert!(cache.insert("1"));
+        assert!(cache.insert("1"));
+    }
+
+    #[test]
+    fn websocket() {
+        let stale = r#"{"metadata":{"message_id":"befa7b53-d79d-478f-86b9-120f112b044e","message_type":"notification","message_timestamp":"2019-11-16T10:11:12.123Z","subscription_type":"channel.follow","subscription_version":"2"},"payload":{"subscription":{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","type":"channel.follow","version":"2","status":"enabled","cost":1,"condition":{"broadcaster_user_id":"12826","moderator_user_id":"12826"},"transport":{"method":"websocket","session_id":"AQoQexAWVYKSTIu4ec_2VAxyuhAB"},"created_at":"2019-11-16T10:11:12.123Z"},"event":{"user_id":"1337","user_login":"awesome_user","user_name":"Awesome_User","broadcaster_user_id":"12826","broadcaster_user_login":"twitch","broadcaster_user_name":"Twitch","followed_at":"2020-07-15T18:16:11.17106713Z"}}}"#;
+        let keepalive = r#"{"metadata":{"message_id":"8d8e0935-0e0c-479a-8fa2-ad4c02cef742","message_type":"session_keepalive","message_timestamp":"2022-11-04T13:11:49.770459792Z"},"payload":{}}"#;
+        let now = types::Timestamp::now();
+        // the first timestamp is the one of the message
+        let frame = stale.replacen("2019-11-16T10:11:12.123Z", now.as_str(), 1);
+        let dedup = Deduplicator::default();
+        assert!(dedup.parse_websocket(&frame).unwrap().is_some());
+        assert!(dedup.parse_websocket(&frame).unwrap().is_none());
+        dedup.forget("befa7b53-d79d-478f-86b9-120f112b044e");
+        assert!(dedup.parse_websocket(&frame).unwrap().is_some());
+        assert!(dedup.parse_websocket(keepalive).unwrap().is_some());
+        assert!(dedup.parse_websocket(keepalive).unwrap().is_some());
+
+        // stale messages are dropped without remembering their id
+        let dedup = Deduplicator::default();
+        assert!(dedup.parse_websocket(stale).unwrap().is_none());
+        assert!(dedup.parse_websocket(&frame).unwrap().is_some());
+
+        let dedup = Deduplicator::default().max_age(Duration::MAX);
+        assert!(dedup.parse_websocket(stale).unwrap().is_some());
+    }
+
+    #[test]
+    fn http() {
+        let body = r#"{"subscription":{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","type":"channel.follow","version":"2","status":"enabled","cost":0,"condition":{"broadcaster_user_id":"1337","moderator_user_id":"1337"},"transport":{"method":"webhook","callback":"https://example.com/webhooks/callback"},"created_at":"2019-11-16T10:11:12.123Z"},"event":{"user_id":"1234","user_login":"cool_user","user_name":"Cool_User","broadcaster_user_id":"1337","broadcaster_user_login":"cooler_user","broadcaster_user_name":"Cooler_User","followed_at":"2020-07-15T18:16:11.17106713Z"}}"#;
+        let request = |id: &str, timestamp: &str| {
+            http::Request::builder()
+                .header("Twitch-Eventsub-Message-Id", id)
+                .header("Twitch-Eventsub-Message-Retry", "0")
+                .header("Twitch-Eventsub-Message-Type", "notification")
+                .header("Twitch-Eventsub-Message-Timestamp", timestamp)
+                .header("Twitch-Eventsub-Subscription-Type", "channel.follow")
+                .header("Twitch-Eventsub-Subscription-Version", "2")
+                .body(body.as_bytes())
+                .unwrap()
+        };
+        let now = types::Timestamp::now();
+        let dedup = Deduplicator::default();
+        assert!(dedup
+            .parse_http(&request("1", now.as_str()))
+            .unwrap()
+            .is_some());
+        assert!(dedup
+            .parse_http(&request("1", now.as_str()))
+            .unwrap()
+            .is_none());
+
+        // stale messages are dropped without remembering their id
+        assert!(dedup
+            .parse_http(&request("2", "2019-11-16T10:11:12.123Z"))
+            .unwrap()
+            .is_none());
+        assert!(dedup
+            .parse_http(&request("2", now.as_str()))
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,176 @@
+#![doc(alias = "drop.entitlement.grant")]
+//! An entitlement for a Drop is granted to a user.
+use super::*;
+/// [`drop.entitlement.grant`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#dropentitlementgrant): an entitlement for a Drop is granted to a user.
+///
+/// Notifications for this subscription are batched, a single notification can contain multiple grants.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct DropEntitlementGrantV1 {
+    /// The organization ID of the organization that owns the game on the developer portal.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    pub organization_id: types::OrganizationId,
+    /// The category (or game) ID of the game for which entitlement notifications will be received.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::deserialize_none_from_empty_string"
+    )]
+    pub category_id: Option<types::CategoryId>,
+    /// The campaign ID for a specific campaign for which entitlement notifications will be received.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::deserialize_none_from_empty_string"
+    )]
+    pub campaign_id: Option<types::EntitlementCampaignId>,
+}
+
+impl DropEntitlementGrantV1 {
+    /// Get notifications for entitlements granted in games owned by this organization
+    pub fn organization_id(organization_id: impl Into<types::OrganizationId>) -> Self {
+        Self {
+            organization_id: organization_id.into(),
+            category_id: None,
+            campaign_id: None,
+        }
+    }
+
+    /// Only get notifications for entitlements in this category (or game)
+    pub fn category_id(mut self, category_id: impl Into<types::CategoryId>) -> Self {
+        self.category_id = Some(category_id.into());
+        self
+    }
+
+    /// Only get notifications for entitlements in this campaign
+    pub fn campaign_id(mut self, campaign_id: impl Into<types::EntitlementCampaignId>) -> Self {
+        self.campaign_id = Some(campaign_id.into());
+        self
+    }
+}
+
+impl EventSubscription for DropEntitlementGrantV1 {
+    type Payload = Vec<DropEntitlementGrantV1Payload>;
+
+    const EVENT_TYPE: EventType = EventType::DropEntitlementGrant;
+    const IS_BATCHING_ENABLED: bool = true;
+    #[cfg(feature = "twitch_oauth2")]
+    /// App access token where the client ID is associated with a user on the Twitch developer console.
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+    const VERSION: &'static str = "1";
+}
+
+/// A single grant in a [`drop.entitlement.grant`](DropEntitlementGrantV1) notification.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct DropEntitlementGrantV1Payload {
+    /// Individual event ID, as assigned by EventSub. Use this for de-duplicating messages.
+    pub id: String,
+    /// Entitlement object.
+    pub data: DropEntitlementGrantData,
+}
+
+/// Entitlement granted in a [`drop.entitlement.grant`](DropEntitlementGrantV1) notification.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct DropEntitlementGrantData {
+    /// The ID of the organization that owns the game that has Drops enabled.
+    pub organization_id: types::OrganizationId,
+    /// Twitch category ID of the game that was being played when this benefit was entitled.
+    pub category_id: types::CategoryId,
+    /// The category name.
+    pub category_name: String,
+    /// The campaign this entitlement is associated with.
+    pub campaign_id: types::EntitlementCampaignId,
+    /// Twitch user ID of the user who was granted the entitlement.
+    pub user_id: types::UserId,
+    /// The user display name of the user who was granted the entitlement.
+    pub user_name: types::DisplayName,
+    /// The user login of the user who was granted the entitlement.
+    pub user_login: types::UserName,
+    /// Unique identifier of the entitlement. Use this to de-duplicate entitlements.
+    pub entitlement_id: types::EntitlementId,
+    /// Identifier of the Benefit.
+    pub benefit_id: types::BenefitId,
+    /// UTC timestamp in ISO format when this entitlement was granted on Twitch.
+    pub created_at: types::Timestamp,
+}
+
+#[cfg(test)]
+#[test]
+fn parse_payload() {
+    let payload = r##"
+    {
+        "subscription": {
+            "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+            "type": "drop.entitlement.grant",
+            "version": "1",
+            "status": "enabled",
+            "cost": 0,
+            "condition": {
+                "organization_id": "9001",
+                "category_id": "9002",
+                "campaign_id": ""
+            },
+            "transport": {
+                "method": "webhook",
+                "callback": "https://example.com/webhooks/callback"
+            },
+            "created_at": "2019-11-16T10:11:12.634234626Z"
+        },
+        "events": [
+            {
+                "id": "bf7c8577-e3e2-4fd1-a5d6-2c7c6f9d4f7c",
+                "data": {
+                    "organization_id": "9001",
+                    "category_id": "9002",
+                    "category_name": "Fortnite",
+                    "campaign_id": "9003",
+                    "user_id": "1234",
+                    "user_name": "Cool_User",
+                    "user_login": "cool_user",
+                    "entitlement_id": "fb78259e-fb81-4d1b-8333-34a06ffc24c0",
+                    "benefit_id": "74c52265-e214-48a6-91b9-23b6014e8041",
+                    "created_at": "2019-01-28T04:17:53.325Z"
+                }
+            },
+            {
+                "id": "a0a1f2c3-d4e5-4f6a-8b9c-0d1e2f3a4b5c",
+                "data": {
+                    "organization_id": "9001",
+                    "category_id": "9002",
+                    "category_name": "Fortnite",
+                    "campaign_id": "9003",
+                    "user_id": "5678",
+                    "user_name": "Cooler_User",
+                    "user_login": "cooler_user",
+                    "entitlement_id": "862750a5-265e-4ab6-9f0a-c64df3d54dd0",
+                    "benefit_id": "74c52265-e214-48a6-91b9-23b6014e8041",
+                    "created_at": "2019-01-28T04:17:53.325Z"
+                }
+            }
+        ]
+    }
+    "##;
+
+    let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
+    crate::tests::roundtrip(&val);
+
+    match val {
+        crate::eventsub::Event::DropEntitlementGrantV1(crate::eventsub::Payload {
+            subscription,
+            message: crate::eventsub::Message::Notification(events),
+        }) => {
+            assert_eq!(subscription.condition.campaign_id, None);
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[1].data.user_login.as_str(), "cooler_user");
+        }
+        _ => panic!("unexpected event"),
+    }
+}
@@ -0,0 +1,7 @@
+#![doc(alias = "drop.entitlement")]
+//! Subscription types regarding Drops entitlements.
+use super::{EventSubscription, EventType};
+use crate::types;
+use serde_derive::{Deserialize, Serialize};
+
+pub mod grant;
@@ -0,0 +1,9 @@
+//! Subscription types regarding Drops.
+use super::{EventSubscription, EventType};
+
+pub mod entitlement;
+
+#[doc(inline)]
+pub use entitlement::grant::{
+    DropEntitlementGrantData, DropEntitlementGrantV1, DropEntitlementGrantV1Payload,
+};
@@ -47,8 +47,11 @@ macro_rules! fill_events {
             #[cfg(feature = "beta")]
             channel::ChannelGuestStarSettingsUpdateBeta;
             channel::ChannelHypeTrainBeginV1;
+            channel::ChannelHypeTrainBeginV2;
             channel::ChannelHypeTrainEndV1;
+            channel::ChannelHypeTrainEndV2;
             channel::ChannelHypeTrainProgressV1;
+            channel::ChannelHypeTrainProgressV2;
             channel::ChannelModerateV1;
             channel::ChannelModerateV2;
             channel::ChannelModeratorAddV1;
@@ -90,6 +93,8 @@ macro_rules! fill_events {
             channel::ChannelWarningAcknowledgeV1;
             channel::ChannelWarningSendV1;
             conduit::ConduitShardDisabledV1;
+            drop::DropEntitlementGrantV1;
+            extension::ExtensionBitsTransactionCreateV1;
             stream::StreamOfflineV1;
             stream::StreamOnlineV1;
             user::UserAuthorizationGrantV1;
@@ -310,6 +315,10 @@ make_event_type!("Event Types": pub enum EventType {
     ChannelVipRemove => "channel.vip.remove",
     "sends a notification when eventsub disables a shard due to the status of the underlying transport changing.":
     ConduitShardDisabled => "conduit.shard.disabled",
+    "an entitlement for a Drop is granted to a user.":
+    DropEntitlementGrant => "drop.entitlement.grant",
+    "a Bits transaction occurred for a specified Twitch Extension.":
+    ExtensionBitsTransactionCreate => "extension.bits_transaction.create",
     "the specified broadcaster starts a stream.":
     StreamOnline => "stream.online",
     "the specified broadcaster stops a stream.":
@@ -483,10 +492,16 @@ pub enum Event {
     ChannelGuestStarGuestUpdateBeta(Payload<channel::ChannelGuestStarGuestUpdateBeta>),
     /// Channel Hype Train Begin V1 Event
     ChannelHypeTrainBeginV1(Payload<channel::ChannelHypeTrainBeginV1>),
+    /// Channel Hype Train Begin V2 Event
+    ChannelHypeTrainBeginV2(Payload<channel::ChannelHypeTrainBeginV2>),
     /// Channel Hype Train Progress V1 Event
     ChannelHypeTrainProgressV1(Payload<channel::ChannelHypeTrainProgressV1>),
+    /// Channel Hype Train Progress V2 Event
+    ChannelHypeTrainProgressV2(Payload<channel::ChannelHypeTrainProgressV2>),
     /// Channel Hype Train End V1 Event
     ChannelHypeTrainEndV1(Payload<channel::ChannelHypeTrainEndV1>),
+    /// Channel Hype Train End V2 Event
+    ChannelHypeTrainEndV2(Payload<channel::ChannelHypeTrainEndV2>),
     /// Channel Moderate V1 Event
     ChannelModerateV1(Payload<channel::ChannelModerateV1>),
     /// Channel Moderate V2 Event
@@ -497,6 +512,10 @@ pub enum Event {
     ChannelModeratorRemoveV1(Payload<channel::ChannelModeratorRemoveV1>),
     /// Conduit Shard Disabled V1 Event
     ConduitShardDisabledV1(Payload<conduit::ConduitShardDisabledV1>),
+    /// Drop Entitlement Grant V1 Event
+    DropEntitlementGrantV1(Payload<drop::DropEntitlementGrantV1>),
+    /// Extension Bits Transaction Create V1 Event
+    ExtensionBitsTransactionCreateV1(Payload<extension::ExtensionBitsTransactionCreateV1>),
     /// StreamOnline V1 Event
     StreamOnlineV1(Payload<stream::StreamOnlineV1>),
     /// StreamOffline V1 Event
@@ -641,8 +660,7 @@ impl Event {
         if let Some((message, signature)) = message_and_signature(request) {
             let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret).expect("");
             mac.update(&message);
-            mac.verify(crypto_hmac::digest::generic_array::GenericArray::from_slice(&signature))
-                .is_ok()
+            mac.verify_slice(&signature).is_ok()
         } else {
             false
         }
@@ -670,6 +688,7 @@ fn get_version_event_type_and_message_type_from_text(
     struct IEvent {
         subscription: IEventSubscripionInformation,
         challenge: Option<serde_json::Value>,
+        #[serde(alias = "events")]
         event: Option<serde_json::Value>,
     }
 
@@ -0,0 +1,91 @@
+#![doc(alias = "extension.bits_transaction.create")]
+//! A Bits transaction occurred for a specified Twitch Extension.
+use super::*;
+/// [`extension.bits_transaction.create`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#extensionbits_transactioncreate): a Bits transaction occurred for a specified Twitch Extension.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionBitsTransactionCreateV1 {
+    /// The client ID of the extension.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    pub extension_client_id: String,
+}
+
+impl ExtensionBitsTransactionCreateV1 {
+    /// Get notifications for Bits transactions in the extension with this client ID
+    pub fn extension_client_id(extension_client_id: impl Into<String>) -> Self {
+        Self {
+            extension_client_id: extension_client_id.into(),
+        }
+    }
+}
+
+impl EventSubscription for ExtensionBitsTransactionCreateV1 {
+    type Payload = ExtensionBitsTransactionCreateV1Payload;
+
+    const EVENT_TYPE: EventType = EventType::ExtensionBitsTransactionCreate;
+    #[cfg(feature = "twitch_oauth2")]
+    /// App access token where the client ID matches the client ID in the condition.
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+    const VERSION: &'static str = "1";
+}
+
+/// [`extension.bits_transaction.create`](ExtensionBitsTransactionCreateV1) response payload.
+///
+/// This is the same [`BitsTransaction`](types::BitsTransaction) returned by [Get Extension Transactions](crate::helix::bits::get_extension_transactions).
+pub type ExtensionBitsTransactionCreateV1Payload = types::BitsTransaction;
+
+#[cfg(test)]
+#[test]
+fn parse_payload() {
+    let payload = r##"
+    {
+        "subscription": {
+            "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+            "type": "extension.bits_transaction.create",
+            "version": "1",
+            "status": "enabled",
+            "cost": 0,
+            "condition": {
+                "extension_client_id": "deadbeef"
+            },
+            "transport": {
+                "method": "webhook",
+                "callback": "https://example.com/webhooks/callback"
+            },
+            "created_at": "2019-11-16T10:11:12.634234626Z"
+        },
+        "event": {
+            "extension_client_id": "deadbeef",
+            "id": "bits-tx-id",
+            "broadcaster_user_id": "1337",
+            "broadcaster_user_login": "cool_user",
+            "broadcaster_user_name": "Cool_User",
+            "user_name": "Coolest_User",
+            "user_login": "coolest_user",
+            "user_id": "1236",
+            "product": {
+                "name": "great_product",
+                "sku": "skuskusku",
+                "bits": 1234,
+                "in_development": false
+            }
+        }
+    }
+    "##;
+
+    let val = dbg!(crate::eventsub::Event::parse(payload).unwrap());
+    crate::tests::roundtrip(&val);
+
+    match val {
+        crate::eventsub::Event::ExtensionBitsTransactionCreateV1(crate::eventsub::Payload {
+            message: crate::eventsub::Message::Notification(transaction),
+            ..
+        }) => {
+            assert_eq!(transaction.product.bits, 1234);
+            assert_eq!(transaction.timestamp, None);
+        }
+        _ => panic!("unexpected event"),
+    }
+}
@@ -0,0 +1,7 @@
+#![doc(alias = "extension.bits_transaction")]
+//! Subscription types regarding Bits transactions in extensions.
+use super::{EventSubscription, EventType};
+use crate::types;
+use serde_derive::{Deserialize, Serialize};
+
+pub mod create;
@@ -0,0 +1,9 @@
+//! Subscription types regarding extensions.
+use super::{EventSubscription, EventType};
+
+pub mod bits_transaction;
+
+#[doc(inline)]
+pub use bits_transaction::create::{
+    ExtensionBitsTransactionCreateV1, ExtensionBitsTransactionCreateV1Payload,
+};
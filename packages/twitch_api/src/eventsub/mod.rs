@@ -128,8 +128,11 @@
 //! | [`channel.guest_star_session.end`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelguest_star_sessionend) (beta) | [ChannelGuestStarSessionEndBeta](channel::ChannelGuestStarSessionEndBeta)<br>[ChannelGuestStarSessionEndBetaPayload](channel::ChannelGuestStarSessionEndBetaPayload) |
 //! | [`channel.guest_star_settings.update`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelguest_star_settingsupdate) (beta) | [ChannelGuestStarSettingsUpdateBeta](channel::ChannelGuestStarSettingsUpdateBeta)<br>[ChannelGuestStarSettingsUpdateBetaPayload](channel::ChannelGuestStarSettingsUpdateBetaPayload) |
 //! | [`channel.hype_train.begin`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainbegin) (v1) | [ChannelHypeTrainBeginV1](channel::ChannelHypeTrainBeginV1)<br>[ChannelHypeTrainBeginV1Payload](channel::ChannelHypeTrainBeginV1Payload) |
+//! | [`channel.hype_train.begin`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainbegin-v2) (v2) | [ChannelHypeTrainBeginV2](channel::ChannelHypeTrainBeginV2)<br>[ChannelHypeTrainBeginV2Payload](channel::ChannelHypeTrainBeginV2Payload) |
 //! | [`channel.hype_train.end`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainend) (v1) | [ChannelHypeTrainEndV1](channel::ChannelHypeTrainEndV1)<br>[ChannelHypeTrainEndV1Payload](channel::ChannelHypeTrainEndV1Payload) |
+//! | [`channel.hype_train.end`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainend-v2) (v2) | [ChannelHypeTrainEndV2](channel::ChannelHypeTrainEndV2)<br>[ChannelHypeTrainEndV2Payload](channel::ChannelHypeTrainEndV2Payload) |
 //! | [`channel.hype_train.progress`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainprogress) (v1) | [ChannelHypeTrainProgressV1](channel::ChannelHypeTrainProgressV1)<br>[ChannelHypeTrainProgressV1Payload](channel::ChannelHypeTrainProgressV1Payload) |
+//! | [`channel.hype_train.progress`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelhype_trainprogress-v2) (v2) | [ChannelHypeTrainProgressV2](channel::ChannelHypeTrainProgressV2)<br>[ChannelHypeTrainProgressV2Payload](channel::ChannelHypeTrainProgressV2Payload) |
 //! | [`channel.moderate`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelmoderate) (v1) | [ChannelModerateV1](channel::ChannelModerateV1)<br>[ChannelModerateV1Payload](channel::ChannelModerateV1Payload) |
 //! | [`channel.moderate`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelmoderate-v2) (v2) | [ChannelModerateV2](channel::ChannelModerateV2)<br>[ChannelModerateV2Payload](channel::ChannelModerateV2Payload) |
 //! | [`channel.moderator.add`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#channelmoderatoradd) (v1) | [ChannelModeratorAddV1](channel::ChannelModeratorAddV1)<br>[ChannelModeratorAddV1Payload](channel::ChannelModeratorAddV1Payload) |
@@ -174,19 +177,19 @@
 //!
 //! </details>
 //!
-//! <details><summary style="cursor: pointer"><code style="color: var(--link-color)">drop.*</code> 🔴 0/1</summary>
+//! <details><summary style="cursor: pointer"><code style="color: var(--link-color)">drop.*</code> 🟢 1/1</summary>
 //!
 //! | Name | Subscription<br>Payload |
 //! |---|:---|
-//! | [`drop.entitlement.grant`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#dropentitlementgrant) (v1) | -<br>- |
+//! | [`drop.entitlement.grant`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#dropentitlementgrant) (v1) | [DropEntitlementGrantV1](drop::DropEntitlementGrantV1)<br>[DropEntitlementGrantV1Payload](drop::DropEntitlementGrantV1Payload) |
 //!
 //! </details>
 //!
-//! <details><summary style="cursor: pointer"><code style="color: var(--link-color)">extension.*</code> 🔴 0/1</summary>
+//! <details><summary style="cursor: pointer"><code style="color: var(--link-color)">extension.*</code> 🟢 1/1</summary>
 //!
 //! | Name | Subscription<br>Payload |
 //! |---|:---|
-//! | [`extension.bits_transaction.create`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#extensionbits_transactioncreate) (v1) | -<br>- |
+//! | [`extension.bits_transaction.create`](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types#extensionbits_transactioncreate) (v1) | [ExtensionBitsTransactionCreateV1](extension::ExtensionBitsTransactionCreateV1)<br>[ExtensionBitsTransactionCreateV1Payload](extension::ExtensionBitsTransactionCreateV1Payload) |
 //!
 //! </details>
 //!
@@ -223,9 +226,18 @@ use crate::parse_json;
 pub mod automod;
 pub mod channel;
 pub mod conduit;
+pub mod dedup;
+pub mod drop;
 pub mod event;
+pub mod extension;
+#[cfg(all(feature = "helix", feature = "client"))]
+pub mod reconcile;
 pub mod stream;
 pub mod user;
+#[cfg(feature = "eventsub_webhook")]
+pub mod webhook;
+#[cfg(feature = "eventsub_websocket")]
+pub mod websocket;
 
 #[doc(inline)]
 pub use event::{Event, EventType};
@@ -247,6 +259,10 @@ pub trait EventSubscription: DeserializeOwned + serde::Serialize + PartialEq + C
     const VERSION: &'static str;
     /// Subscription type name.
     const EVENT_TYPE: EventType;
+    /// Whether notifications for this subscription are batched.
+    ///
+    /// Batched notifications carry multiple events at once, their [`Payload`](EventSubscription::Payload) is a list of events.
+    const IS_BATCHING_ENABLED: bool = false;
 
     /// Creates the [`condition`](https://dev.twitch.tv/docs/eventsub/eventsub-reference#conditions) for this EventSub subscription
     fn condition(&self) -> Result<serde_json::Value, serde_json::Error> {
@@ -349,7 +365,7 @@ impl<E: EventSubscription> Payload<E> {
         struct Notification<E: EventSubscription> {
             #[serde(bound = "E: EventSubscription")]
             pub subscription: EventSubscriptionInformation<E>,
-            #[serde(bound = "E: EventSubscription")]
+            #[serde(bound = "E: EventSubscription", alias = "events")]
             pub event: <E as EventSubscription>::Payload,
         }
 
@@ -1339,9 +1355,16 @@ mod test {
         let body = r#"{"subscription":{"id":"ae2ff348-e102-16be-a3eb-6830c1bf38d2","status":"enabled","type":"channel.follow","version":"1","condition":{"broadcaster_user_id":"44429626"},"transport":{"method":"webhook","callback":"null"},"created_at":"2021-02-19T23:47:00.7621315Z"},"event":{"user_id":"28408015","user_login":"testFromUser","user_name":"testFromUser","broadcaster_user_id":"44429626","broadcaster_user_login":"44429626","broadcaster_user_name":"testBroadcaster"}}"#;
         let mut request = http::Request::builder();
         let _ = std::mem::replace(request.headers_mut().unwrap(), headers);
-        let request = request.body(body.as_bytes().to_vec()).unwrap();
+        let mut request = request.body(body.as_bytes().to_vec()).unwrap();
         dbg!(&body);
         assert!(crate::eventsub::Event::verify_payload(&request, secret));
+
+        // a truncated signature is rejected
+        request.headers_mut().insert(
+            "Twitch-Eventsub-Message-Signature",
+            HeaderValue::from_static("sha256=d10f5bd9"),
+        );
+        assert!(!crate::eventsub::Event::verify_payload(&request, secret));
     }
 
     #[test]
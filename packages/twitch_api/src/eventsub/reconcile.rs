@@ -0,0 +1,543 @@
+//! Keep EventSub subscriptions in sync with a declared set
+//!
+//! A [`Reconciler`] holds the subscriptions that should exist on a [transport](Transport).
+//! [`Reconciler::reconcile`] compares them with the subscriptions returned by [`get_eventsub_subscriptions`](HelixClient::get_eventsub_subscriptions) and makes the live state match:
+//!
+//! * subscriptions on the transport that are not declared are deleted.
+//! * duplicates of a declared subscription are deleted.
+//! * declared subscriptions that are missing, or that [failed or were revoked](Status), are created again.
+//!
+//! Only subscriptions delivered to the same transport, that is the same webhook callback, WebSocket session or conduit, are touched.
+//! A subscription that is already gone when it's deleted, or already exists when it's created, counts as done.
+//! Other failures don't stop the rest of the changes, they are collected in [`Reconciled::failed`].
+//! Reconciling is idempotent, so it can simply be run again to retry them.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use twitch_api::{
+//!     eventsub::{self, reconcile::Reconciler, Transport},
+//!     HelixClient,
+//! };
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//! let client: HelixClient<'static, reqwest::Client> = HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let secret = twitch_oauth2::ClientSecret::new("secret".to_string());
+//! # let token = twitch_oauth2::AppAccessToken::from_existing(&client, token, None, secret).await?;
+//! let transport = Transport::webhook(
+//!     "https://example.org/twitch/eventsub",
+//!     "secretabcd".to_string(),
+//! );
+//! let reconciled = Reconciler::new(transport)
+//!     .subscribe(eventsub::stream::StreamOnlineV1::broadcaster_user_id("1234"))
+//!     .subscribe(eventsub::stream::StreamOfflineV1::broadcaster_user_id("1234"))
+//!     .reconcile(&client, &token)
+//!     .await?;
+//! println!(
+//!     "created {}, deleted {}",
+//!     reconciled.created.len(),
+//!     reconciled.deleted.len()
+//! );
+//! for failed in reconciled.failed {
+//!     eprintln!("{} {}: {}", failed.event_type, failed.condition, failed.error);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use futures::TryStreamExt;
+use twitch_oauth2::TwitchToken;
+
+use super::{EventSubscription, EventType, Status, Transport, TransportResponse};
+use crate::client::BoxedFuture;
+use crate::helix::{
+    ClientRequestError, HelixClient, HelixRequestDeleteError, HelixRequestPostError,
+};
+use crate::types;
+use crate::HttpClient;
+
+/// A subscription that should exist
+trait Declared<C: HttpClient, T>: Send + Sync {
+    fn event_type(&self) -> EventType;
+
+    fn version(&self) -> &'static str;
+
+    fn condition(&self) -> serde_json::Value;
+
+    fn create<'a>(
+        &'a self,
+        helix: &'a HelixClient<'a, C>,
+        transport: &'a Transport,
+        token: &'a T,
+    ) -> BoxedFuture<'a, Result<types::EventSubId, ClientRequestError<C::Error>>>;
+}
+
+impl<E, C, T> Declared<C, T> for E
+where
+    E: EventSubscription + Send + Sync,
+    C: HttpClient + Sync,
+    T: TwitchToken + Send + Sync,
+{
+    fn event_type(&self) -> EventType { E::EVENT_TYPE }
+
+    fn version(&self) -> &'static str { E::VERSION }
+
+    fn condition(&self) -> serde_json::Value {
+        EventSubscription::condition(self).unwrap_or_default()
+    }
+
+    fn create<'a>(
+        &'a self,
+        helix: &'a HelixClient<'a, C>,
+        transport: &'a Transport,
+        token: &'a T,
+    ) -> BoxedFuture<'a, Result<types::EventSubId, ClientRequestError<C::Error>>> {
+        Box::pin(async move {
+            helix
+                .create_eventsub_subscription(self.clone(), transport.clone(), token)
+                .await
+                .map(|subscription| subscription.id)
+        })
+    }
+}
+
+/// The outcome of [`Reconciler::reconcile`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Reconciled<RE: std::error::Error + Send + Sync + 'static> {
+    /// Subscriptions that already existed
+    pub kept: Vec<types::EventSubId>,
+    /// Subscriptions that were created. Subscriptions that were created by someone else in the meantime are not included.
+    pub created: Vec<types::EventSubId>,
+    /// Subscriptions that were deleted, because they were not declared, duplicates, failed or revoked
+    pub deleted: Vec<types::EventSubId>,
+    /// Subscriptions that could not be deleted or created
+    pub failed: Vec<Failed<RE>>,
+}
+
+impl<RE: std::error::Error + Send + Sync + 'static> Default for Reconciled<RE> {
+    fn default() -> Self {
+        Self {
+            kept: vec![],
+            created: vec![],
+            deleted: vec![],
+            failed: vec![],
+        }
+    }
+}
+
+/// A subscription that [`Reconciler::reconcile`] could not delete or create
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Failed<RE: std::error::Error + Send + Sync + 'static> {
+    /// Id of the subscription that could not be deleted, `None` if it could not be created
+    pub id: Option<types::EventSubId>,
+    /// Type of the subscription
+    pub event_type: EventType,
+    /// Condition of the subscription
+    pub condition: serde_json::Value,
+    /// Why the request failed
+    pub error: ClientRequestError<RE>,
+}
+
+/// Syncs the subscriptions on a transport, see the [module documentation](self).
+pub struct Reconciler<C: HttpClient, T> {
+    transport: Transport,
+    subscriptions: Vec<Box<dyn Declared<C, T>>>,
+}
+
+impl<C: HttpClient, T> std::fmt::Debug for Reconciler<C, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reconciler")
+            .field("transport", &self.transport)
+            .field("subscriptions", &self.subscriptions.len())
+            .finish()
+    }
+}
+
+impl<C, T> Reconciler<C, T>
+where
+    C: HttpClient + Sync,
+    T: TwitchToken + Send + Sync,
+{
+    /// Create a reconciler for the subscriptions on `transport`
+    ///
+    /// Webhook and conduit transports need an app access token, WebSocket transports a user access token.
+    pub fn new(transport: Transport) -> Self {
+        Self {
+            transport,
+            subscriptions: vec![],
+        }
+    }
+
+    /// Declare a subscription that should exist. Declaring the same subscription twice has no effect.
+    pub fn subscribe<E>(mut self, subscription: E) -> Self
+    where E: EventSubscription + Send + Sync + 'static {
+        let condition = Declared::<C, T>::condition(&subscription);
+        let declared = self.subscriptions.iter().any(|existing| {
+            existing.event_type() == E::EVENT_TYPE
+                && existing.version() == E::VERSION
+                && same_condition(&existing.condition(), &condition)
+        });
+        if !declared {
+            self.subscriptions.push(Box::new(subscription));
+        }
+        self
+    }
+
+    /// Create and delete subscriptions so that the subscriptions on the transport are the declared ones
+    ///
+    /// Subscriptions are deleted before new ones are created, so that deleted subscriptions don't count against the cost limit.
+    ///
+    /// Fails only if the live subscriptions can't be fetched, failures to delete or create a subscription are collected in [`Reconciled::failed`].
+    pub async fn reconcile(
+        &self,
+        helix: &HelixClient<'_, C>,
+        token: &T,
+    ) -> Result<Reconciled<C::Error>, ClientRequestError<C::Error>> {
+        let mut live: Vec<super::EventSubSubscription> = helix
+            .get_eventsub_subscriptions(None, None, None, token)
+            .map_ok(|response| {
+                futures::stream::iter(
+                    response
+                        .subscriptions
+                        .into_iter()
+                        .map(Ok::<_, ClientRequestError<C::Error>>),
+                )
+            })
+            .try_flatten()
+            .try_collect()
+            .await?;
+        live.retain(|subscription| same_target(&self.transport, &subscription.transport));
+
+        let mut reconciled = Reconciled::default();
+        let mut missing = vec![];
+        for declared in &self.subscriptions {
+            let condition = declared.condition();
+            let existing = live.iter().position(|subscription| {
+                is_active(&subscription.status)
+                    && subscription.type_ == declared.event_type()
+                    && subscription.version == declared.version()
+                    && same_condition(&condition, &subscription.condition)
+            });
+            match existing {
+                Some(index) => reconciled.kept.push(live.swap_remove(index).id),
+                None => missing.push(declared),
+            }
+        }
+
+        // what's left is not declared, a duplicate, or no longer active
+        for subscription in live {
+            match helix
+                .delete_eventsub_subscription(&subscription.id, token)
+                .await
+            {
+                Ok(_) => reconciled.deleted.push(subscription.id),
+                // already deleted, for example by a revocation or another instance
+                Err(e) if has_status(&e, http::StatusCode::NOT_FOUND) => {
+                    reconciled.deleted.push(subscription.id)
+                }
+                Err(error) => reconciled.failed.push(Failed {
+                    id: Some(subscription.id),
+                    event_type: subscription.type_,
+                    condition: subscription.condition,
+                    error,
+                }),
+            }
+        }
+
+        for declared in missing {
+            match declared.create(helix, &self.transport, token).await {
+                Ok(id) => reconciled.created.push(id),
+                // created by another instance in the meantime
+                Err(e) if has_status(&e, http::StatusCode::CONFLICT) => {}
+                Err(error) => reconciled.failed.push(Failed {
+                    id: None,
+                    event_type: declared.event_type(),
+                    condition: declared.condition(),
+                    error,
+                }),
+            }
+        }
+        Ok(reconciled)
+    }
+}
+
+/// Returns `true` if the subscription is delivering, or will be once its callback is verified
+fn is_active(status: &Status) -> bool {
+    matches!(
+        status,
+        Status::Enabled | Status::WebhookCallbackVerificationPending
+    )
+}
+
+/// Returns `true` if a delete or create request was answered with `expected`
+fn has_status<RE: std::error::Error + Send + Sync + 'static>(
+    error: &ClientRequestError<RE>,
+    expected: http::StatusCode,
+) -> bool {
+    match error {
+        ClientRequestError::HelixRequestDeleteError(
+            HelixRequestDeleteError::Error { status, .. }
+            | HelixRequestDeleteError::InvalidResponse { status, .. },
+        )
+        | ClientRequestError::HelixRequestPostError(
+            HelixRequestPostError::Error { status, .. }
+            | HelixRequestPostError::InvalidResponse { status, .. },
+        ) => *status == expected,
+        _ => false,
+    }
+}
+
+/// Returns `true` if the subscription is delivered to `transport`
+fn same_target(transport: &Transport, response: &TransportResponse) -> bool {
+    match (transport, response) {
+        (Transport::Webhook(transport), TransportResponse::Webhook(response)) => {
+            transport.callback == response.callback
+        }
+        (Transport::Websocket(transport), TransportResponse::Websocket(response)) => {
+            transport.session_id == response.session_id
+        }
+        (Transport::Conduit(transport), TransportResponse::Conduit(response)) => {
+            transport.conduit_id == response.conduit_id
+        }
+        _ => false,
+    }
+}
+
+/// Compare two conditions, Twitch returns optional fields that were not set as empty strings
+fn same_condition(declared: &serde_json::Value, live: &serde_json::Value) -> bool {
+    let unset = |value: Option<&serde_json::Value>| match value {
+        None | Some(serde_json::Value::Null) => true,
+        Some(serde_json::Value::String(s)) => s.is_empty(),
+        _ => false,
+    };
+    match (declared.as_object(), live.as_object()) {
+        (Some(declared), Some(live)) => declared.keys().chain(live.keys()).all(|key| {
+            let (a, b) = (declared.get(key), live.get(key));
+            a == b || unset(a) && unset(b)
+        }),
+        _ => declared == live,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Answers with `subscriptions` and records the requests
+    struct ReconcileClient {
+        subscriptions: serde_json::Value,
+        /// Requests to answer with an error status
+        errors: Vec<(&'static str, u16)>,
+        requests: Mutex<Vec<(http::Method, String)>>,
+    }
+
+    impl HttpClient for ReconcileClient {
+        type Error = std::io::Error;
+
+        fn req(
+            &self,
+            req: crate::client::Request,
+        ) -> BoxedFuture<'_, Result<crate::client::Response, Self::Error>> {
+            let method = req.method().clone();
+            let target = match method {
+                http::Method::POST => {
+                    let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+                    format!(
+                        "{} {}",
+                        body["type"].as_str().unwrap(),
+                        body["condition"]["broadcaster_user_id"].as_str().unwrap()
+                    )
+                }
+                _ => req.uri().query().unwrap_or_default().to_owned(),
+            };
+            self.requests
+                .lock()
+                .unwrap()
+                .push((method.clone(), target.clone()));
+            let error = self.errors.iter().find(|(t, _)| *t == target);
+            let (status, body) = match method {
+                _ if error.is_some() => {
+                    let status = error.unwrap().1;
+                    (
+                        status,
+                        serde_json::json!({"error": "Error", "status": status, "message": ""}),
+                    )
+                }
+                http::Method::GET => (
+                    200,
+                    serde_json::json!({
+                        "data": self.subscriptions,
+                        "total": 0,
+                        "total_cost": 0,
+                        "max_total_cost": 10000,
+                        "pagination": {},
+                    }),
+                ),
+                http::Method::DELETE => (204, serde_json::Value::Null),
+                _ => (
+                    202,
+                    serde_json::json!({
+                        "data": [subscription("new", target.split(' ').next().unwrap(), "enabled", "https://example.org")],
+                        "total": 1,
+                        "total_cost": 1,
+                        "max_total_cost": 10000,
+                    }),
+                ),
+            };
+            let body = match body {
+                serde_json::Value::Null => vec![],
+                body => serde_json::to_vec(&body).unwrap(),
+            };
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(status)
+                    .body(body.into())
+                    .unwrap())
+            })
+        }
+    }
+
+    fn subscription(id: &str, type_: &str, status: &str, callback: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "status": status,
+            "type": type_,
+            "version": "1",
+            "cost": 1,
+            "condition": {"broadcaster_user_id": "1234"},
+            "transport": {"method": "webhook", "callback": callback},
+            "created_at": "2020-11-10T14:32:18.730260295Z",
+        })
+    }
+
+    fn token() -> twitch_oauth2::AppAccessToken {
+        twitch_oauth2::AppAccessToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            twitch_oauth2::ClientSecret::new("secret".to_string()),
+            None,
+            None,
+        )
+    }
+
+    fn ids(ids: &[types::EventSubId]) -> Vec<String> {
+        let mut ids: Vec<_> = ids.iter().map(|id| id.as_str().to_owned()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn reconciles() {
+        let callback = "https://example.org";
+        let client = ReconcileClient {
+            subscriptions: serde_json::json!([
+                subscription("online", "stream.online", "enabled", callback),
+                // duplicate
+                subscription("online2", "stream.online", "enabled", callback),
+                // revoked
+                subscription(
+                    "offline",
+                    "stream.offline",
+                    "authorization_revoked",
+                    callback
+                ),
+                // not declared
+                subscription("update", "channel.update", "enabled", callback),
+                // another callback
+                subscription("other", "channel.update", "enabled", "https://example.com"),
+            ]),
+            errors: vec![],
+            requests: Mutex::default(),
+        };
+        let helix = HelixClient::with_client(client);
+        let reconciled = Reconciler::new(Transport::webhook(callback, "secretabcd".to_string()))
+            .subscribe(crate::eventsub::stream::StreamOnlineV1::broadcaster_user_id("1234"))
+            .subscribe(crate::eventsub::stream::StreamOfflineV1::broadcaster_user_id("1234"))
+            .subscribe(crate::eventsub::stream::StreamOfflineV1::broadcaster_user_id("1234"))
+            .reconcile(&helix, &token())
+            .await
+            .unwrap();
+
+        assert!(reconciled.failed.is_empty());
+        assert_eq!(ids(&reconciled.kept), ["online"]);
+        assert_eq!(ids(&reconciled.created), ["new"]);
+        assert_eq!(ids(&reconciled.deleted), ["offline", "online2", "update"]);
+        let requests = helix.get_client().requests.lock().unwrap();
+        assert_eq!(
+            requests
+                .iter()
+                .filter(|(method, _)| method == http::Method::DELETE)
+                .count(),
+            3
+        );
+        assert_eq!(
+            requests.last().unwrap(),
+            &(http::Method::POST, "stream.offline 1234".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn continues_after_failures() {
+        let callback = "https://example.org";
+        let client = ReconcileClient {
+            subscriptions: serde_json::json!([
+                subscription("gone", "channel.update", "enabled", callback),
+                subscription("broken", "channel.update", "enabled", callback),
+                subscription("update", "channel.update", "enabled", callback),
+            ]),
+            errors: vec![
+                ("id=gone", 404),
+                ("id=broken", 500),
+                ("stream.online 1234", 409),
+                ("channel.ban 1234", 500),
+            ],
+            requests: Mutex::default(),
+        };
+        let helix = HelixClient::with_client(client);
+        let reconciled = Reconciler::new(Transport::webhook(callback, "secretabcd".to_string()))
+            .subscribe(crate::eventsub::stream::StreamOnlineV1::broadcaster_user_id("1234"))
+            .subscribe(crate::eventsub::channel::ChannelBanV1::broadcaster_user_id(
+                "1234",
+            ))
+            .subscribe(crate::eventsub::stream::StreamOfflineV1::broadcaster_user_id("1234"))
+            .reconcile(&helix, &token())
+            .await
+            .unwrap();
+
+        assert!(reconciled.kept.is_empty());
+        // a 409 on create means the subscription exists
+        assert_eq!(ids(&reconciled.created), ["new"]);
+        // a 404 on delete means the subscription is already gone
+        assert_eq!(ids(&reconciled.deleted), ["gone", "update"]);
+        assert_eq!(reconciled.failed.len(), 2);
+        let broken = &reconciled.failed[0];
+        assert_eq!(broken.id.as_ref().unwrap().as_str(), "broken");
+        assert_eq!(broken.event_type, EventType::ChannelUpdate);
+        assert!(has_status(
+            &broken.error,
+            http::StatusCode::INTERNAL_SERVER_ERROR
+        ));
+        let ban = &reconciled.failed[1];
+        assert!(ban.id.is_none());
+        assert_eq!(ban.event_type, EventType::ChannelBan);
+        assert_eq!(ban.condition["broadcaster_user_id"], "1234");
+    }
+
+    #[test]
+    fn conditions() {
+        let declared = serde_json::json!({"to_broadcaster_user_id": "1234"});
+        let live =
+            serde_json::json!({"to_broadcaster_user_id": "1234", "from_broadcaster_user_id": ""});
+        assert!(same_condition(&declared, &live));
+        let live =
+            serde_json::json!({"to_broadcaster_user_id": "1234", "from_broadcaster_user_id": "1"});
+        assert!(!same_condition(&declared, &live));
+    }
+}
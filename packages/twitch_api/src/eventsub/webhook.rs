@@ -0,0 +1,452 @@
+//! A [`tower::Service`](tower_service::Service) for [EventSub](crate::eventsub) over webhooks
+//!
+//! [`Webhook`] handles the requests Twitch sends to the callback of a webhook subscription:
+//!
+//! * the signature of every request is [verified](Event::verify_payload) with the secret of the subscription.
+//! * messages older than [`Webhook::max_age`], or that have already been received, are not delivered again.
+//! * verification requests are answered with their challenge.
+//! * bodies larger than [`Webhook::max_body_size`] are answered with `413 Payload Too Large` without being read further.
+//! * notifications and revocations are handed to an [`EventSink`], like a callback or a channel, and answered with `204 No Content`.
+//! * verified messages that can't be parsed are also answered with `204 No Content`, so Twitch doesn't deliver them again, and reported to [`EventSink::parse_error`].
+//!
+//! The sink is called before the response is sent, so it should not block. Twitch considers a delivery failed if it's not answered within a few seconds.
+//!
+//! # Examples
+//!
+//! With [axum](https://docs.rs/axum)
+//!
+//! ```rust,ignore
+//! use twitch_api::eventsub::{webhook::Webhook, Event};
+//!
+//! let (sender, mut receiver) = futures::channel::mpsc::unbounded::<Event>();
+//! let app = axum::Router::new().route_service(
+//!     "/twitch/eventsub",
+//!     Webhook::new("secretabcd", sender),
+//! );
+//! ```
+//!
+//! With [hyper](https://docs.rs/hyper), using [`hyper_util::service::TowerToHyperService`](https://docs.rs/hyper-util/*/hyper_util/service/struct.TowerToHyperService.html)
+//!
+//! ```rust,ignore
+//! use twitch_api::eventsub::webhook::Webhook;
+//!
+//! let webhook = Webhook::new("secretabcd", |event| println!("{event:?}"));
+//! hyper::server::conn::http1::Builder::new()
+//!     .serve_connection(io, hyper_util::service::TowerToHyperService::new(webhook))
+//!     .await?;
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Bytes;
+
+use super::dedup::Deduplicator;
+use super::{Event, PayloadParseError};
+
+/// Receives the events delivered to a [`Webhook`]
+pub trait EventSink: Send + Sync + 'static {
+    /// Accept an event. Returns `false` if the event could not be accepted, Twitch will deliver it again later.
+    fn accept(&self, event: Event) -> bool;
+
+    /// Report a verified message that could not be parsed, for example of an unsupported subscription type. The message is acknowledged to Twitch.
+    fn parse_error(&self, error: PayloadParseError) { let _ = error; }
+}
+
+impl<F> EventSink for F
+where F: Fn(Event) + Send + Sync + 'static
+{
+    fn accept(&self, event: Event) -> bool {
+        self(event);
+        true
+    }
+}
+
+impl EventSink for futures::channel::mpsc::UnboundedSender<Event> {
+    fn accept(&self, event: Event) -> bool { self.unbounded_send(event).is_ok() }
+}
+
+/// A bounded channel, the event is rejected when the channel is full.
+///
+/// The sender is kept behind a [`Mutex`](std::sync::Mutex), since every clone of a [`Sender`](futures::channel::mpsc::Sender) gets its own guaranteed slot and would never find the channel full.
+impl EventSink for std::sync::Mutex<futures::channel::mpsc::Sender<Event>> {
+    fn accept(&self, event: Event) -> bool {
+        match self.lock() {
+            Ok(mut sender) => sender.try_send(event).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl EventSink for std::sync::mpsc::Sender<Event> {
+    fn accept(&self, event: Event) -> bool { self.send(event).is_ok() }
+}
+
+/// Handler for EventSub webhook requests, see the [module documentation](self).
+pub struct Webhook<S> {
+    secret: Arc<[u8]>,
+    sink: Arc<S>,
+    max_age: Duration,
+    max_body_size: usize,
+    dedup: Deduplicator,
+}
+
+impl<S> Clone for Webhook<S> {
+    fn clone(&self) -> Self {
+        Self {
+            secret: self.secret.clone(),
+            sink: self.sink.clone(),
+            max_age: self.max_age,
+            max_body_size: self.max_body_size,
+            dedup: self.dedup.clone(),
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for Webhook<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook")
+            .field("max_age", &self.max_age)
+            .field("max_body_size", &self.max_body_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: EventSink> Webhook<S> {
+    /// Create a new handler, verifying requests with `secret` and delivering events to `sink`.
+    ///
+    /// `secret` is the secret given in the [transport](super::Transport::webhook) of the subscriptions.
+    pub fn new(secret: impl AsRef<[u8]>, sink: S) -> Self {
+        Self {
+            secret: secret.as_ref().into(),
+            sink: Arc::new(sink),
+            max_age: Duration::from_secs(10 * 60),
+            max_body_size: 1024 * 1024,
+            dedup: Deduplicator::default(),
+        }
+    }
+
+    /// Set how old a message can be before it's rejected. Defaults to 10 minutes, as recommended by Twitch.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Set the largest request body that is read, in bytes. Defaults to 1 MiB.
+    ///
+    /// The body is read before its signature can be checked, this limits how much memory any client can make the server allocate.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Set how already received messages are detected. Defaults to remembering message ids in memory for 10 minutes.
+    ///
+    /// Use a shared [store](super::dedup::MessageIdStore) when multiple servers receive the same subscriptions.
+    pub fn with_dedup(mut self, dedup: Deduplicator) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Handle a request, returning the response to send to Twitch
+    pub fn handle<B: AsRef<[u8]>>(&self, request: &http::Request<B>) -> http::Response<Vec<u8>> {
+        if !Event::verify_payload(request, &self.secret) {
+            return response(http::StatusCode::FORBIDDEN);
+        }
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let (id, timestamp, message_type) = match (
+            header("Twitch-Eventsub-Message-Id"),
+            header("Twitch-Eventsub-Message-Timestamp"),
+            header("Twitch-Eventsub-Message-Type"),
+        ) {
+            (Some(id), Some(timestamp), Some(message_type)) => (id, timestamp, message_type),
+            _ => return response(http::StatusCode::BAD_REQUEST),
+        };
+        if !self.is_recent(timestamp) {
+            return response(http::StatusCode::FORBIDDEN);
+        }
+
+        if message_type == "webhook_callback_verification" {
+            #[derive(serde_derive::Deserialize)]
+            struct Challenge {
+                challenge: String,
+            }
+            return match serde_json::from_slice::<Challenge>(request.body().as_ref()) {
+                Ok(Challenge { challenge }) => http::Response::builder()
+                    .status(http::StatusCode::OK)
+                    .header(http::header::CONTENT_TYPE, "text/plain")
+                    .body(challenge.into_bytes())
+                    .expect("response should be valid"),
+                Err(_) => response(http::StatusCode::BAD_REQUEST),
+            };
+        }
+
+        if !self.dedup.first_seen(id) {
+            // redelivery of a message that was already accepted
+            return response(http::StatusCode::NO_CONTENT);
+        }
+        let event = match Event::parse_http(request) {
+            Ok(event) => event,
+            Err(e) => {
+                // the message is authentic, delivering it again wouldn't help
+                self.sink.parse_error(e);
+                return response(http::StatusCode::NO_CONTENT);
+            }
+        };
+        if self.sink.accept(event) {
+            response(http::StatusCode::NO_CONTENT)
+        } else {
+            self.dedup.forget(id);
+            response(http::StatusCode::SERVICE_UNAVAILABLE)
+        }
+    }
+
+    /// Returns `true` if `timestamp` is within [`max_age`](Self::max_age) of now
+    fn is_recent(&self, timestamp: &str) -> bool {
+        match crate::types::TimestampRef::from_str(timestamp) {
+            Ok(timestamp) => super::dedup::is_recent(timestamp, self.max_age),
+            Err(_) => false,
+        }
+    }
+}
+
+fn response(status: http::StatusCode) -> http::Response<Vec<u8>> {
+    http::Response::builder()
+        .status(status)
+        .body(vec![])
+        .expect("response should be valid")
+}
+
+impl<S, B> tower_service::Service<http::Request<B>> for Webhook<S>
+where
+    S: EventSink,
+    B: hyper::body::Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    type Error = std::convert::Infallible;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+    type Response = http::Response<Full<Bytes>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let webhook = self.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let response = match Limited::new(body, webhook.max_body_size).collect().await {
+                Ok(body) => webhook.handle(&http::Request::from_parts(parts, body.to_bytes())),
+                Err(e) if e.is::<LengthLimitError>() => {
+                    response(http::StatusCode::PAYLOAD_TOO_LARGE)
+                }
+                Err(_) => response(http::StatusCode::BAD_REQUEST),
+            };
+            Ok(response.map(Full::from))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crypto_hmac::Mac;
+    use tower_service::Service;
+
+    use super::*;
+
+    const SECRET: &[u8] = b"secretabcd";
+    const NOTIFICATION: &str = r#"{"subscription":{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","type":"channel.follow","version":"2","status":"enabled","cost":0,"condition":{"broadcaster_user_id":"1337","moderator_user_id":"1337"},"transport":{"method":"webhook","callback":"https://example.com/webhooks/callback"},"created_at":"2019-11-16T10:11:12.123Z"},"event":{"user_id":"1234","user_login":"cool_user","user_name":"Cool_User","broadcaster_user_id":"1337","broadcaster_user_login":"cooler_user","broadcaster_user_name":"Cooler_User","followed_at":"2020-07-15T18:16:11.17106713Z"}}"#;
+
+    fn request(
+        id: &str,
+        message_type: &str,
+        timestamp: &str,
+        body: &str,
+    ) -> http::Request<Full<Bytes>> {
+        let mut mac = crypto_hmac::Hmac::<sha2::Sha256>::new_from_slice(SECRET).unwrap();
+        mac.update(id.as_bytes());
+        mac.update(timestamp.as_bytes());
+        mac.update(body.as_bytes());
+        let signature: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        http::Request::builder()
+            .method(http::Method::POST)
+            .header("Twitch-Eventsub-Message-Id", id)
+            .header("Twitch-Eventsub-Message-Retry", "0")
+            .header("Twitch-Eventsub-Message-Type", message_type)
+            .header(
+                "Twitch-Eventsub-Message-Signature",
+                format!("sha256={}", signature),
+            )
+            .header("Twitch-Eventsub-Message-Timestamp", timestamp)
+            .header("Twitch-Eventsub-Subscription-Type", "channel.follow")
+            .header("Twitch-Eventsub-Subscription-Version", "2")
+            .body(Full::from(body.to_owned()))
+            .unwrap()
+    }
+
+    async fn call<S: EventSink>(
+        webhook: &mut Webhook<S>,
+        request: http::Request<Full<Bytes>>,
+    ) -> (http::StatusCode, Bytes) {
+        let response = webhook.call(request).await.unwrap();
+        let status = response.status();
+        (
+            status,
+            response.into_body().collect().await.unwrap().to_bytes(),
+        )
+    }
+
+    #[tokio::test]
+    async fn handles_messages() {
+        let received = Arc::new(AtomicUsize::new(0));
+        let counter = received.clone();
+        let mut webhook = Webhook::new(SECRET, move |event: Event| {
+            assert!(matches!(event, Event::ChannelFollowV2(_)));
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let now = crate::types::Timestamp::now();
+        let now = now.as_str();
+
+        let verification = r#"{"challenge":"pogchamp-kappa-360noscope-vohiyo","subscription":{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","status":"webhook_callback_verification_pending","type":"channel.follow","version":"2","cost":1,"condition":{"broadcaster_user_id":"12826","moderator_user_id":"12826"},"transport":{"method":"webhook","callback":"https://example.com/webhooks/callback"},"created_at":"2019-11-16T10:11:12.123Z"}}"#;
+        let (status, body) = call(
+            &mut webhook,
+            request(
+                "verification",
+                "webhook_callback_verification",
+                now,
+                verification,
+            ),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body, "pogchamp-kappa-360noscope-vohiyo");
+
+        let (status, _) = call(
+            &mut webhook,
+            request("1", "notification", now, NOTIFICATION),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::NO_CONTENT);
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+
+        // redelivery is acknowledged but not delivered again
+        let (status, _) = call(
+            &mut webhook,
+            request("1", "notification", now, NOTIFICATION),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::NO_CONTENT);
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+
+        // stale message
+        let (status, _) = call(
+            &mut webhook,
+            request(
+                "2",
+                "notification",
+                "2019-11-16T10:11:12.123Z",
+                NOTIFICATION,
+            ),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::FORBIDDEN);
+
+        // wrong signature
+        let mut forged = request("3", "notification", now, NOTIFICATION);
+        forged.headers_mut().insert(
+            "Twitch-Eventsub-Message-Signature",
+            http::HeaderValue::from_static("sha256=00"),
+        );
+        let (status, _) = call(&mut webhook, forged).await;
+        assert_eq!(status, http::StatusCode::FORBIDDEN);
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn reports_parse_errors() {
+        #[derive(Default)]
+        struct Sink(AtomicUsize);
+
+        impl EventSink for Arc<Sink> {
+            fn accept(&self, _: Event) -> bool { panic!("no event should be delivered") }
+
+            fn parse_error(&self, _: PayloadParseError) { self.0.fetch_add(1, Ordering::SeqCst); }
+        }
+
+        let sink = Arc::new(Sink::default());
+        let mut webhook = Webhook::new(SECRET, sink.clone());
+        let now = crate::types::Timestamp::now();
+
+        let (status, _) = call(
+            &mut webhook,
+            request("1", "notification", now.as_str(), r#"{"subscription":{}}"#),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::NO_CONTENT);
+        assert_eq!(sink.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_events_when_the_channel_is_full() {
+        let (sender, mut receiver) = futures::channel::mpsc::channel::<Event>(0);
+        let mut webhook = Webhook::new(SECRET, std::sync::Mutex::new(sender));
+        let now = crate::types::Timestamp::now();
+        let now = now.as_str();
+
+        // the sender has one guaranteed slot
+        let (status, _) = call(
+            &mut webhook,
+            request("1", "notification", now, NOTIFICATION),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::NO_CONTENT);
+        let (status, _) = call(
+            &mut webhook,
+            request("2", "notification", now, NOTIFICATION),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+
+        assert!(receiver.try_next().unwrap().is_some());
+        // the rejected message was forgotten, so its redelivery is accepted
+        let (status, _) = call(
+            &mut webhook,
+            request("2", "notification", now, NOTIFICATION),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::NO_CONTENT);
+        assert!(receiver.try_next().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn rejects_large_bodies() {
+        let mut webhook = Webhook::new(SECRET, |_: Event| panic!("no event should be delivered"))
+            .max_body_size(16);
+        let now = crate::types::Timestamp::now();
+
+        let (status, _) = call(
+            &mut webhook,
+            request("1", "notification", now.as_str(), NOTIFICATION),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
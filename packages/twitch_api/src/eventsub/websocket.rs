@@ -0,0 +1,600 @@
+//! A client for [EventSub](crate::eventsub) over WebSockets
+//!
+//! [`Client`] connects to the EventSub WebSocket server, creates the configured subscriptions once the session is welcomed and yields every notification as an [`Event`].
+//! The connection is kept alive for as long as the stream is polled:
+//!
+//! * a `session_reconnect` message moves the session to the given URL, subscriptions carry over to it and are not created again.
+//!   The old connection is read until the new one is welcomed, so no notification is lost in between.
+//! * when no message is received within the keepalive timeout of the session, the connection is considered dead and a new session is started.
+//! * when the connection is closed or fails, a new session is started.
+//! * notifications that have already been received, or are older than 10 minutes, are dropped, see [`Client::with_dedup`].
+//!
+//! A new session has no subscriptions, so they are created again after every welcome on a fresh connection.
+//!
+//! Errors are yielded on the stream but are not fatal, the client recovers from them by itself.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use twitch_api::{
+//!     eventsub::{self, websocket},
+//!     HelixClient,
+//! };
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//! let client: HelixClient<'static, reqwest::Client> = HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let stream = websocket::Client::new(client, token)
+//!     .subscribe(eventsub::channel::ChannelBanV1::broadcaster_user_id("1234"))
+//!     .subscribe(eventsub::channel::ChannelUnbanV1::broadcaster_user_id("1234"))
+//!     .into_stream();
+//! futures::pin_mut!(stream);
+//! while let Some(event) = stream.next().await {
+//!     match event {
+//!         Ok(event) => println!("{event:?}"),
+//!         Err(e) => eprintln!("{e}"),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use tokio_tungstenite::tungstenite;
+use twitch_oauth2::TwitchToken;
+
+use super::dedup::Deduplicator;
+use super::{Event, EventSubscription, EventsubWebsocketData, PayloadParseError, SessionData};
+use crate::client::BoxedFuture;
+use crate::helix::{ClientRequestError, HelixClient};
+use crate::HttpClient;
+
+pub mod conduit;
+
+type Socket =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Errors yielded by the [`Client`] stream
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum WebsocketError<RE: std::error::Error + Send + Sync + 'static> {
+    /// Could not connect to the EventSub WebSocket server
+    #[error("could not connect to websocket")]
+    Connect(#[source] tungstenite::Error),
+    /// The connection failed, a new session will be started
+    #[error("websocket connection failed")]
+    Connection(#[source] tungstenite::Error),
+    /// Could not parse a message
+    #[error("could not parse message")]
+    Parse(#[from] PayloadParseError),
+    /// No message was received within the keepalive timeout, a new session will be started
+    #[error("missed keepalive, reconnecting")]
+    MissedKeepalive,
+    /// Could not create a subscription
+    #[error("could not create subscription")]
+    Subscribe(#[source] ClientRequestError<RE>),
+    /// A subscription was revoked by Twitch
+    #[error("subscription was revoked")]
+    Revoked(Box<Event>),
+    /// A conduit request failed
+    #[error("conduit request failed")]
+    Conduit(#[source] ClientRequestError<RE>),
+    /// Twitch could not assign a session to a conduit shard
+    #[error("could not assign shard {}: {}", .0.id, .0.message)]
+    Shard(super::ShardError),
+}
+
+/// Sets up a session once it has been welcomed
+trait Welcome: Send + Sync + 'static {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Called for every welcome, `reconnected` is set if the session was moved to a new connection with `session_reconnect`
+    fn welcome<'a>(
+        &'a self,
+        session_id: &'a str,
+        reconnected: bool,
+    ) -> BoxedFuture<'a, Vec<WebsocketError<Self::Error>>>;
+}
+
+/// Connection settings shared by all sessions
+#[derive(Clone, Debug)]
+struct Options {
+    url: url::Url,
+    keepalive_timeout: Option<Duration>,
+    keepalive_grace: Duration,
+    reconnect_delay: Duration,
+    dedup: Deduplicator,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            url: crate::TWITCH_EVENTSUB_WEBSOCKET_URL.clone(),
+            keepalive_timeout: None,
+            keepalive_grace: Duration::from_secs(5),
+            reconnect_delay: Duration::from_secs(1),
+            dedup: Deduplicator::default(),
+        }
+    }
+}
+
+impl Options {
+    /// The URL to start a new session on
+    fn connect_url(&self) -> url::Url {
+        let mut url = self.url.clone();
+        if let Some(timeout) = self.keepalive_timeout {
+            url.query_pairs_mut()
+                .append_pair("keepalive_timeout_seconds", &timeout.as_secs().to_string());
+        }
+        url
+    }
+}
+
+/// A subscription that can be created on a session
+trait Subscription<C: HttpClient, T>: Send + Sync {
+    fn subscribe<'a>(
+        &'a self,
+        helix: &'a HelixClient<'a, C>,
+        session_id: &'a str,
+        token: &'a T,
+    ) -> BoxedFuture<'a, Result<(), ClientRequestError<C::Error>>>;
+}
+
+impl<E, C, T> Subscription<C, T> for E
+where
+    E: EventSubscription + Send + Sync,
+    C: HttpClient + Sync,
+    T: TwitchToken + Send + Sync,
+{
+    fn subscribe<'a>(
+        &'a self,
+        helix: &'a HelixClient<'a, C>,
+        session_id: &'a str,
+        token: &'a T,
+    ) -> BoxedFuture<'a, Result<(), ClientRequestError<C::Error>>> {
+        Box::pin(async move {
+            helix
+                .create_eventsub_subscription(
+                    self.clone(),
+                    super::Transport::websocket(session_id),
+                    token,
+                )
+                .await
+                .map(|_| ())
+        })
+    }
+}
+
+/// Client for EventSub over WebSockets, see the [module documentation](self).
+pub struct Client<C: HttpClient + 'static, T> {
+    helix: HelixClient<'static, C>,
+    token: T,
+    subscriptions: Vec<Arc<dyn Subscription<C, T>>>,
+    options: Options,
+}
+
+impl<C: HttpClient + 'static, T: std::fmt::Debug> std::fmt::Debug for Client<C, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("token", &self.token)
+            .field("subscriptions", &self.subscriptions.len())
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, T> Client<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static,
+{
+    /// Create a new client, creating subscriptions with `helix` and `token`.
+    ///
+    /// The token must be a user access token, app access tokens can not be used with WebSockets.
+    pub fn new(helix: HelixClient<'static, C>, token: T) -> Self {
+        Self {
+            helix,
+            token,
+            subscriptions: vec![],
+            options: Options::default(),
+        }
+    }
+
+    /// Subscribe to an event on every new session
+    pub fn subscribe<E>(mut self, subscription: E) -> Self
+    where E: EventSubscription + Send + Sync + 'static {
+        self.subscriptions.push(Arc::new(subscription));
+        self
+    }
+
+    /// Set the URL of the EventSub WebSocket server. Defaults to [`TWITCH_EVENTSUB_WEBSOCKET_URL`](crate::TWITCH_EVENTSUB_WEBSOCKET_URL).
+    pub fn with_url(mut self, url: url::Url) -> Self {
+        self.options.url = url;
+        self
+    }
+
+    /// Request a keepalive timeout from Twitch, between 10 and 600 seconds. Defaults to the timeout chosen by Twitch.
+    pub fn keepalive_timeout(mut self, keepalive_timeout: Duration) -> Self {
+        self.options.keepalive_timeout = Some(keepalive_timeout);
+        self
+    }
+
+    /// Set how long to wait after the keepalive timeout before the connection is considered dead. Defaults to 5 seconds.
+    pub fn keepalive_grace(mut self, keepalive_grace: Duration) -> Self {
+        self.options.keepalive_grace = keepalive_grace;
+        self
+    }
+
+    /// Set how long to wait before connecting again after the connection failed. Defaults to 1 second.
+    pub fn reconnect_delay(mut self, reconnect_delay: Duration) -> Self {
+        self.options.reconnect_delay = reconnect_delay;
+        self
+    }
+
+    /// Set how already received and stale messages are detected. Defaults to remembering message ids in memory for 10 minutes and dropping messages older than 10 minutes.
+    pub fn with_dedup(mut self, dedup: Deduplicator) -> Self {
+        self.options.dedup = dedup;
+        self
+    }
+
+    /// Connect and yield events for as long as the stream is polled
+    pub fn into_stream(
+        self,
+    ) -> impl futures::Stream<Item = Result<Event, WebsocketError<C::Error>>> + Send {
+        let options = self.options.clone();
+        futures::stream::unfold(Runtime::new(self, options), |mut runtime| async move {
+            let item = runtime.next().await;
+            Some((item, runtime))
+        })
+    }
+}
+
+impl<C, T> Welcome for Client<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static,
+{
+    type Error = C::Error;
+
+    fn welcome<'a>(
+        &'a self,
+        session_id: &'a str,
+        reconnected: bool,
+    ) -> BoxedFuture<'a, Vec<WebsocketError<Self::Error>>> {
+        Box::pin(async move {
+            let mut errors = vec![];
+            // subscriptions carry over to the new connection
+            if reconnected {
+                return errors;
+            }
+            for subscription in &self.subscriptions {
+                if let Err(e) = subscription
+                    .subscribe(&self.helix, session_id, &self.token)
+                    .await
+                {
+                    errors.push(WebsocketError::Subscribe(e));
+                }
+            }
+            errors
+        })
+    }
+}
+
+/// State of the connection loop
+struct Runtime<W: Welcome> {
+    welcome: W,
+    options: Options,
+    socket: Option<Socket>,
+    /// Connection to the `reconnect_url` of the session, replaces `socket` once it's welcomed
+    handover: Option<Socket>,
+    /// The socket was opened from a `session_reconnect`, its session already has all subscriptions
+    reconnected: bool,
+    /// Keepalive timeout of the current session
+    keepalive: Option<Duration>,
+    /// Wait before connecting again
+    backoff: bool,
+    pending: VecDeque<WebsocketError<W::Error>>,
+}
+
+impl<W: Welcome> Runtime<W> {
+    fn new(welcome: W, options: Options) -> Self {
+        Self {
+            welcome,
+            options,
+            socket: None,
+            handover: None,
+            reconnected: false,
+            keepalive: None,
+            backoff: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Drop the connection, the next message will be read from a new session
+    fn reset(&mut self) {
+        self.socket = None;
+        self.handover = None;
+        self.reconnected = false;
+        self.keepalive = None;
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn next(&mut self) -> Result<Event, WebsocketError<W::Error>> {
+        loop {
+            if let Some(error) = self.pending.pop_front() {
+                return Err(error);
+            }
+            let socket = match &mut self.socket {
+                Some(socket) => socket,
+                None => {
+                    if std::mem::take(&mut self.backoff) {
+                        tokio::time::sleep(self.options.reconnect_delay).await;
+                    }
+                    match connect(self.options.connect_url().as_str()).await {
+                        Ok(socket) => self.socket.insert(socket),
+                        Err(e) => {
+                            self.backoff = true;
+                            return Err(WebsocketError::Connect(e));
+                        }
+                    }
+                }
+            };
+            let read = read(socket, self.handover.as_mut());
+            let (message, from_handover) = match self.keepalive {
+                Some(keepalive) => {
+                    match tokio::time::timeout(keepalive + self.options.keepalive_grace, read).await
+                    {
+                        Ok(read) => read,
+                        Err(_) => {
+                            self.reset();
+                            return Err(WebsocketError::MissedKeepalive);
+                        }
+                    }
+                }
+                None => read.await,
+            };
+            let text = match message {
+                Some(Ok(tungstenite::Message::Text(text))) => text,
+                Some(Ok(tungstenite::Message::Close(_))) | None if from_handover => {
+                    // keep the session on the old connection
+                    self.handover = None;
+                    continue;
+                }
+                Some(Ok(tungstenite::Message::Close(_))) | None if self.handover.is_some() => {
+                    // the old connection is done, the session continues on the new one
+                    self.socket = self.handover.take();
+                    continue;
+                }
+                Some(Ok(tungstenite::Message::Close(_))) | None => {
+                    self.reset();
+                    self.backoff = true;
+                    continue;
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(e)) if from_handover => {
+                    self.handover = None;
+                    return Err(WebsocketError::Connection(e));
+                }
+                Some(Err(e)) => {
+                    self.reset();
+                    self.backoff = true;
+                    return Err(WebsocketError::Connection(e));
+                }
+            };
+            let data = Event::parse_websocket(&text)?;
+            if !self.options.dedup.first_seen_websocket(&data) {
+                // redelivery of a message that was already received
+                continue;
+            }
+            match data {
+                EventsubWebsocketData::Welcome { payload, .. } => {
+                    if from_handover {
+                        // the old connection is closed by dropping it
+                        self.socket = self.handover.take();
+                    }
+                    self.keepalive = payload
+                        .session
+                        .keepalive_timeout_seconds
+                        .map(|seconds| Duration::from_secs(seconds.max(0) as u64));
+                    let reconnected = std::mem::take(&mut self.reconnected);
+                    let errors = self.welcome.welcome(&payload.session.id, reconnected).await;
+                    self.pending.extend(errors);
+                }
+                EventsubWebsocketData::Keepalive { .. } => {}
+                EventsubWebsocketData::Notification { payload, .. } => return Ok(payload),
+                EventsubWebsocketData::Revocation { payload, .. } => {
+                    return Err(WebsocketError::Revoked(Box::new(payload)))
+                }
+                EventsubWebsocketData::Reconnect { payload, .. } => {
+                    self.reconnect(payload.session).await?
+                }
+            }
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn reconnect(
+        &mut self,
+        session: SessionData<'_>,
+    ) -> Result<(), WebsocketError<W::Error>> {
+        let url = match session.reconnect_url {
+            Some(url) => url,
+            None => return Ok(()),
+        };
+        match connect(&url).await {
+            Ok(socket) => {
+                // the old connection delivers events until the new one is welcomed
+                self.handover = Some(socket);
+                self.reconnected = true;
+                Ok(())
+            }
+            Err(e) => {
+                self.reset();
+                Err(WebsocketError::Connect(e))
+            }
+        }
+    }
+}
+
+/// Read the next message from `socket` or `handover`, telling if it came from `handover`
+async fn read(
+    socket: &mut Socket,
+    handover: Option<&mut Socket>,
+) -> (
+    Option<Result<tungstenite::Message, tungstenite::Error>>,
+    bool,
+) {
+    match handover {
+        Some(handover) => match futures::future::select(socket.next(), handover.next()).await {
+            futures::future::Either::Left((message, _)) => (message, false),
+            futures::future::Either::Right((message, _)) => (message, true),
+        },
+        None => (socket.next().await, false),
+    }
+}
+
+async fn connect(url: &str) -> Result<Socket, tungstenite::Error> {
+    tokio_tungstenite::connect_async(url)
+        .await
+        .map(|(socket, _)| socket)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::SinkExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Responds to all requests with a created `user.update` subscription
+    #[derive(Default)]
+    struct SubscribeClient(AtomicUsize);
+
+    impl HttpClient for SubscribeClient {
+        type Error = std::io::Error;
+
+        fn req(
+            &self,
+            _: crate::client::Request,
+        ) -> BoxedFuture<'_, Result<crate::client::Response, Self::Error>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {
+                let body = br#"{"data":[{"id":"26b1c993-bfcf-44d9-b876-379dacafe75a","status":"enabled","type":"user.update","version":"1","condition":{"user_id":"1234"},"created_at":"2020-11-10T14:32:18.730260295Z","transport":{"method":"websocket","session_id":"session","connected_at":"2020-11-10T14:32:18.730260295Z"},"cost":0}],"total":1,"total_cost":0,"max_total_cost":10000}"#;
+                Ok(http::Response::builder()
+                    .status(202)
+                    .body(body.to_vec().into())
+                    .unwrap())
+            })
+        }
+    }
+
+    fn welcome(keepalive: u64) -> String {
+        format!(
+            r#"{{"metadata":{{"message_id":"welcome","message_type":"session_welcome","message_timestamp":"2022-10-19T14:56:51.634234626Z"}},"payload":{{"session":{{"id":"session","status":"connected","connected_at":"2022-10-19T14:56:51.616329898Z","keepalive_timeout_seconds":{},"reconnect_url":null,"recovery_url":null}}}}}}"#,
+            keepalive
+        )
+    }
+
+    fn reconnect(url: &str) -> String {
+        format!(
+            r#"{{"metadata":{{"message_id":"reconnect","message_type":"session_reconnect","message_timestamp":"2022-10-19T14:56:51.634234626Z"}},"payload":{{"session":{{"id":"session","status":"reconnecting","connected_at":"2022-10-19T14:56:51.616329898Z","keepalive_timeout_seconds":null,"reconnect_url":"{}"}}}}}}"#,
+            url
+        )
+    }
+
+    fn notification(login: &str) -> String {
+        format!(
+            r#"{{"metadata":{{"message_id":"{0}","message_type":"notification","message_timestamp":"{now}","subscription_type":"user.update","subscription_version":"1"}},"payload":{{"subscription":{{"id":"26b1c993-bfcf-44d9-b876-379dacafe75a","type":"user.update","version":"1","status":"enabled","cost":0,"condition":{{"user_id":"1234"}},"transport":{{"method":"websocket","session_id":"session"}},"created_at":"2022-10-19T14:56:51.616329898Z"}},"event":{{"user_id":"1234","user_login":"{0}","user_name":"{0}","description":""}}}}}}"#,
+            login,
+            now = crate::types::Timestamp::now(),
+        )
+    }
+
+    fn login(event: Result<Event, WebsocketError<std::io::Error>>) -> String {
+        match event {
+            Ok(Event::UserUpdateV1(payload)) => match payload.message {
+                super::super::Message::Notification(n) => n.user_login.to_string(),
+                m => panic!("unexpected message {:?}", m),
+            },
+            e => panic!("unexpected event {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnects() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let reconnect_url = url.clone();
+        tokio::spawn(async move {
+            for connection in 0.. {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let messages = match connection {
+                    // session is moved to a new connection, the old one is still read until then
+                    0 => vec![
+                        welcome(10),
+                        notification("first"),
+                        reconnect(&reconnect_url),
+                        notification("handover"),
+                    ],
+                    // keepalives stop
+                    1 => vec![welcome(0), notification("second")],
+                    // "second" is delivered again
+                    _ => vec![welcome(10), notification("second"), notification("third")],
+                };
+                tokio::spawn(async move {
+                    for message in messages {
+                        socket
+                            .send(tungstenite::Message::Text(message))
+                            .await
+                            .unwrap();
+                    }
+                    while let Some(Ok(_)) = socket.next().await {}
+                });
+            }
+        });
+
+        let helix = HelixClient::with_client(SubscribeClient::default());
+        let token = twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        );
+        let client = Client::new(helix, token)
+            .subscribe(crate::eventsub::user::UserUpdateV1::new("1234"))
+            .with_url(url.parse().unwrap())
+            .keepalive_grace(Duration::from_millis(100));
+        let subscribed = |client: &Client<SubscribeClient, _>| {
+            client.helix.get_client().0.load(Ordering::SeqCst)
+        };
+        let options = client.options.clone();
+        let mut runtime = Runtime::new(client, options);
+
+        assert_eq!(login(runtime.next().await), "first");
+        assert_eq!(subscribed(&runtime.welcome), 1);
+        assert_eq!(login(runtime.next().await), "handover");
+        assert_eq!(login(runtime.next().await), "second");
+        assert!(runtime.handover.is_none());
+        assert_eq!(subscribed(&runtime.welcome), 1);
+        assert!(matches!(
+            runtime.next().await,
+            Err(WebsocketError::MissedKeepalive)
+        ));
+        assert_eq!(login(runtime.next().await), "third");
+        assert_eq!(subscribed(&runtime.welcome), 2);
+    }
+}
@@ -0,0 +1,756 @@
+//! Conduits over EventSub WebSockets
+//!
+//! A [conduit](https://dev.twitch.tv/docs/eventsub/handling-conduit-events/) distributes the events of its subscriptions over its shards.
+//! [`ConduitManager`] opens one WebSocket session per shard, assigns each session to its shard and yields the events of all shards as one stream.
+//!
+//! * a shard is assigned again whenever its session is welcomed, including after a reconnect.
+//! * the manager subscribes to [`conduit.shard.disabled`](crate::eventsub::conduit::ConduitShardDisabledV1) for the conduit, when Twitch disables a shard it's started again on a new session.
+//!   These notifications are handled by the manager and not yielded.
+//! * a message delivered again, for example after a shard moved to a new session, is only yielded once.
+//! * the conduit is resized with a [`ConduitHandle`], or automatically with a [`ScalePolicy`]. Shards keep running while the conduit is resized.
+//!
+//! Subscriptions are created on the conduit with [`Transport::conduit`](crate::eventsub::Transport::conduit) and an app access token, they are not affected by sessions coming and going.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use twitch_api::{
+//!     eventsub::websocket::conduit::{ConduitManager, ScalePolicy},
+//!     HelixClient,
+//! };
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//! let client: HelixClient<'static, reqwest::Client> = HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let secret = twitch_oauth2::ClientSecret::new("secret".to_string());
+//! # let token = twitch_oauth2::AppAccessToken::from_existing(&client, token, None, secret).await?;
+//! let manager = ConduitManager::create(client, token, 4)
+//!     .await?
+//!     .scale(ScalePolicy::new(100).max_shards(16));
+//! let handle = manager.handle();
+//! let stream = manager.into_stream();
+//! futures::pin_mut!(stream);
+//! while let Some(event) = stream.next().await {
+//!     match event {
+//!         Ok(event) => println!("{event:?}"),
+//!         Err(e) => eprintln!("{e}"),
+//!     }
+//! }
+//! # handle.resize(8);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::channel::mpsc;
+use futures::future::{AbortHandle, Abortable, Either};
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use twitch_oauth2::TwitchToken;
+
+use super::{Options, Runtime, WebsocketError, Welcome};
+use crate::client::BoxedFuture;
+use crate::eventsub::{conduit::ConduitShardDisabledV1, Event, Message, Shard, Transport};
+use crate::helix::{ClientRequestError, HelixClient, HelixRequestPostError};
+use crate::types;
+use crate::HttpClient;
+
+/// State shared by all shards of a conduit
+struct Shared<C: HttpClient + 'static, T> {
+    helix: HelixClient<'static, C>,
+    token: T,
+    conduit_id: types::ConduitId,
+}
+
+/// The session of a shard, assigned to the shard on welcome
+struct ShardSession<C: HttpClient + 'static, T> {
+    shared: Arc<Shared<C, T>>,
+    shard_id: types::ConduitShardId,
+}
+
+impl<C, T> Welcome for ShardSession<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static,
+{
+    type Error = C::Error;
+
+    fn welcome<'a>(
+        &'a self,
+        session_id: &'a str,
+        _reconnected: bool,
+    ) -> BoxedFuture<'a, Vec<WebsocketError<Self::Error>>> {
+        Box::pin(async move {
+            let shard = Shard::new(self.shard_id.clone(), Transport::websocket(session_id));
+            match self
+                .shared
+                .helix
+                .update_conduit_shards(&*self.shared.conduit_id, vec![shard], &self.shared.token)
+                .await
+            {
+                Ok(response) => response
+                    .errors
+                    .into_iter()
+                    .map(WebsocketError::Shard)
+                    .collect(),
+                Err(e) => vec![WebsocketError::Conduit(e)],
+            }
+        })
+    }
+}
+
+/// Scales a conduit with the rate of events it receives
+///
+/// The rate is measured over an interval, after which the conduit is resized to the amount of shards needed for the rate.
+/// The rate is only checked when an event is received, an idle conduit is scaled down with the next event.
+#[derive(Clone, Debug)]
+pub struct ScalePolicy {
+    events_per_shard: u32,
+    min_shards: usize,
+    max_shards: usize,
+    interval: Duration,
+}
+
+impl ScalePolicy {
+    /// Create a policy that gives every shard `events_per_shard` events per second.
+    pub fn new(events_per_shard: u32) -> Self {
+        Self {
+            events_per_shard: events_per_shard.max(1),
+            min_shards: 1,
+            max_shards: 20_000,
+            interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Set the minimum amount of shards. Defaults to `1`.
+    pub fn min_shards(mut self, min_shards: usize) -> Self {
+        self.min_shards = min_shards.max(1);
+        self
+    }
+
+    /// Set the maximum amount of shards. Defaults to `20000`, the maximum allowed by Twitch.
+    pub fn max_shards(mut self, max_shards: usize) -> Self {
+        self.max_shards = max_shards;
+        self
+    }
+
+    /// Set the interval the rate of events is measured over. Defaults to 60 seconds.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The amount of shards needed for `events` received in `elapsed`
+    fn shards_for(&self, events: u64, elapsed: Duration) -> usize {
+        let rate = events as f64 / elapsed.as_secs_f64().max(1.0);
+        let shards = (rate / f64::from(self.events_per_shard)).ceil() as usize;
+        shards.min(self.max_shards).max(self.min_shards)
+    }
+}
+
+#[derive(Debug)]
+enum Command {
+    Resize(usize),
+}
+
+/// Handle to a running [`ConduitManager`]
+#[derive(Clone, Debug)]
+pub struct ConduitHandle {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl ConduitHandle {
+    /// Resize the conduit to `shard_count` shards. Has no effect if the stream of the manager has been dropped.
+    pub fn resize(&self, shard_count: usize) {
+        let _ = self.commands.unbounded_send(Command::Resize(shard_count));
+    }
+}
+
+/// Manages the shards of a conduit, see the [module documentation](self).
+pub struct ConduitManager<C: HttpClient + 'static, T> {
+    shared: Arc<Shared<C, T>>,
+    shard_count: usize,
+    scale: Option<ScalePolicy>,
+    options: Options,
+    sender: mpsc::UnboundedSender<Command>,
+    receiver: mpsc::UnboundedReceiver<Command>,
+}
+
+impl<C: HttpClient + 'static, T> std::fmt::Debug for ConduitManager<C, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConduitManager")
+            .field("conduit_id", &self.shared.conduit_id)
+            .field("shard_count", &self.shard_count)
+            .field("scale", &self.scale)
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, T> ConduitManager<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static,
+{
+    /// Manage an existing conduit, resizing it to `shard_count` shards when the stream starts.
+    ///
+    /// The token must be an app access token.
+    pub fn new(
+        helix: HelixClient<'static, C>,
+        token: T,
+        conduit_id: impl Into<types::ConduitId>,
+        shard_count: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        Self {
+            shared: Arc::new(Shared {
+                helix,
+                token,
+                conduit_id: conduit_id.into(),
+            }),
+            shard_count,
+            scale: None,
+            options: Options::default(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Create a new conduit with `shard_count` shards and manage it.
+    ///
+    /// The token must be an app access token.
+    pub async fn create(
+        helix: HelixClient<'static, C>,
+        token: T,
+        shard_count: usize,
+    ) -> Result<Self, ClientRequestError<C::Error>> {
+        let conduit = helix.create_conduit(shard_count, &token).await?;
+        Ok(Self::new(helix, token, conduit.id, conduit.shard_count))
+    }
+
+    /// The id of the managed conduit
+    pub fn conduit_id(&self) -> &types::ConduitIdRef { &self.shared.conduit_id }
+
+    /// Resize the conduit automatically with a [`ScalePolicy`]
+    pub fn scale(mut self, scale: ScalePolicy) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Set the URL of the EventSub WebSocket server. Defaults to [`TWITCH_EVENTSUB_WEBSOCKET_URL`](crate::TWITCH_EVENTSUB_WEBSOCKET_URL).
+    pub fn with_url(mut self, url: url::Url) -> Self {
+        self.options.url = url;
+        self
+    }
+
+    /// Request a keepalive timeout from Twitch, between 10 and 600 seconds. Defaults to the timeout chosen by Twitch.
+    pub fn keepalive_timeout(mut self, keepalive_timeout: Duration) -> Self {
+        self.options.keepalive_timeout = Some(keepalive_timeout);
+        self
+    }
+
+    /// Set how long to wait after the keepalive timeout before a session is considered dead. Defaults to 5 seconds.
+    pub fn keepalive_grace(mut self, keepalive_grace: Duration) -> Self {
+        self.options.keepalive_grace = keepalive_grace;
+        self
+    }
+
+    /// Set how long to wait before connecting again after a connection failed. Defaults to 1 second.
+    pub fn reconnect_delay(mut self, reconnect_delay: Duration) -> Self {
+        self.options.reconnect_delay = reconnect_delay;
+        self
+    }
+
+    /// Set how already received and stale messages are detected. Defaults to remembering message ids in memory for 10 minutes, shared by all shards, and dropping messages older than 10 minutes.
+    pub fn with_dedup(mut self, dedup: crate::eventsub::dedup::Deduplicator) -> Self {
+        self.options.dedup = dedup;
+        self
+    }
+
+    /// Get a handle to control the conduit while the stream is running
+    pub fn handle(&self) -> ConduitHandle {
+        ConduitHandle {
+            commands: self.sender.clone(),
+        }
+    }
+
+    /// Start all shards and yield the events of all of them for as long as the stream is polled
+    pub fn into_stream(
+        self,
+    ) -> impl futures::Stream<Item = Result<Event, WebsocketError<C::Error>>> + Send {
+        self.handle().resize(self.shard_count);
+        let mut state = State {
+            shared: self.shared,
+            scale: self.scale,
+            options: self.options,
+            shards: vec![],
+            running: FuturesUnordered::new(),
+            resizing: false,
+            queued_resize: None,
+            _sender: self.sender,
+            receiver: self.receiver,
+            window: (Instant::now(), 0),
+            pending: VecDeque::new(),
+        };
+        state.subscribe_disabled();
+        futures::stream::unfold(state, |mut state| async move {
+            let item = state.next().await;
+            Some((item, state))
+        })
+    }
+}
+
+type Step<C, T> = (
+    usize,
+    Runtime<ShardSession<C, T>>,
+    Result<Event, WebsocketError<<C as HttpClient>::Error>>,
+);
+
+/// Work running alongside the shards
+enum Task<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static, {
+    /// The next event of a shard, `None` if the shard was stopped
+    Shard(Option<Box<Step<C, T>>>),
+    /// The conduit was resized to the amount of shards
+    Resized(usize, Result<(), ClientRequestError<C::Error>>),
+    /// The subscription to `conduit.shard.disabled` was created
+    Subscribed(Result<(), ClientRequestError<C::Error>>),
+}
+
+/// State of a running conduit
+struct State<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static, {
+    shared: Arc<Shared<C, T>>,
+    scale: Option<ScalePolicy>,
+    options: Options,
+    /// Abort handles of the running shards, indexed by shard id
+    shards: Vec<AbortHandle>,
+    running: FuturesUnordered<BoxedFuture<'static, Task<C, T>>>,
+    /// A resize of the conduit is running
+    resizing: bool,
+    /// Resize to start once the running one is done
+    queued_resize: Option<usize>,
+    /// Keeps the command channel open
+    _sender: mpsc::UnboundedSender<Command>,
+    receiver: mpsc::UnboundedReceiver<Command>,
+    /// Start of the current scale interval and events received in it
+    window: (Instant, u64),
+    pending: VecDeque<WebsocketError<C::Error>>,
+}
+
+impl<C, T> State<C, T>
+where
+    C: HttpClient + Sync + 'static,
+    T: TwitchToken + Send + Sync + 'static,
+{
+    #[allow(clippy::result_large_err)]
+    async fn next(&mut self) -> Result<Event, WebsocketError<C::Error>> {
+        loop {
+            if let Some(error) = self.pending.pop_front() {
+                return Err(error);
+            }
+            let next =
+                match futures::future::select(self.running.next(), self.receiver.next()).await {
+                    Either::Left((step, _)) => Either::Left(step),
+                    Either::Right((command, _)) => Either::Right(command),
+                };
+            let (index, runtime, item) = match next {
+                Either::Left(Some(Task::Shard(Some(step)))) => *step,
+                // the shard was stopped
+                Either::Left(Some(Task::Shard(None))) => continue,
+                Either::Left(Some(Task::Resized(shard_count, result))) => {
+                    self.resized(shard_count, result);
+                    continue;
+                }
+                Either::Left(Some(Task::Subscribed(result))) => {
+                    match result {
+                        // the subscription is kept from an earlier manager of the conduit
+                        Err(ClientRequestError::HelixRequestPostError(
+                            HelixRequestPostError::Error { status, .. },
+                        )) if status == http::StatusCode::CONFLICT => {}
+                        Err(e) => self.pending.push_back(WebsocketError::Conduit(e)),
+                        Ok(()) => {}
+                    }
+                    continue;
+                }
+                Either::Left(None) => {
+                    // no shards are running, wait for a resize
+                    if let Some(command) = self.receiver.next().await {
+                        self.command(command);
+                    }
+                    continue;
+                }
+                Either::Right(Some(command)) => {
+                    self.command(command);
+                    continue;
+                }
+                Either::Right(None) => unreachable!("the command channel is kept open"),
+            };
+            let disabled = match &item {
+                Ok(Event::ConduitShardDisabledV1(payload)) => match &payload.message {
+                    Message::Notification(disabled)
+                        if disabled.conduit_id == self.shared.conduit_id.as_str() =>
+                    {
+                        disabled
+                            .shard_id
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|&shard| shard < self.shards.len())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(shard) = disabled {
+                self.start(shard);
+            }
+            if index < self.shards.len() && disabled != Some(index) {
+                self.spawn(index, runtime);
+            }
+            if let Ok(Event::ConduitShardDisabledV1(_)) = item {
+                continue;
+            }
+            if item.is_ok() {
+                self.record_event();
+            }
+            return item;
+        }
+    }
+
+    fn command(&mut self, command: Command) {
+        match command {
+            Command::Resize(shard_count) => self.resize(shard_count),
+        }
+    }
+
+    /// Subscribe to `conduit.shard.disabled` of the conduit, delivered to its own shards
+    fn subscribe_disabled(&mut self) {
+        let shared = self.shared.clone();
+        self.running.push(Box::pin(async move {
+            let subscription = ConduitShardDisabledV1::client_id(shared.token.client_id().as_str())
+                .conduit_id(shared.conduit_id.as_str());
+            let result = shared
+                .helix
+                .create_eventsub_subscription(
+                    subscription,
+                    Transport::conduit(shared.conduit_id.as_str()),
+                    &shared.token,
+                )
+                .await;
+            Task::Subscribed(result.map(|_| ()))
+        }));
+    }
+
+    /// Resize the conduit, the shards keep running while Twitch is updating it
+    fn resize(&mut self, shard_count: usize) {
+        let shard_count = shard_count.max(1);
+        if self.resizing {
+            self.queued_resize = Some(shard_count);
+            return;
+        }
+        self.resizing = true;
+        let shared = self.shared.clone();
+        self.running.push(Box::pin(async move {
+            let result = shared
+                .helix
+                .update_conduit(&*shared.conduit_id, shard_count, &shared.token)
+                .await;
+            Task::Resized(shard_count, result.map(|_| ()))
+        }));
+    }
+
+    /// Start or stop shards after the conduit was resized
+    fn resized(&mut self, shard_count: usize, result: Result<(), ClientRequestError<C::Error>>) {
+        self.resizing = false;
+        if let Some(shard_count) = self.queued_resize.take() {
+            self.resize(shard_count);
+        }
+        if let Err(e) = result {
+            self.pending.push_back(WebsocketError::Conduit(e));
+            return;
+        }
+        if shard_count < self.shards.len() {
+            for shard in self.shards.drain(shard_count..) {
+                shard.abort();
+            }
+        }
+        for index in self.shards.len()..shard_count {
+            self.start(index);
+        }
+    }
+
+    /// Count an event and scale the conduit if the interval has passed
+    fn record_event(&mut self) {
+        let scale = match &self.scale {
+            Some(scale) => scale,
+            None => return,
+        };
+        self.window.1 += 1;
+        let elapsed = self.window.0.elapsed();
+        if elapsed < scale.interval {
+            return;
+        }
+        let shard_count = scale.shards_for(self.window.1, elapsed);
+        self.window = (Instant::now(), 0);
+        if shard_count != self.shards.len() {
+            self.resize(shard_count);
+        }
+    }
+
+    /// Start a shard on a new session, stopping the session it had
+    fn start(&mut self, index: usize) {
+        if let Some(shard) = self.shards.get(index) {
+            shard.abort();
+        }
+        let session = ShardSession {
+            shared: self.shared.clone(),
+            shard_id: index.to_string().into(),
+        };
+        self.spawn(index, Runtime::new(session, self.options.clone()));
+    }
+
+    /// Poll the session of a shard for its next event
+    fn spawn(&mut self, index: usize, mut runtime: Runtime<ShardSession<C, T>>) {
+        let (handle, registration) = AbortHandle::new_pair();
+        if index < self.shards.len() {
+            self.shards[index] = handle;
+        } else {
+            self.shards.push(handle);
+        }
+        let step = Abortable::new(
+            async move {
+                let item = runtime.next().await;
+                (index, runtime, item)
+            },
+            registration,
+        );
+        self.running.push(Box::pin(async move {
+            Task::Shard(step.await.ok().map(Box::new))
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures::SinkExt;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite;
+
+    use super::*;
+
+    /// Records conduit requests and responds like Twitch
+    #[derive(Default)]
+    struct ConduitClient(Mutex<Vec<(String, serde_json::Value)>>);
+
+    impl ConduitClient {
+        /// The last session assigned to `shard`
+        fn assigned(&self, shard: &str) -> Option<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(path, _)| path.ends_with("/shards"))
+                .filter(|(_, body)| body["shards"][0]["id"] == shard)
+                .map(|(_, body)| {
+                    body["shards"][0]["transport"]["session_id"]
+                        .as_str()
+                        .unwrap()
+                        .to_owned()
+                })
+                .next_back()
+        }
+    }
+
+    impl HttpClient for ConduitClient {
+        type Error = std::io::Error;
+
+        fn req(
+            &self,
+            req: crate::client::Request,
+        ) -> BoxedFuture<'_, Result<crate::client::Response, Self::Error>> {
+            let path = req.uri().path().to_owned();
+            let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+            let response = if path.ends_with("/shards") {
+                serde_json::json!({
+                    "data": [{
+                        "id": body["shards"][0]["id"],
+                        "status": "enabled",
+                        "transport": {
+                            "method": "websocket",
+                            "session_id": body["shards"][0]["transport"]["session_id"],
+                            "connected_at": "2020-11-10T14:32:18.730260295Z",
+                        },
+                    }],
+                    "errors": [],
+                })
+            } else if path.ends_with("/subscriptions") {
+                serde_json::json!({
+                    "data": [{
+                        "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+                        "status": "enabled",
+                        "type": body["type"],
+                        "version": body["version"],
+                        "condition": body["condition"],
+                        "created_at": "2023-04-11T10:11:12.123Z",
+                        "transport": body["transport"],
+                        "cost": 0,
+                    }],
+                    "total": 1,
+                    "total_cost": 0,
+                    "max_total_cost": 10000,
+                })
+            } else {
+                serde_json::json!({
+                    "data": [{"id": "conduit", "shard_count": body["shard_count"]}],
+                })
+            };
+            self.0.lock().unwrap().push((path, body));
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(200)
+                    .body(serde_json::to_vec(&response).unwrap().into())
+                    .unwrap())
+            })
+        }
+    }
+
+    fn welcome(session: &str) -> String {
+        format!(
+            r#"{{"metadata":{{"message_id":"welcome","message_type":"session_welcome","message_timestamp":"2022-10-19T14:56:51.634234626Z"}},"payload":{{"session":{{"id":"{}","status":"connected","connected_at":"2022-10-19T14:56:51.616329898Z","keepalive_timeout_seconds":10,"reconnect_url":null,"recovery_url":null}}}}}}"#,
+            session
+        )
+    }
+
+    fn notification(session: &str) -> String {
+        format!(
+            r#"{{"metadata":{{"message_id":"{0}","message_type":"notification","message_timestamp":"{now}","subscription_type":"user.update","subscription_version":"1"}},"payload":{{"subscription":{{"id":"26b1c993-bfcf-44d9-b876-379dacafe75a","type":"user.update","version":"1","status":"enabled","cost":0,"condition":{{"user_id":"1234"}},"transport":{{"method":"conduit","conduit_id":"conduit"}},"created_at":"2022-10-19T14:56:51.616329898Z"}},"event":{{"user_id":"1234","user_login":"{0}","user_name":"{0}","description":""}}}}}}"#,
+            session,
+            now = crate::types::Timestamp::now(),
+        )
+    }
+
+    fn disabled(shard: &str) -> String {
+        format!(
+            r#"{{"metadata":{{"message_id":"disabled","message_type":"notification","message_timestamp":"{now}","subscription_type":"conduit.shard.disabled","subscription_version":"1"}},"payload":{{"subscription":{{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","type":"conduit.shard.disabled","version":"1","status":"enabled","cost":0,"condition":{{"client_id":"clientid"}},"transport":{{"method":"conduit","conduit_id":"conduit"}},"created_at":"2023-04-11T10:11:12.123Z"}},"event":{{"conduit_id":"conduit","shard_id":"{}","status":"websocket_disconnected","transport":{{"method":"websocket","session_id":"session0","connected_at":"2020-11-10T14:32:18.730260295Z","disconnected_at":"2020-11-11T14:32:18.730260295Z"}}}}}}}}"#,
+            shard,
+            now = crate::types::Timestamp::now(),
+        )
+    }
+
+    /// Read events until a notification from `session`
+    async fn until_notification<S>(stream: &mut S, session: &str)
+    where S: futures::Stream<Item = Result<Event, WebsocketError<std::io::Error>>> + Unpin {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while let Some(event) = stream.next().await {
+                if let Ok(Event::UserUpdateV1(payload)) = event {
+                    match payload.message {
+                        Message::Notification(n) if n.user_login.as_str() == session => return,
+                        _ => {}
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn scale() {
+        let policy = ScalePolicy::new(10).max_shards(4);
+        let minute = Duration::from_secs(60);
+        assert_eq!(policy.shards_for(0, minute), 1);
+        assert_eq!(policy.shards_for(600, minute), 1);
+        assert_eq!(policy.shards_for(601, minute), 2);
+        assert_eq!(policy.shards_for(100_000, minute), 4);
+        assert_eq!(policy.min_shards(2).shards_for(0, minute), 2);
+    }
+
+    #[tokio::test]
+    async fn manages_shards() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for connection in 0.. {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let session = format!("session{}", connection);
+                let messages = match connection {
+                    // Twitch disables shard 0, whichever shard this session belongs to
+                    0 => vec![welcome(&session), disabled("0")],
+                    _ => vec![welcome(&session), notification(&session)],
+                };
+                tokio::spawn(async move {
+                    for message in messages {
+                        socket
+                            .send(tungstenite::Message::Text(message))
+                            .await
+                            .unwrap();
+                    }
+                    while let Some(Ok(_)) = socket.next().await {}
+                });
+            }
+        });
+
+        let token = twitch_oauth2::AppAccessToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            twitch_oauth2::ClientSecret::new("secret".to_string()),
+            None,
+            None,
+        );
+        let manager = ConduitManager::new(
+            HelixClient::with_client(ConduitClient::default()),
+            token,
+            "conduit",
+            2,
+        )
+        .with_url(url.parse().unwrap());
+        let shared = manager.shared.clone();
+        let handle = manager.handle();
+        let stream = manager.into_stream();
+        futures::pin_mut!(stream);
+
+        // the first two sessions are the initial shards, shard 0 is disabled and started on a new session
+        until_notification(&mut stream, "session2").await;
+        let client = shared.helix.get_client();
+        assert!(client.0.lock().unwrap().iter().any(|(_, body)| {
+            body["type"] == "conduit.shard.disabled"
+                && body["condition"]["conduit_id"] == "conduit"
+                && body["transport"]["conduit_id"] == "conduit"
+        }));
+        assert!(client
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(_, body)| body["shard_count"] == 2));
+        assert_eq!(client.assigned("0").as_deref(), Some("session2"));
+        assert!(client.assigned("1").is_some());
+
+        handle.resize(3);
+        until_notification(&mut stream, "session3").await;
+        assert_eq!(
+            client.0.lock().unwrap().last().unwrap().1["shards"][0]["id"],
+            "2"
+        );
+        assert_eq!(client.assigned("2").as_deref(), Some("session3"));
+        assert!(client
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(_, body)| body["shard_count"] == 3));
+    }
+}
@@ -6,6 +6,11 @@ use super::*;
 pub(crate) mod client_ext;
 #[cfg(feature = "unsupported")]
 mod custom;
+pub mod instrument;
+pub mod interceptor;
+pub mod jwt;
+pub mod ratelimit;
+pub mod retry;
 
 #[doc(inline)]
 pub use client_ext::ClientExtError;
@@ -71,7 +76,11 @@ impl<C: crate::HttpClient + crate::client::ClientDefault<'static>> Default
 #[cfg(feature = "helix")] // this is needed due to a bug?
 pub struct HelixClient<'a, C: 'a> {
     pub(crate) client: C,
-    pub(crate) _pd: std::marker::PhantomData<&'a ()>, // TODO: Implement rate limiter...
+    pub(crate) ratelimiter: Option<ratelimit::RateLimiter>,
+    pub(crate) retry_policy: Option<retry::RetryPolicy>,
+    pub(crate) strict_scopes: bool,
+    pub(crate) interceptors: Vec<std::sync::Arc<dyn interceptor::Interceptor>>,
+    pub(crate) _pd: std::marker::PhantomData<&'a ()>,
 }
 
 #[cfg(feature = "helix")]
@@ -81,6 +90,10 @@ where C: crate::HttpClient + Clone + 'a
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            ratelimiter: self.ratelimiter.clone(),
+            retry_policy: self.retry_policy.clone(),
+            strict_scopes: self.strict_scopes,
+            interceptors: self.interceptors.clone(),
             _pd: self._pd,
         }
     }
@@ -91,10 +104,93 @@ impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
     pub const fn with_client(client: C) -> Self {
         HelixClient {
             client,
+            ratelimiter: None,
+            retry_policy: None,
+            strict_scopes: false,
+            interceptors: Vec::new(),
             _pd: std::marker::PhantomData,
         }
     }
 
+    /// Use a [`RateLimiter`](ratelimit::RateLimiter) for all requests made with this client
+    ///
+    /// Requests will wait when the rate limit bucket for the token is empty, and requests rejected with `429 Too Many Requests` are retried after the bucket is reset.
+    ///
+    /// ```rust
+    /// use twitch_api::helix::{ratelimit::RateLimiter, HelixClient};
+    /// # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+    /// let client: HelixClient<reqwest::Client> =
+    ///     HelixClient::new().with_ratelimiter(RateLimiter::new(tokio::time::sleep));
+    /// ```
+    pub fn with_ratelimiter(mut self, ratelimiter: ratelimit::RateLimiter) -> Self {
+        self.ratelimiter = Some(ratelimiter);
+        self
+    }
+
+    /// Retrieve the [`RateLimiter`](ratelimit::RateLimiter) used by this client, if any
+    pub const fn ratelimiter(&self) -> Option<&ratelimit::RateLimiter> { self.ratelimiter.as_ref() }
+
+    /// Use a [`RetryPolicy`](retry::RetryPolicy) for all requests made with this client
+    ///
+    /// Requests that fail with a retryable status or client error are retried with exponential backoff.
+    ///
+    /// ```rust
+    /// use twitch_api::helix::{retry::RetryPolicy, HelixClient};
+    /// # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+    /// let client: HelixClient<reqwest::Client> =
+    ///     HelixClient::new().with_retry_policy(RetryPolicy::new(tokio::time::sleep));
+    /// ```
+    pub fn with_retry_policy(mut self, retry_policy: retry::RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Retrieve the [`RetryPolicy`](retry::RetryPolicy) used by this client, if any
+    pub const fn retry_policy(&self) -> Option<&retry::RetryPolicy> { self.retry_policy.as_ref() }
+
+    /// Check the scopes of the token against [`Request::SCOPE`] before sending a request
+    ///
+    /// When enabled, requests with a token that is missing required scopes fail with [`ClientRequestError::MissingScope`] without contacting Twitch.
+    /// With the `tracing` feature, tokens missing any of [`Request::OPT_SCOPE`] are also logged.
+    ///
+    /// ```rust
+    /// use twitch_api::HelixClient;
+    /// # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+    /// let client: HelixClient<reqwest::Client> = HelixClient::new().with_strict_scopes(true);
+    /// ```
+    pub fn with_strict_scopes(mut self, strict_scopes: bool) -> Self {
+        self.strict_scopes = strict_scopes;
+        self
+    }
+
+    /// Returns `true` if the scopes of tokens are checked before sending a request, see [`HelixClient::with_strict_scopes`]
+    pub const fn strict_scopes(&self) -> bool { self.strict_scopes }
+
+    /// Add an [`Interceptor`](interceptor::Interceptor) to all requests made with this client
+    ///
+    /// Interceptors are called in the order they were added, see the [`interceptor`] module.
+    ///
+    /// ```rust
+    /// use twitch_api::helix::{interceptor::Interceptor, HelixClient};
+    /// # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+    /// struct Noop;
+    /// impl Interceptor for Noop {}
+    ///
+    /// let client: HelixClient<reqwest::Client> = HelixClient::new().with_interceptor(Noop);
+    /// ```
+    pub fn with_interceptor(
+        mut self,
+        interceptor: impl interceptor::Interceptor + 'static,
+    ) -> Self {
+        self.interceptors.push(std::sync::Arc::new(interceptor));
+        self
+    }
+
+    /// Retrieve the [`Interceptor`s](interceptor::Interceptor) used by this client
+    pub fn interceptors(&self) -> &[std::sync::Arc<dyn interceptor::Interceptor>] {
+        &self.interceptors
+    }
+
     /// Create a new [`HelixClient`] with a default [`HttpClient`][crate::HttpClient]
     pub fn new() -> Self
     where C: crate::client::ClientDefault<'a> {
@@ -140,14 +236,10 @@ impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
         T: TwitchToken + ?Sized,
         C: Send,
     {
+        self.check_scopes::<R, _>(token)?;
         let req = request.create_request(token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?
-            .into_response_vec();
+        let response = self.send(R::PATH, req, token).await?.into_response_vec();
         <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
     }
 
@@ -164,15 +256,11 @@ impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
         D: serde::de::DeserializeOwned + PartialEq,
         T: TwitchToken + ?Sized,
     {
+        self.check_scopes::<R, _>(token)?;
         let req =
             request.create_request(body, token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?
-            .into_response_vec();
+        let response = self.send(R::PATH, req, token).await?.into_response_vec();
         <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
     }
 
@@ -189,15 +277,11 @@ impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
         D: serde::de::DeserializeOwned + PartialEq,
         T: TwitchToken + ?Sized,
     {
+        self.check_scopes::<R, _>(token)?;
         let req =
             request.create_request(body, token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?
-            .into_response_vec();
+        let response = self.send(R::PATH, req, token).await?.into_response_vec();
         <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
     }
 
@@ -212,14 +296,10 @@ impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
         D: serde::de::DeserializeOwned + PartialEq,
         T: TwitchToken + ?Sized,
     {
+        self.check_scopes::<R, _>(token)?;
         let req = request.create_request(token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?
-            .into_response_vec();
+        let response = self.send(R::PATH, req, token).await?.into_response_vec();
         <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
     }
 
@@ -236,15 +316,349 @@ impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
         D: serde::de::DeserializeOwned + PartialEq,
         T: TwitchToken + ?Sized,
     {
+        self.check_scopes::<R, _>(token)?;
         let req =
             request.create_request(body, token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
+        let response = self.send(R::PATH, req, token).await?.into_response_vec();
+        <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
+    }
+
+    /// Check that the token has the scopes needed for the request, if strict scopes are enabled
+    #[allow(clippy::result_large_err)]
+    fn check_scopes<R, T>(
+        &self,
+        token: &T,
+    ) -> Result<(), ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        R: Request,
+        T: TwitchToken + ?Sized,
+    {
+        if !self.strict_scopes {
+            return Ok(());
+        }
+        let scopes = token.scopes();
+        if !R::SCOPE.matches(scopes) {
+            return Err(ClientRequestError::MissingScope {
+                path: R::PATH,
+                required: R::SCOPE,
+            });
+        }
+        #[cfg(feature = "tracing")]
+        for scope in R::OPT_SCOPE.iter().filter(|s| !scopes.contains(s)) {
+            tracing::debug!(
+                path = R::PATH,
+                %scope,
+                "token is missing optional scope, response may be less detailed"
+            );
+        }
+        Ok(())
+    }
+
+    /// Send a request, refreshing the token if it supports it
+    ///
+    /// See [`TwitchToken::refresh_expiring`] and [`TwitchToken::refresh_rejected`]
+    pub(crate) async fn send<T>(
+        &self,
+        path: &'static str,
+        req: crate::client::Request,
+        token: &T,
+    ) -> Result<crate::client::Response, ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        T: TwitchToken + ?Sized,
+    {
+        let info = interceptor::RequestInfo::new(path, req.method().clone());
+        instrument::instrument(&info, async {
+            let mut attempts = 0;
+            let result = self.send_refreshing(&info, req, token, &mut attempts).await;
+            (result, attempts)
+        })
+        .await
+    }
+
+    /// Send a request, refreshing the token if it is expiring or was rejected
+    async fn send_refreshing<T>(
+        &self,
+        info: &interceptor::RequestInfo,
+        mut req: crate::client::Request,
+        token: &T,
+        attempts: &mut u32,
+    ) -> Result<crate::client::Response, ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        T: TwitchToken + ?Sized,
+    {
+        if let Some(refresh) = token.refresh_expiring() {
+            refresh.await;
+        }
+        // the request was created with `token()`, which can be older than the latest token
+        set_authorization(&mut req, token)?;
+        let used = token.latest_token().into_owned();
+        let key = ratelimit::BucketKey::from_token(token);
         let response = self
-            .client
-            .req(req)
+            .send_retrying(info, clone_request(&req), &key, attempts)
+            .await?;
+        if response.status() != http::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let refreshed = match token.refresh_rejected(&used) {
+            Some(refresh) => refresh.await,
+            None => false,
+        };
+        if !refreshed {
+            return Ok(response);
+        }
+        set_authorization(&mut req, token)?;
+        self.send_retrying(info, req, &key, attempts).await
+    }
+
+    /// Send a request authorized with an extension JWT
+    pub(crate) async fn send_with_jwt<J>(
+        &self,
+        path: &'static str,
+        req: crate::client::Request,
+        jwt: &J,
+    ) -> Result<crate::client::Response, ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        J: jwt::JwtToken + ?Sized,
+    {
+        let key = ratelimit::BucketKey::from_client_id(jwt.client_id());
+        let info = interceptor::RequestInfo::new(path, req.method().clone());
+        instrument::instrument(&info, async {
+            let mut attempts = 0;
+            let result = self.send_retrying(&info, req, &key, &mut attempts).await;
+            (result, attempts)
+        })
+        .await
+    }
+
+    /// Send a request, respecting the rate limiter and retry policy if set
+    ///
+    /// `attempts` counts the times the request was sent
+    async fn send_retrying(
+        &self,
+        info: &interceptor::RequestInfo,
+        req: crate::client::Request,
+        key: &ratelimit::BucketKey,
+        attempts: &mut u32,
+    ) -> Result<crate::client::Response, ClientRequestError<<C as crate::HttpClient>::Error>> {
+        let retry_policy = match &self.retry_policy {
+            Some(policy) if policy.allows(req.method()) => policy,
+            _ => {
+                return self
+                    .send_ratelimited(info, req, key, attempts)
+                    .await
+                    .map_err(ClientRequestError::RequestError)
+            }
+        };
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .send_ratelimited(info, clone_request(&req), key, attempts)
+                .await;
+            if !retry_policy.should_retry(&result, attempt) {
+                return result.map_err(ClientRequestError::RequestError);
+            }
+            retry_policy.wait(attempt).await;
+            attempt += 1;
+        }
+    }
+
+    /// Send a request, respecting the rate limiter if one is set
+    async fn send_ratelimited(
+        &self,
+        info: &interceptor::RequestInfo,
+        req: crate::client::Request,
+        key: &ratelimit::BucketKey,
+        attempts: &mut u32,
+    ) -> Result<crate::client::Response, <C as crate::HttpClient>::Error> {
+        let Some(ratelimiter) = &self.ratelimiter else {
+            return self.send_intercepted(info, req, attempts).await;
+        };
+        let mut attempt = 0;
+        loop {
+            ratelimiter.acquire(key).await;
+            let response = self
+                .send_intercepted(info, clone_request(&req), attempts)
+                .await?;
+            ratelimiter.update(key, response.headers());
+            if !ratelimiter.wait_for_retry(&response, attempt).await {
+                return Ok(response);
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Send a request through the interceptors, see [`interceptor`]
+    async fn send_intercepted(
+        &self,
+        info: &interceptor::RequestInfo,
+        mut req: crate::client::Request,
+        attempts: &mut u32,
+    ) -> Result<crate::client::Response, <C as crate::HttpClient>::Error> {
+        let info = &interceptor::RequestInfo {
+            attempt: *attempts,
+            ..info.clone()
+        };
+        *attempts += 1;
+        for (i, interceptor) in self.interceptors.iter().enumerate() {
+            if let Some(mut response) = interceptor.on_request(info, &mut req).await {
+                for interceptor in self.interceptors[..i].iter().rev() {
+                    interceptor.on_response(info, &mut response).await;
+                }
+                return Ok(response);
+            }
+        }
+        let mut response = self.client.req(req).await?;
+        for interceptor in self.interceptors.iter().rev() {
+            interceptor.on_response(info, &mut response).await;
+        }
+        Ok(response)
+    }
+}
+
+/// Replace the authorization of a request with the current token
+fn set_authorization<T: TwitchToken + ?Sized>(
+    req: &mut crate::client::Request,
+    token: &T,
+) -> Result<(), CreateRequestError> {
+    let mut bearer =
+        http::HeaderValue::from_str(&format!("Bearer {}", token.latest_token().secret())).map_err(
+            |_| CreateRequestError::Custom("Could not make token into headervalue".into()),
+        )?;
+    bearer.set_sensitive(true);
+    req.headers_mut()
+        .insert(http::header::AUTHORIZATION, bearer);
+    Ok(())
+}
+
+/// Clone a request so it can be sent again
+pub(crate) fn clone_request(req: &crate::client::Request) -> crate::client::Request {
+    let mut clone = http::Request::new(req.body().clone());
+    *clone.method_mut() = req.method().clone();
+    *clone.uri_mut() = req.uri().clone();
+    *clone.version_mut() = req.version();
+    *clone.headers_mut() = req.headers().clone();
+    clone
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn strict_scopes() {
+        let token = twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            Some(vec![twitch_oauth2::Scope::UserReadEmail]),
+            None,
+        );
+        let client =
+            HelixClient::with_client(crate::client::DummyHttpClient).with_strict_scopes(true);
+        let req = raids::StartARaidRequest::new("1234", "5678");
+        let err = client
+            .req_post(req, Default::default(), &token)
             .await
-            .map_err(ClientRequestError::RequestError)?
-            .into_response_vec();
-        <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
+            .unwrap_err();
+        match err {
+            ClientRequestError::MissingScope { path, required } => {
+                assert_eq!(path, "raids");
+                assert!(required.matches(&[twitch_oauth2::Scope::ChannelManageRaids]));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        // requests with the required scopes are sent
+        let req = users::GetUsersRequest::logins(&["justintv"][..]);
+        assert!(matches!(
+            client.req_get(req, &token).await,
+            Err(ClientRequestError::RequestError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn refresh_rejected_token() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Rejects the initial token
+        #[derive(Default)]
+        struct Helix(AtomicUsize);
+
+        impl crate::HttpClient for Helix {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                req: crate::client::Request,
+            ) -> crate::client::BoxedFuture<'_, Result<crate::client::Response, Self::Error>>
+            {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                let status = match req.headers()[http::header::AUTHORIZATION].to_str() {
+                    Ok("Bearer token") => 401,
+                    _ => 200,
+                };
+                Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(status)
+                        .body(Default::default())
+                        .unwrap())
+                })
+            }
+        }
+
+        /// Refreshes tokens
+        struct Auth;
+
+        impl twitch_oauth2::client::Client for Auth {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                _: http::Request<Vec<u8>>,
+            ) -> twitch_oauth2::client::BoxedFuture<'_, Result<http::Response<Vec<u8>>, Self::Error>>
+            {
+                Box::pin(async {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .body(
+                            br#"{"access_token":"newtoken","refresh_token":"refresh","expires_in":14400,"token_type":"bearer"}"#
+                                .to_vec(),
+                        )
+                        .unwrap())
+                })
+            }
+        }
+
+        let token = twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            twitch_oauth2::RefreshToken::new("refresh".to_string()),
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            twitch_oauth2::ClientSecret::new("secret".to_string()),
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        );
+        let token = twitch_oauth2::RefreshingToken::new(token, Auth);
+        let client = HelixClient::with_client(Helix::default());
+        let req = users::GetUsersRequest::logins(&["justintv"][..]);
+        let req = req
+            .create_request(token.token().secret(), token.client_id().as_str())
+            .unwrap();
+        let response = client
+            .send("users", clone_request(&req), &token)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 2);
+        assert_eq!(token.latest_token().secret(), "newtoken");
+
+        // the request was created with the old token, but is sent with the new one
+        let response = client.send("users", req, &token).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 3);
     }
 }
@@ -990,6 +990,313 @@ impl<'client, C: crate::HttpClient + Sync + 'client> HelixClient<'client, C> {
             .data)
     }
 
+    #[cfg(feature = "beta")]
+    /// Get the Guest Star settings of a broadcaster
+    pub async fn get_channel_guest_star_settings<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::GuestStarSettings, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_get(
+                helix::guest_star::GetChannelGuestStarSettingsRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                ),
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Update the Guest Star settings of a broadcaster
+    pub async fn update_channel_guest_star_settings<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        settings: helix::guest_star::UpdateChannelGuestStarSettingsBody,
+        token: &T,
+    ) -> Result<helix::guest_star::UpdateChannelGuestStarSettings, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_put(
+                helix::guest_star::UpdateChannelGuestStarSettingsRequest::new(broadcaster_id),
+                settings,
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Get the ongoing Guest Star session of a broadcaster, if any
+    pub async fn get_guest_star_session<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<Option<helix::guest_star::GuestStarSession>, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_get(
+                helix::guest_star::GetGuestStarSessionRequest::new(broadcaster_id, moderator_id),
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Create a Guest Star session for a broadcaster
+    pub async fn create_guest_star_session<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::GuestStarSession, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_post(
+                helix::guest_star::CreateGuestStarSessionRequest::new(broadcaster_id),
+                helix::EmptyBody,
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// End the Guest Star session of a broadcaster
+    pub async fn end_guest_star_session<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::GuestStarSession, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_delete(
+                helix::guest_star::EndGuestStarSessionRequest::new(broadcaster_id, session_id),
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Get the pending invites to a Guest Star session
+    pub async fn get_guest_star_invites<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<Vec<helix::guest_star::GuestStarInvite>, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_get(
+                helix::guest_star::GetGuestStarInvitesRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                    session_id,
+                ),
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Invite a user to a Guest Star session
+    pub async fn send_guest_star_invite<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        guest_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::SendGuestStarInvite, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_post(
+                helix::guest_star::SendGuestStarInviteRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                    session_id,
+                    guest_id,
+                ),
+                helix::EmptyBody,
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Revoke the invite of a user to a Guest Star session
+    pub async fn delete_guest_star_invite<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        guest_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::DeleteGuestStarInvite, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_delete(
+                helix::guest_star::DeleteGuestStarInviteRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                    session_id,
+                    guest_id,
+                ),
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Assign an invited guest that is ready to a slot in a Guest Star session
+    pub async fn assign_guest_star_slot<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        guest_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        slot_id: impl types::IntoCow<'b, types::GuestStarSlotIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::AssignGuestStarSlot, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_post(
+                helix::guest_star::AssignGuestStarSlotRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                    session_id,
+                    guest_id,
+                    slot_id,
+                ),
+                helix::EmptyBody,
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Move the guest in a slot to another slot in a Guest Star session. If the destination slot is occupied, the guests are swapped.
+    pub async fn update_guest_star_slot<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        source_slot_id: impl types::IntoCow<'b, types::GuestStarSlotIdRef> + Send + 'b,
+        destination_slot_id: impl types::IntoCow<'b, types::GuestStarSlotIdRef> + Send + 'b,
+        token: &T,
+    ) -> Result<helix::guest_star::UpdateGuestStarSlot, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_patch(
+                helix::guest_star::UpdateGuestStarSlotRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                    session_id,
+                    source_slot_id,
+                )
+                .destination_slot_id(destination_slot_id),
+                helix::EmptyBody,
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Remove a guest from their slot in a Guest Star session, optionally sending them back to the invite queue
+    #[allow(clippy::too_many_arguments)]
+    pub async fn delete_guest_star_slot<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        guest_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        slot_id: impl types::IntoCow<'b, types::GuestStarSlotIdRef> + Send + 'b,
+        reinvite: bool,
+        token: &T,
+    ) -> Result<helix::guest_star::DeleteGuestStarSlot, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        Ok(self
+            .req_delete(
+                helix::guest_star::DeleteGuestStarSlotRequest::new(
+                    broadcaster_id,
+                    moderator_id,
+                    session_id,
+                    guest_id,
+                    slot_id,
+                )
+                .should_reinvite_guest(reinvite),
+                token,
+            )
+            .await?
+            .data)
+    }
+
+    #[cfg(feature = "beta")]
+    /// Update the settings of a slot in a Guest Star session, settings that are [`None`] are left unchanged
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_guest_star_slot_settings<'b, T>(
+        &'client self,
+        broadcaster_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        moderator_id: impl types::IntoCow<'b, types::UserIdRef> + Send + 'b,
+        session_id: impl types::IntoCow<'b, types::GuestStarSessionIdRef> + Send + 'b,
+        slot_id: impl types::IntoCow<'b, types::GuestStarSlotIdRef> + Send + 'b,
+        is_audio_enabled: impl Into<Option<bool>>,
+        is_video_enabled: impl Into<Option<bool>>,
+        is_live: impl Into<Option<bool>>,
+        volume: impl Into<Option<u8>>,
+        token: &T,
+    ) -> Result<helix::guest_star::UpdateGuestStarSlotSettings, ClientError<C>>
+    where
+        T: TwitchToken + Send + Sync + ?Sized,
+    {
+        let mut request = helix::guest_star::UpdateGuestStarSlotSettingsRequest::new(
+            broadcaster_id,
+            moderator_id,
+            session_id,
+            slot_id,
+        );
+        request.is_audio_enabled = is_audio_enabled.into();
+        request.is_video_enabled = is_video_enabled.into();
+        request.is_live = is_live.into();
+        request.volume = volume.into();
+        Ok(self.req_patch(request, helix::EmptyBody, token).await?.data)
+    }
+
     // FIXME: Example should use https://github.com/twitch-rs/twitch_api/issues/162
     /// Get all scheduled streams in a channel.
     ///
@@ -2107,6 +2414,74 @@ impl<'client, C: crate::HttpClient + Sync + 'client> HelixClient<'client, C> {
             .await
             .map(|response| response.data)
     }
+
+    /// Download and parse the CSV report of an [analytics endpoint](helix::analytics)
+    ///
+    /// The `url` is the [`url`](helix::analytics::GameAnalytics::url) of a report, which is only valid for a few minutes.
+    /// The URL is pre-signed, so the request is sent through the [`HttpClient`](crate::HttpClient) without any authorization.
+    /// Every row of the report is deserialized into `R` by its column names.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    /// # let client: helix::HelixClient<'static, twitch_api::client::DummyHttpClient> = helix::HelixClient::default();
+    /// # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+    /// # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+    /// use twitch_api::helix::{self, analytics::GetGameAnalyticsRequest};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Row {
+    ///     #[serde(rename = "Date")]
+    ///     date: String,
+    ///     #[serde(rename = "Live Views")]
+    ///     live_views: Option<u64>,
+    /// }
+    ///
+    /// let reports = client
+    ///     .req_get(GetGameAnalyticsRequest::game_id("493057"), &token)
+    ///     .await?
+    ///     .data;
+    /// for report in reports {
+    ///     let rows: Vec<Row> = client.get_analytics_report(&report.url).await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "analytics_report")]
+    pub async fn get_analytics_report<R>(
+        &'client self,
+        url: &str,
+    ) -> Result<Vec<R>, ClientExtError<C, csv::Error>>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri(url)
+            .body(Default::default())
+            .map_err(|e| {
+                ClientExtError::ClientError(helix::CreateRequestError::HttpError(e).into())
+            })?;
+        let response = self
+            .client
+            .req(req)
+            .await
+            .map_err(|e| ClientExtError::ClientError(ClientRequestError::RequestError(e)))?;
+        if !response.status().is_success() {
+            return Err(ClientExtError::ClientError(ClientRequestError::Custom(
+                format!(
+                    "could not download report, got status {}",
+                    response.status()
+                )
+                .into(),
+            )));
+        }
+        csv::Reader::from_reader(response.body().as_ref())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map_err(ClientExtError::Other)
+    }
 }
 
 /// Error type to combine a http client error with a other error
@@ -2295,3 +2670,59 @@ where
     })
     .boxed()
 }
+
+#[cfg(all(test, feature = "analytics_report"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn analytics_report() {
+        /// Serves a report
+        #[derive(Debug)]
+        struct Reports;
+
+        impl crate::HttpClient for Reports {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                req: crate::client::Request,
+            ) -> crate::client::BoxedFuture<'_, Result<crate::client::Response, Self::Error>>
+            {
+                assert!(!req.headers().contains_key(http::header::AUTHORIZATION));
+                Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .body("Date,Game Name,Live Views\n2018-03-01,Oculus Lords,12\n2018-03-02,Oculus Lords,\n".into())
+                        .unwrap())
+                })
+            }
+        }
+
+        #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+        struct Row {
+            #[serde(rename = "Date")]
+            date: String,
+            #[serde(rename = "Live Views")]
+            live_views: Option<u64>,
+        }
+
+        let client = HelixClient::with_client(Reports);
+        let rows: Vec<Row> = client
+            .get_analytics_report("https://reports.example.com/report.csv")
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                Row {
+                    date: "2018-03-01".to_string(),
+                    live_views: Some(12)
+                },
+                Row {
+                    date: "2018-03-02".to_string(),
+                    live_views: None
+                },
+            ]
+        );
+    }
+}
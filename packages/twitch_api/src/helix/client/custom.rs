@@ -46,13 +46,10 @@ impl<C: crate::HttpClient> HelixClient<'_, C> {
         T: TwitchToken + ?Sized,
         C: Send,
     {
+        self.check_scopes::<R, _>(token)?;
         let req = request.create_request(token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?;
+        let response = self.send(R::PATH, req, token).await?;
         {
             let request = Some(request);
             let uri = &uri;
@@ -107,14 +104,11 @@ impl<C: crate::HttpClient> HelixClient<'_, C> {
         T: TwitchToken + ?Sized,
         C: Send,
     {
+        self.check_scopes::<R, _>(token)?;
         let req =
             request.create_request(body, token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?;
+        let response = self.send(R::PATH, req, token).await?;
         {
             let request = Some(request);
             let uri = &uri;
@@ -176,14 +170,11 @@ impl<C: crate::HttpClient> HelixClient<'_, C> {
         C: Send,
         F: Fn(&R, &http::Uri, &str, http::StatusCode) -> Result<(), HelixRequestPatchError>,
     {
+        self.check_scopes::<R, _>(token)?;
         let req =
             request.create_request(body, token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?;
+        let response = self.send(R::PATH, req, token).await?;
         {
             let uri = &uri;
             let text = std::str::from_utf8(response.body()).map_err(|e| {
@@ -242,13 +233,10 @@ impl<C: crate::HttpClient> HelixClient<'_, C> {
         C: Send,
         F: Fn(&R, &http::Uri, &str, http::StatusCode) -> Result<(), HelixRequestDeleteError>,
     {
+        self.check_scopes::<R, _>(token)?;
         let req = request.create_request(token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?;
+        let response = self.send(R::PATH, req, token).await?;
         {
             let uri = &uri;
             let text = std::str::from_utf8(response.body()).map_err(|e| {
@@ -309,14 +297,11 @@ impl<C: crate::HttpClient> HelixClient<'_, C> {
         C: Send,
         F: Fn(&R, &http::Uri, &str, http::StatusCode) -> Result<(), HelixRequestDeleteError>,
     {
+        self.check_scopes::<R, _>(token)?;
         let req =
             request.create_request(body, token.token().secret(), token.client_id().as_str())?;
         let uri = req.uri().clone();
-        let response = self
-            .client
-            .req(req)
-            .await
-            .map_err(ClientRequestError::RequestError)?;
+        let response = self.send(R::PATH, req, token).await?;
         {
             let uri = &uri;
             let text = std::str::from_utf8(response.body()).map_err(|e| {
@@ -0,0 +1,297 @@
+//! Instrumentation of requests made with [`HelixClient`](super::HelixClient)
+//!
+//! Every call to one of the `req_*` methods is instrumented as a whole, including the attempts made by a [`RateLimiter`](super::ratelimit::RateLimiter) or [`RetryPolicy`](super::retry::RetryPolicy) and token refreshes.
+//!
+//! # Tracing
+//!
+//! With the `tracing` feature, every call is wrapped in an `INFO` span named `helix_request` with the following fields.
+//!
+//! | Field | Description |
+//! |---|---|
+//! | `path` | The [path](crate::helix::Request::PATH) of the endpoint |
+//! | `method` | The method of the request |
+//! | `status` | The status code of the final response |
+//! | `ratelimit.limit` | The rate at which points are added to the [rate limit bucket](super::ratelimit::RateLimit) |
+//! | `ratelimit.remaining` | The points remaining in the rate limit bucket |
+//! | `retries` | The number of times the request was sent again |
+//! | `latency_ms` | The time the call took, in milliseconds |
+//! | `error` | The error returned by the [`HttpClient`](crate::HttpClient), if the call failed |
+//!
+//! # Metrics
+//!
+//! With the `metrics` feature, the following metrics are recorded through the [`metrics`](https://docs.rs/metrics) facade.
+//! Install a recorder, for example from [`metrics-exporter-prometheus`](https://docs.rs/metrics-exporter-prometheus), to export them.
+//!
+//! | Metric | Type | Labels | Description |
+//! |---|---|---|---|
+//! | [`twitch_api_helix_requests_total`](REQUESTS_TOTAL) | counter | `path`, `method`, `status` | Calls made, `status` is `error` if the call failed |
+//! | [`twitch_api_helix_request_duration_seconds`](REQUEST_DURATION_SECONDS) | histogram | `path`, `method` | The time calls took |
+//! | [`twitch_api_helix_retries_total`](RETRIES_TOTAL) | counter | `path`, `method` | Requests sent again |
+//! | [`twitch_api_helix_ratelimit_remaining`](RATELIMIT_REMAINING) | gauge | | The points remaining in the rate limit bucket after the latest call |
+//!
+//! Rate limits are shared by all endpoints, so the gauge is not labeled by `path`.
+
+use super::interceptor::RequestInfo;
+use crate::client::Response;
+
+/// Name of the counter of calls made
+pub const REQUESTS_TOTAL: &str = "twitch_api_helix_requests_total";
+/// Name of the histogram of the time calls took, in seconds
+pub const REQUEST_DURATION_SECONDS: &str = "twitch_api_helix_request_duration_seconds";
+/// Name of the counter of requests sent again
+pub const RETRIES_TOTAL: &str = "twitch_api_helix_retries_total";
+/// Name of the gauge of the points remaining in the rate limit bucket
+pub const RATELIMIT_REMAINING: &str = "twitch_api_helix_ratelimit_remaining";
+
+/// Instrument a call to an endpoint, see the [module documentation](self)
+///
+/// `call` returns its result and the number of times the request was sent.
+pub(crate) async fn instrument<F, E>(info: &RequestInfo, call: F) -> Result<Response, E>
+where
+    F: std::future::Future<Output = (Result<Response, E>, u32)>,
+    E: std::error::Error, {
+    #[cfg(not(any(feature = "tracing", feature = "metrics")))]
+    let _ = info;
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "helix_request",
+        path = info.path,
+        method = %info.method,
+        status = tracing::field::Empty,
+        ratelimit.limit = tracing::field::Empty,
+        ratelimit.remaining = tracing::field::Empty,
+        retries = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    #[cfg(feature = "tracing")]
+    let call = tracing::Instrument::instrument(call, span.clone());
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    let start = std::time::Instant::now();
+
+    let (result, attempts) = call.await;
+
+    #[cfg(not(any(feature = "tracing", feature = "metrics")))]
+    let _ = attempts;
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    {
+        let outcome = Outcome::new(&result, attempts, start.elapsed());
+        #[cfg(feature = "tracing")]
+        outcome.record(&span, &result);
+        #[cfg(feature = "metrics")]
+        outcome.emit(info);
+    }
+    result
+}
+
+/// The outcome of a call to an endpoint
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+struct Outcome {
+    status: Option<http::StatusCode>,
+    ratelimit: Option<super::ratelimit::RateLimit>,
+    retries: u32,
+    latency: std::time::Duration,
+}
+
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+impl Outcome {
+    fn new<E>(result: &Result<Response, E>, attempts: u32, latency: std::time::Duration) -> Self {
+        let response = result.as_ref().ok();
+        Self {
+            status: response.map(|r| r.status()),
+            ratelimit: response
+                .and_then(|r| super::ratelimit::RateLimit::from_headers(r.headers())),
+            retries: attempts.saturating_sub(1),
+            latency,
+        }
+    }
+
+    /// Record the outcome on the span of the call
+    #[cfg(feature = "tracing")]
+    fn record<E: std::error::Error>(&self, span: &tracing::Span, result: &Result<Response, E>) {
+        if let Some(status) = self.status {
+            span.record("status", status.as_u16());
+        }
+        if let Some(ratelimit) = &self.ratelimit {
+            span.record("ratelimit.limit", ratelimit.limit);
+            span.record("ratelimit.remaining", ratelimit.remaining);
+        }
+        span.record("retries", self.retries);
+        span.record(
+            "latency_ms",
+            std::convert::TryFrom::try_from(self.latency.as_millis()).unwrap_or(u64::MAX),
+        );
+        if let Err(e) = result {
+            span.record("error", tracing::field::display(e));
+        }
+    }
+
+    /// Emit the metrics of the call
+    #[cfg(feature = "metrics")]
+    fn emit(&self, info: &RequestInfo) {
+        let method = info.method.to_string();
+        let status = self
+            .status
+            .map_or_else(|| "error".to_owned(), |s| s.as_u16().to_string());
+        metrics::counter!(
+            REQUESTS_TOTAL,
+            "path" => info.path,
+            "method" => method.clone(),
+            "status" => status
+        )
+        .increment(1);
+        metrics::histogram!(
+            REQUEST_DURATION_SECONDS,
+            "path" => info.path,
+            "method" => method.clone()
+        )
+        .record(self.latency.as_secs_f64());
+        if self.retries > 0 {
+            metrics::counter!(RETRIES_TOTAL, "path" => info.path, "method" => method)
+                .increment(u64::from(self.retries));
+        }
+        if let Some(ratelimit) = &self.ratelimit {
+            metrics::gauge!(RATELIMIT_REMAINING).set(f64::from(ratelimit.remaining));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Records the value of counters by name and labels
+    #[derive(Default)]
+    struct Counters(Mutex<HashMap<String, Arc<Counter>>>);
+
+    #[derive(Default)]
+    struct Counter(AtomicU64);
+
+    impl metrics::CounterFn for Counter {
+        fn increment(&self, value: u64) { self.0.fetch_add(value, Ordering::SeqCst); }
+
+        fn absolute(&self, value: u64) { self.0.store(value, Ordering::SeqCst); }
+    }
+
+    impl Counters {
+        fn get(&self, key: &str) -> u64 {
+            self.0
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |c| c.0.load(Ordering::SeqCst))
+        }
+    }
+
+    impl metrics::Recorder for Counters {
+        fn describe_counter(
+            &self,
+            _: metrics::KeyName,
+            _: Option<metrics::Unit>,
+            _: metrics::SharedString,
+        ) {
+        }
+
+        fn describe_gauge(
+            &self,
+            _: metrics::KeyName,
+            _: Option<metrics::Unit>,
+            _: metrics::SharedString,
+        ) {
+        }
+
+        fn describe_histogram(
+            &self,
+            _: metrics::KeyName,
+            _: Option<metrics::Unit>,
+            _: metrics::SharedString,
+        ) {
+        }
+
+        fn register_counter(
+            &self,
+            key: &metrics::Key,
+            _: &metrics::Metadata<'_>,
+        ) -> metrics::Counter {
+            let labels: Vec<_> = key
+                .labels()
+                .map(|l| format!("{}={}", l.key(), l.value()))
+                .collect();
+            let name = format!("{}{{{}}}", key.name(), labels.join(","));
+            let counter = self.0.lock().unwrap().entry(name).or_default().clone();
+            metrics::Counter::from_arc(counter)
+        }
+
+        fn register_gauge(&self, _: &metrics::Key, _: &metrics::Metadata<'_>) -> metrics::Gauge {
+            metrics::Gauge::noop()
+        }
+
+        fn register_histogram(
+            &self,
+            _: &metrics::Key,
+            _: &metrics::Metadata<'_>,
+        ) -> metrics::Histogram {
+            metrics::Histogram::noop()
+        }
+    }
+
+    /// Responds with `503 Service Unavailable` once, then with `200 OK`
+    #[derive(Default)]
+    struct UnavailableOnce(AtomicU64);
+
+    impl crate::HttpClient for UnavailableOnce {
+        type Error = std::convert::Infallible;
+
+        fn req(
+            &self,
+            _: crate::client::Request,
+        ) -> crate::client::BoxedFuture<'_, Result<Response, Self::Error>> {
+            let status = match self.0.fetch_add(1, Ordering::SeqCst) {
+                0 => 503,
+                _ => 200,
+            };
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(status)
+                    .body(Default::default())
+                    .unwrap())
+            })
+        }
+    }
+
+    #[test]
+    fn metrics() {
+        let token = twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        );
+        let client = crate::HelixClient::with_client(UnavailableOnce::default())
+            .with_retry_policy(super::super::retry::RetryPolicy::new(|_| async {}));
+        let counters = Counters::default();
+        metrics::with_local_recorder(&counters, || {
+            futures::executor::block_on(client.send(
+                "users",
+                http::Request::new(Default::default()),
+                &token,
+            ))
+            .unwrap()
+        });
+        assert_eq!(
+            counters.get("twitch_api_helix_requests_total{path=users,method=GET,status=200}"),
+            1
+        );
+        assert_eq!(
+            counters.get("twitch_api_helix_retries_total{path=users,method=GET}"),
+            1
+        );
+    }
+}
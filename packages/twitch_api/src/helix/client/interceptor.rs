@@ -0,0 +1,279 @@
+//! Interceptors for requests made with [`HelixClient`](super::HelixClient)
+//!
+//! An [`Interceptor`] can inspect and modify every request before it is sent with the [`HttpClient`](crate::HttpClient), and every response before it is parsed.
+//! This is useful for metrics, injecting headers, signing requests, caching responses or injecting faults in tests.
+//!
+//! Interceptors run in the order they were added for requests, and in reverse order for responses.
+//! They are called for every attempt, so requests retried by a [`RateLimiter`](super::ratelimit::RateLimiter) or [`RetryPolicy`](super::retry::RetryPolicy) pass through them again.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use twitch_api::{
+//!     client::{BoxedFuture, Request},
+//!     helix::{
+//!         interceptor::{Interceptor, RequestInfo},
+//!         HelixClient,
+//!     },
+//! };
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//!
+//! struct Tracking;
+//!
+//! impl Interceptor for Tracking {
+//!     fn on_request<'a>(
+//!         &'a self,
+//!         info: &'a RequestInfo,
+//!         request: &'a mut Request,
+//!     ) -> BoxedFuture<'a, Option<twitch_api::client::Response>> {
+//!         println!("{} {}", info.method, info.path);
+//!         request
+//!             .headers_mut()
+//!             .insert("x-request-source", http::HeaderValue::from_static("overlay"));
+//!         Box::pin(async { None })
+//!     }
+//! }
+//!
+//! let client: HelixClient<reqwest::Client> = HelixClient::new().with_interceptor(Tracking);
+//! ```
+
+use std::sync::Arc;
+
+use crate::client::{BoxedFuture, Request, Response};
+
+/// Information about the endpoint a request is made to
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestInfo {
+    /// The path of the endpoint, see [`Request::PATH`](crate::helix::Request::PATH)
+    pub path: &'static str,
+    /// The method of the request
+    pub method: http::Method,
+    /// The number of times this request was sent before this attempt
+    ///
+    /// Requests are sent multiple times when they are retried by a [`RateLimiter`](super::ratelimit::RateLimiter) or [`RetryPolicy`](super::retry::RetryPolicy).
+    pub attempt: u32,
+}
+
+impl RequestInfo {
+    /// Create information about the first attempt of a request to the endpoint at `path`
+    pub const fn new(path: &'static str, method: http::Method) -> Self {
+        Self {
+            path,
+            method,
+            attempt: 0,
+        }
+    }
+}
+
+/// Hook into requests made with [`HelixClient`](super::HelixClient), see the [module documentation](self).
+pub trait Interceptor: Send + Sync {
+    /// Inspect or modify a request before it is sent.
+    ///
+    /// Return a response to answer the request without sending it.
+    /// Interceptors added after this one are then skipped, the ones added before it still see the response in [`Interceptor::on_response`].
+    fn on_request<'a>(
+        &'a self,
+        info: &'a RequestInfo,
+        request: &'a mut Request,
+    ) -> BoxedFuture<'a, Option<Response>> {
+        let _ = (info, request);
+        Box::pin(std::future::ready(None))
+    }
+
+    /// Inspect or modify a response before it is parsed.
+    fn on_response<'a>(
+        &'a self,
+        info: &'a RequestInfo,
+        response: &'a mut Response,
+    ) -> BoxedFuture<'a, ()> {
+        let _ = (info, response);
+        Box::pin(std::future::ready(()))
+    }
+}
+
+impl<I: Interceptor + ?Sized> Interceptor for Arc<I> {
+    fn on_request<'a>(
+        &'a self,
+        info: &'a RequestInfo,
+        request: &'a mut Request,
+    ) -> BoxedFuture<'a, Option<Response>> {
+        (**self).on_request(info, request)
+    }
+
+    fn on_response<'a>(
+        &'a self,
+        info: &'a RequestInfo,
+        response: &'a mut Response,
+    ) -> BoxedFuture<'a, ()> {
+        (**self).on_response(info, response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Echo(AtomicUsize);
+
+    impl crate::HttpClient for Echo {
+        type Error = std::convert::Infallible;
+
+        fn req(&self, request: Request) -> BoxedFuture<'_, Result<Response, Self::Error>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            let source = request.headers().get("x-source").cloned();
+            Box::pin(async move {
+                let mut response = http::Response::builder().status(200);
+                if let Some(source) = source {
+                    response = response.header("x-source", source);
+                }
+                Ok(response.body(Default::default()).unwrap())
+            })
+        }
+    }
+
+    /// Records the order it is called in
+    struct Record(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl Interceptor for Record {
+        fn on_request<'a>(
+            &'a self,
+            info: &'a RequestInfo,
+            request: &'a mut Request,
+        ) -> BoxedFuture<'a, Option<Response>> {
+            self.1
+                .lock()
+                .unwrap()
+                .push(format!("{} request {} {}", self.0, info.method, info.path));
+            request
+                .headers_mut()
+                .insert("x-source", http::HeaderValue::from_static(self.0));
+            Box::pin(async { None })
+        }
+
+        fn on_response<'a>(
+            &'a self,
+            _: &'a RequestInfo,
+            response: &'a mut Response,
+        ) -> BoxedFuture<'a, ()> {
+            self.1.lock().unwrap().push(format!(
+                "{} response {:?}",
+                self.0,
+                response.headers().get("x-source").unwrap()
+            ));
+            Box::pin(async {})
+        }
+    }
+
+    /// Answers every request with `503 Service Unavailable`
+    struct Unavailable;
+
+    impl Interceptor for Unavailable {
+        fn on_request<'a>(
+            &'a self,
+            _: &'a RequestInfo,
+            _: &'a mut Request,
+        ) -> BoxedFuture<'a, Option<Response>> {
+            Box::pin(async {
+                Some(
+                    http::Response::builder()
+                        .status(503)
+                        .header("x-source", "unavailable")
+                        .body(Default::default())
+                        .unwrap(),
+                )
+            })
+        }
+    }
+
+    /// Records the attempt of every request
+    #[derive(Default)]
+    struct Attempts(Mutex<Vec<u32>>);
+
+    impl Interceptor for Attempts {
+        fn on_request<'a>(
+            &'a self,
+            info: &'a RequestInfo,
+            _: &'a mut Request,
+        ) -> BoxedFuture<'a, Option<Response>> {
+            self.0.lock().unwrap().push(info.attempt);
+            Box::pin(async { None })
+        }
+    }
+
+    fn token() -> twitch_oauth2::UserToken {
+        twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn order() {
+        let log = Arc::new(Mutex::new(vec![]));
+        let client = crate::HelixClient::with_client(Echo::default())
+            .with_interceptor(Record("first", log.clone()))
+            .with_interceptor(Record("second", log.clone()));
+
+        let response = client
+            .send("users", http::Request::new(Default::default()), &token())
+            .await
+            .unwrap();
+        assert_eq!(response.headers().get("x-source").unwrap(), "second");
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "first request GET users",
+                "second request GET users",
+                r#"second response "second""#,
+                r#"first response "second""#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn short_circuit() {
+        let log = Arc::new(Mutex::new(vec![]));
+        let client = crate::HelixClient::with_client(Echo::default())
+            .with_interceptor(Record("first", log.clone()))
+            .with_interceptor(Unavailable)
+            .with_interceptor(Record("last", log.clone()));
+
+        let response = client
+            .send("users", http::Request::new(Default::default()), &token())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["first request GET users", r#"first response "unavailable""#,]
+        );
+    }
+
+    #[tokio::test]
+    async fn attempts() {
+        let attempts = Arc::new(Attempts::default());
+        let client = crate::HelixClient::with_client(Echo::default())
+            .with_retry_policy(super::super::retry::RetryPolicy::new(|_| async {}))
+            .with_interceptor(attempts.clone())
+            .with_interceptor(Unavailable);
+
+        let response = client
+            .send("users", http::Request::new(Default::default()), &token())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+        assert_eq!(*attempts.0.lock().unwrap(), [0, 1, 2]);
+    }
+}
@@ -0,0 +1,229 @@
+//! Authorization with extension JWTs
+//!
+//! Some [extension endpoints](crate::helix::extensions) are not authorized with an OAuth token, but with a JWT signed with the secret of the extension.
+//! These requests are sent with [`HelixClient::req_get_with_jwt`], [`HelixClient::req_post_with_jwt`] or [`HelixClient::req_put_with_jwt`],
+//! which accept any [`JwtToken`].
+//!
+//! With feature `jwt`, [`ExtensionJwt`](twitch_oauth2::ExtensionJwt) mints the tokens from the secret of the extension.
+//! Otherwise, use a [`SignedJwt`] that was signed elsewhere.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use twitch_api::helix::{self, extensions, jwt::SignedJwt};
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! let client: helix::HelixClient<reqwest::Client> = helix::HelixClient::new();
+//! let jwt = SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9...");
+//! let request = extensions::GetExtensionsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//! let extensions: Vec<extensions::Extension> = client.req_get_with_jwt(request, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Cow;
+
+use twitch_oauth2::{ClientId, ClientIdRef};
+
+use super::*;
+
+/// A source of JWTs signed with the secret of an extension
+pub trait JwtToken {
+    /// Client ID of the extension
+    fn client_id(&self) -> &ClientIdRef;
+
+    /// The signed JWT to send as bearer token
+    fn jwt(&self) -> Cow<'_, str>;
+}
+
+impl<J: JwtToken + ?Sized> JwtToken for &J {
+    fn client_id(&self) -> &ClientIdRef { (**self).client_id() }
+
+    fn jwt(&self) -> Cow<'_, str> { (**self).jwt() }
+}
+
+/// An already signed JWT for an extension
+#[derive(Clone)]
+pub struct SignedJwt {
+    client_id: ClientId,
+    jwt: String,
+}
+
+impl SignedJwt {
+    /// Use a signed JWT for the extension with the given client ID
+    pub fn new(client_id: impl Into<ClientId>, jwt: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            jwt: jwt.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SignedJwt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignedJwt")
+            .field("client_id", &self.client_id)
+            .field("jwt", &"[redacted]")
+            .finish()
+    }
+}
+
+impl JwtToken for SignedJwt {
+    fn client_id(&self) -> &ClientIdRef { &self.client_id }
+
+    fn jwt(&self) -> Cow<'_, str> { Cow::Borrowed(&self.jwt) }
+}
+
+#[cfg(feature = "jwt")]
+#[cfg_attr(nightly, doc(cfg(feature = "jwt")))]
+impl JwtToken for twitch_oauth2::ExtensionJwt {
+    fn client_id(&self) -> &ClientIdRef { twitch_oauth2::ExtensionJwt::client_id(self) }
+
+    fn jwt(&self) -> Cow<'_, str> { Cow::Owned(self.token()) }
+}
+
+impl<'a, C: crate::HttpClient + 'a> HelixClient<'a, C> {
+    /// Request on a valid [`RequestGet`] endpoint, authorized with an extension JWT
+    pub async fn req_get_with_jwt<R, D, J>(
+        &'a self,
+        request: R,
+        jwt: &J,
+    ) -> Result<Response<R, D>, ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        R: Request<Response = D> + Request + RequestGet,
+        D: serde::de::DeserializeOwned + PartialEq,
+        J: JwtToken + ?Sized,
+    {
+        let req = request.create_request(&jwt.jwt(), jwt.client_id().as_str())?;
+        let uri = req.uri().clone();
+        let response = self
+            .send_with_jwt(R::PATH, req, jwt)
+            .await?
+            .into_response_vec();
+        <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
+    }
+
+    /// Request on a valid [`RequestPost`] endpoint, authorized with an extension JWT
+    pub async fn req_post_with_jwt<R, B, D, J>(
+        &'a self,
+        request: R,
+        body: B,
+        jwt: &J,
+    ) -> Result<Response<R, D>, ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        R: Request<Response = D> + Request + RequestPost<Body = B>,
+        B: HelixRequestBody,
+        D: serde::de::DeserializeOwned + PartialEq,
+        J: JwtToken + ?Sized,
+    {
+        let req = request.create_request(body, &jwt.jwt(), jwt.client_id().as_str())?;
+        let uri = req.uri().clone();
+        let response = self
+            .send_with_jwt(R::PATH, req, jwt)
+            .await?
+            .into_response_vec();
+        <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
+    }
+
+    /// Request on a valid [`RequestPut`] endpoint, authorized with an extension JWT
+    pub async fn req_put_with_jwt<R, B, D, J>(
+        &'a self,
+        request: R,
+        body: B,
+        jwt: &J,
+    ) -> Result<Response<R, D>, ClientRequestError<<C as crate::HttpClient>::Error>>
+    where
+        R: Request<Response = D> + Request + RequestPut<Body = B>,
+        B: HelixRequestBody,
+        D: serde::de::DeserializeOwned + PartialEq,
+        J: JwtToken + ?Sized,
+    {
+        let req = request.create_request(body, &jwt.jwt(), jwt.client_id().as_str())?;
+        let uri = req.uri().clone();
+        let response = self
+            .send_with_jwt(R::PATH, req, jwt)
+            .await?
+            .into_response_vec();
+        <R>::parse_response(Some(request), &uri, response).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn authorizes_with_jwt() {
+        /// Checks the authorization of requests
+        struct Helix;
+
+        impl crate::HttpClient for Helix {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                req: crate::client::Request,
+            ) -> crate::client::BoxedFuture<'_, Result<crate::client::Response, Self::Error>>
+            {
+                assert_eq!(req.headers()["Client-ID"], "extension");
+                assert_eq!(req.headers()[http::header::AUTHORIZATION], "Bearer jwt");
+                Box::pin(async {
+                    Ok(http::Response::builder()
+                        .status(204)
+                        .body(Default::default())
+                        .unwrap())
+                })
+            }
+        }
+
+        let client = HelixClient::with_client(Helix);
+        let jwt = SignedJwt::new("extension", "jwt");
+        assert!(!format!("{:?}", jwt).contains("\"jwt\""));
+        let req = extensions::SendExtensionChatMessageRequest::new("1234");
+        let body = extensions::SendExtensionChatMessageBody::new("Hello!", "extension", "0.0.1");
+        let response = client.req_post_with_jwt(req, body, &jwt).await.unwrap();
+        assert_eq!(
+            response.data,
+            extensions::SendExtensionChatMessageResponse::Success
+        );
+    }
+
+    #[cfg(feature = "jwt")]
+    #[tokio::test]
+    async fn authorizes_with_extension_jwt() {
+        /// Checks that requests carry a minted token
+        struct Helix(twitch_oauth2::ExtensionJwt);
+
+        impl crate::HttpClient for Helix {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                req: crate::client::Request,
+            ) -> crate::client::BoxedFuture<'_, Result<crate::client::Response, Self::Error>>
+            {
+                let token = req.headers()[http::header::AUTHORIZATION]
+                    .to_str()
+                    .unwrap()
+                    .strip_prefix("Bearer ")
+                    .unwrap();
+                let claims = self.0.verify(token).unwrap();
+                assert_eq!(claims.role, twitch_oauth2::tokens::ExtensionRole::External);
+                Box::pin(async {
+                    Ok(http::Response::builder()
+                        .status(204)
+                        .body(Default::default())
+                        .unwrap())
+                })
+            }
+        }
+
+        let jwt = twitch_oauth2::ExtensionJwt::new("extension", "1234", "c2VjcmV0c2VjcmV0c2VjcmV0")
+            .unwrap();
+        let client = HelixClient::with_client(Helix(jwt.clone()));
+        let req = extensions::SendExtensionChatMessageRequest::new("1234");
+        let body = extensions::SendExtensionChatMessageBody::new("Hello!", "extension", "0.0.1");
+        client.req_post_with_jwt(req, body, &jwt).await.unwrap();
+    }
+}
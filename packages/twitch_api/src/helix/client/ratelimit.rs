@@ -0,0 +1,372 @@
+//! Rate limiting for [`HelixClient`](super::HelixClient)
+//!
+//! Twitch limits the amount of requests that can be made to Helix, see [Twitch rate limits](https://dev.twitch.tv/docs/api/guide#twitch-rate-limits).
+//! Every response contains the headers `Ratelimit-Limit`, `Ratelimit-Remaining` and `Ratelimit-Reset` which describe the bucket the request was counted against.
+//! Buckets are tracked per client ID for app access tokens and per client ID and user for user access tokens.
+//!
+//! A [`RateLimiter`] keeps track of these buckets, delays requests when a bucket is empty and retries requests that were rejected with `429 Too Many Requests` after the bucket has been reset.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use twitch_api::helix::{ratelimit::RateLimiter, HelixClient};
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//! let client: HelixClient<reqwest::Client> =
+//!     HelixClient::new().with_ratelimiter(RateLimiter::new(tokio::time::sleep));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use twitch_oauth2::ClientId;
+
+use crate::client::BoxedFuture;
+use crate::types::UserId;
+
+/// A function that sleeps for the given duration.
+pub(crate) type SleepFn = Arc<dyn Fn(Duration) -> BoxedFuture<'static, ()> + Send + Sync>;
+
+/// Make a [`SleepFn`] out of any async sleep function, like [`tokio::time::sleep`](https://docs.rs/tokio/*/tokio/time/fn.sleep.html)
+pub(crate) fn sleep_fn<F, Fut>(sleep: F) -> SleepFn
+where
+    F: Fn(Duration) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = ()> + Send + 'static, {
+    Arc::new(move |duration| Box::pin(sleep(duration)))
+}
+
+/// The state of a rate limit bucket, as reported by Twitch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RateLimit {
+    /// The rate at which points are added to the bucket.
+    pub limit: u32,
+    /// The number of points remaining in the bucket.
+    pub remaining: u32,
+    /// When the bucket is reset to full.
+    pub reset: SystemTime,
+}
+
+impl RateLimit {
+    /// Header for the rate at which points are added to the bucket.
+    pub const LIMIT_HEADER: &'static str = "ratelimit-limit";
+    /// Header for the number of points remaining in the bucket.
+    pub const REMAINING_HEADER: &'static str = "ratelimit-remaining";
+    /// Header for the unix epoch timestamp of when the bucket is reset to full.
+    pub const RESET_HEADER: &'static str = "ratelimit-reset";
+
+    /// Read the rate limit from the headers of a Helix response.
+    ///
+    /// Returns `None` if any of the headers are missing or malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twitch_api::helix::ratelimit::RateLimit;
+    ///
+    /// let response = http::Response::builder()
+    ///     .header("Ratelimit-Limit", "800")
+    ///     .header("Ratelimit-Remaining", "799")
+    ///     .header("Ratelimit-Reset", "1700000000")
+    ///     .body(())?;
+    /// let ratelimit = RateLimit::from_headers(response.headers()).unwrap();
+    /// assert_eq!(ratelimit.remaining, 799);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_headers(headers: &http::HeaderMap) -> Option<Self> {
+        fn header<T: std::str::FromStr>(headers: &http::HeaderMap, name: &str) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+        let reset: u64 = header(headers, Self::RESET_HEADER)?;
+        Some(Self {
+            limit: header(headers, Self::LIMIT_HEADER)?,
+            remaining: header(headers, Self::REMAINING_HEADER)?,
+            reset: SystemTime::UNIX_EPOCH + Duration::from_secs(reset),
+        })
+    }
+
+    /// Time left until the bucket is reset, zero if the reset has already passed.
+    pub fn reset_in(&self) -> Duration {
+        self.reset
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+}
+
+/// Identifies a rate limit bucket
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct BucketKey {
+    client_id: ClientId,
+    user_id: Option<UserId>,
+}
+
+impl BucketKey {
+    pub(crate) fn from_token<T: twitch_oauth2::TwitchToken + ?Sized>(token: &T) -> Self {
+        Self {
+            client_id: token.client_id().to_owned(),
+            user_id: token.user_id().map(ToOwned::to_owned),
+        }
+    }
+
+    /// Bucket for requests that are not made on behalf of a user, like requests authorized with an extension JWT
+    pub(crate) fn from_client_id(client_id: &twitch_oauth2::ClientIdRef) -> Self {
+        Self {
+            client_id: client_id.to_owned(),
+            user_id: None,
+        }
+    }
+}
+
+/// Rate limiter for Helix requests, see the [module documentation](self).
+///
+/// The limiter is cheap to clone, all clones share the same buckets.
+#[derive(Clone)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<HashMap<BucketKey, RateLimit>>>,
+    sleep: SleepFn,
+    max_retries: u32,
+    /// Fallback when a `429` response does not tell us when the bucket resets
+    retry_after: Duration,
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("buckets", &self.buckets)
+            .field("max_retries", &self.max_retries)
+            .field("retry_after", &self.retry_after)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter.
+    ///
+    /// The crate is runtime agnostic, so `sleep` is used whenever a request needs to wait on a bucket, for example [`tokio::time::sleep`](https://docs.rs/tokio/*/tokio/time/fn.sleep.html).
+    pub fn new<F, Fut>(sleep: F) -> Self
+    where
+        F: Fn(Duration) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static, {
+        Self {
+            buckets: Default::default(),
+            sleep: sleep_fn(sleep),
+            max_retries: 3,
+            retry_after: Duration::from_secs(1),
+        }
+    }
+
+    /// Set how many times a request rejected with `429 Too Many Requests` is retried. Defaults to `3`.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set how long to wait before retrying a `429 Too Many Requests` response that didn't include a `Ratelimit-Reset` header. Defaults to one second.
+    pub fn retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Get the last known state of the bucket used by the given token.
+    pub fn get<T: twitch_oauth2::TwitchToken + ?Sized>(&self, token: &T) -> Option<RateLimit> {
+        self.buckets
+            .lock()
+            .expect("rate limit buckets should not be poisoned")
+            .get(&BucketKey::from_token(token))
+            .copied()
+    }
+
+    /// Wait until the bucket has room for another request, reserving a point in it.
+    pub(crate) async fn acquire(&self, key: &BucketKey) {
+        loop {
+            let wait = {
+                let mut buckets = self
+                    .buckets
+                    .lock()
+                    .expect("rate limit buckets should not be poisoned");
+                match buckets.get_mut(key) {
+                    Some(bucket) if bucket.remaining > 0 => {
+                        bucket.remaining -= 1;
+                        None
+                    }
+                    Some(bucket) if bucket.reset > SystemTime::now() => Some(bucket.reset_in()),
+                    // the bucket has been reset, or we know nothing about it yet
+                    Some(_) => {
+                        buckets.remove(key);
+                        None
+                    }
+                    None => None,
+                }
+            };
+            match wait {
+                Some(duration) => (self.sleep)(duration).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Update the bucket with the rate limit returned in a response.
+    pub(crate) fn update(&self, key: &BucketKey, headers: &http::HeaderMap) {
+        if let Some(ratelimit) = RateLimit::from_headers(headers) {
+            self.buckets
+                .lock()
+                .expect("rate limit buckets should not be poisoned")
+                .insert(key.clone(), ratelimit);
+        }
+    }
+
+    /// If the response was rate limited and there are retries left, wait until the bucket is reset and return `true`.
+    pub(crate) async fn wait_for_retry(
+        &self,
+        response: &crate::client::Response,
+        attempt: u32,
+    ) -> bool {
+        if response.status() != http::StatusCode::TOO_MANY_REQUESTS || attempt >= self.max_retries {
+            return false;
+        }
+        let wait = RateLimit::from_headers(response.headers())
+            .map(|r| r.reset_in())
+            .filter(|d| !d.is_zero())
+            .unwrap_or(self.retry_after);
+        (self.sleep)(wait).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(remaining: u32, reset: SystemTime) -> http::HeaderMap {
+        let reset = reset
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let response = http::Response::builder()
+            .header("Ratelimit-Limit", "800")
+            .header("Ratelimit-Remaining", remaining.to_string())
+            .header("Ratelimit-Reset", reset.to_string())
+            .body(())
+            .unwrap();
+        response.headers().clone()
+    }
+
+    fn key() -> BucketKey {
+        BucketKey {
+            client_id: "clientid".into(),
+            user_id: Some("1234".into()),
+        }
+    }
+
+    #[test]
+    fn parse_headers() {
+        let reset = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let ratelimit = RateLimit::from_headers(&headers(12, reset)).unwrap();
+        assert_eq!(ratelimit.limit, 800);
+        assert_eq!(ratelimit.remaining, 12);
+        assert_eq!(ratelimit.reset, reset);
+        assert!(RateLimit::from_headers(&http::HeaderMap::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn waits_on_empty_bucket() {
+        let slept = Arc::new(Mutex::new(vec![]));
+        let buckets: Arc<Mutex<HashMap<BucketKey, RateLimit>>> = Default::default();
+        let limiter = RateLimiter {
+            buckets: buckets.clone(),
+            ..RateLimiter::new({
+                let slept = slept.clone();
+                move |d| {
+                    slept.lock().unwrap().push(d);
+                    // time passes, the bucket resets while we sleep
+                    for bucket in buckets.lock().unwrap().values_mut() {
+                        bucket.reset = SystemTime::UNIX_EPOCH;
+                    }
+                    async {}
+                }
+            })
+        };
+        let key = key();
+        limiter.update(
+            &key,
+            &headers(1, SystemTime::now() + Duration::from_secs(30)),
+        );
+        // one point left, no waiting
+        limiter.acquire(&key).await;
+        assert!(slept.lock().unwrap().is_empty());
+        // bucket is now empty, so we wait until the reset and then find the bucket reset
+        limiter.acquire(&key).await;
+        let slept = slept.lock().unwrap();
+        assert_eq!(slept.len(), 1);
+        assert!(slept[0] > Duration::from_secs(28) && slept[0] <= Duration::from_secs(30));
+        assert!(limiter.buckets.lock().unwrap().get(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn retries_too_many_requests() {
+        let limiter = RateLimiter::new(|_| async {}).max_retries(1);
+        let response = http::Response::builder()
+            .status(429)
+            .body(Default::default())
+            .unwrap();
+        assert!(limiter.wait_for_retry(&response, 0).await);
+        assert!(!limiter.wait_for_retry(&response, 1).await);
+        let response = http::Response::builder()
+            .status(200)
+            .body(Default::default())
+            .unwrap();
+        assert!(!limiter.wait_for_retry(&response, 0).await);
+    }
+
+    #[tokio::test]
+    async fn helix_client_retries() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct TooManyOnce(AtomicUsize);
+
+        impl crate::HttpClient for TooManyOnce {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                _: crate::client::Request,
+            ) -> BoxedFuture<'_, Result<crate::client::Response, Self::Error>> {
+                let status = match self.0.fetch_add(1, Ordering::SeqCst) {
+                    0 => 429,
+                    _ => 200,
+                };
+                Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(status)
+                        .header("Ratelimit-Limit", "800")
+                        .header("Ratelimit-Remaining", "0")
+                        .header("Ratelimit-Reset", "0")
+                        .body(Default::default())
+                        .unwrap())
+                })
+            }
+        }
+
+        let token = twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        );
+        let client = crate::HelixClient::with_client(TooManyOnce::default())
+            .with_ratelimiter(RateLimiter::new(|_| async {}));
+        let req = http::Request::new(Default::default());
+        let response = client.send("users", req, &token).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            client.ratelimiter().unwrap().get(&token).unwrap().limit,
+            800
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Retrying of failed requests for [`HelixClient`](super::HelixClient)
+//!
+//! Helix occasionally responds with `502 Bad Gateway` or `503 Service Unavailable`, and connections can fail spuriously.
+//! A [`RetryPolicy`] retries these requests with exponential backoff and jitter, so that call sites don't need to.
+//!
+//! Requests that are not idempotent, like [`POST`](super::RequestPost) and [`PATCH`](super::RequestPatch), are only retried if [`RetryPolicy::retry_non_idempotent`] is set.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use twitch_api::helix::{retry::RetryPolicy, HelixClient};
+//! # pub mod reqwest {pub type Client = twitch_api::client::DummyHttpClient;}
+//! let client: HelixClient<reqwest::Client> = HelixClient::new().with_retry_policy(
+//!     RetryPolicy::new(tokio::time::sleep)
+//!         .max_attempts(5)
+//!         .base_delay(std::time::Duration::from_millis(250)),
+//! );
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::ratelimit::{sleep_fn, SleepFn};
+
+/// Decides if an error returned by the [`HttpClient`](crate::HttpClient) should be retried
+type ErrorPredicate = Arc<dyn Fn(&(dyn std::error::Error + 'static)) -> bool + Send + Sync>;
+
+/// Retry policy for Helix requests, see the [module documentation](self).
+#[derive(Clone)]
+pub struct RetryPolicy {
+    sleep: SleepFn,
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    statuses: Vec<http::StatusCode>,
+    retry_error: ErrorPredicate,
+    retry_non_idempotent: bool,
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("statuses", &self.statuses)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy.
+    ///
+    /// By default a request is attempted at most 3 times, responses with status `500`, `502`, `503` and `504` are retried, as well as all errors returned by the [`HttpClient`](crate::HttpClient).
+    ///
+    /// `sleep` is used to wait between attempts, for example [`tokio::time::sleep`](https://docs.rs/tokio/*/tokio/time/fn.sleep.html).
+    pub fn new<F, Fut>(sleep: F) -> Self
+    where
+        F: Fn(Duration) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static, {
+        Self {
+            sleep: sleep_fn(sleep),
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            statuses: vec![
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                http::StatusCode::BAD_GATEWAY,
+                http::StatusCode::SERVICE_UNAVAILABLE,
+                http::StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_error: Arc::new(|_| true),
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// Set the maximum amount of attempts for a request, including the first one. Defaults to `3`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry, the delay is doubled for every following retry. Defaults to 100 milliseconds.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the maximum delay between two attempts. Defaults to 10 seconds.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set if the delay should be randomized between zero and the computed backoff. Defaults to `true`.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set which response statuses are retried.
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = http::StatusCode>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Set which errors returned by the [`HttpClient`](crate::HttpClient) are retried. Defaults to all errors.
+    ///
+    /// The error can be downcast to the error of the client in use.
+    ///
+    /// ```rust
+    /// use twitch_api::helix::retry::RetryPolicy;
+    /// # pub mod reqwest {pub type Error = std::io::Error;}
+    ///
+    /// let policy = RetryPolicy::new(tokio::time::sleep).retry_error(|e| {
+    ///     e.downcast_ref::<reqwest::Error>()
+    ///         .map_or(false, |e| e.kind() == std::io::ErrorKind::ConnectionReset)
+    /// });
+    /// ```
+    pub fn retry_error(
+        mut self,
+        retry_error: impl Fn(&(dyn std::error::Error + 'static)) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_error = Arc::new(retry_error);
+        self
+    }
+
+    /// Set if requests that are not idempotent, like `POST` and `PATCH`, should be retried. Defaults to `false`.
+    ///
+    /// Retrying these requests can cause an action to happen twice, for example when the response was lost after Twitch handled the request.
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /// Returns `true` if the request can be retried with this policy
+    pub(crate) fn allows(&self, method: &http::Method) -> bool {
+        self.retry_non_idempotent || !matches!(*method, http::Method::POST | http::Method::PATCH)
+    }
+
+    /// Returns `true` if the result of an attempt should be retried
+    pub(crate) fn should_retry<E: std::error::Error + 'static>(
+        &self,
+        result: &Result<crate::client::Response, E>,
+        attempt: u32,
+    ) -> bool {
+        if attempt + 1 >= self.max_attempts {
+            return false;
+        }
+        match result {
+            Ok(response) => self.statuses.contains(&response.status()),
+            Err(e) => (self.retry_error)(e),
+        }
+    }
+
+    /// The delay before the given retry, `attempt` starting at zero for the first retry
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        if self.jitter {
+            backoff.mul_f64(random())
+        } else {
+            backoff
+        }
+    }
+
+    /// Wait before the given retry
+    pub(crate) async fn wait(&self, attempt: u32) { (self.sleep)(self.delay(attempt)).await }
+}
+
+/// A random number in `0.0..1.0`, good enough for jitter
+fn random() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let hash = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reset() -> Result<crate::client::Response, std::io::Error> {
+        Err(std::io::ErrorKind::ConnectionReset.into())
+    }
+
+    fn response(status: u16) -> Result<crate::client::Response, std::io::Error> {
+        Ok(http::Response::builder()
+            .status(status)
+            .body(Default::default())
+            .unwrap())
+    }
+
+    #[test]
+    fn retryable() {
+        let policy = RetryPolicy::new(|_| async {});
+        assert!(policy.should_retry(&response(503), 0));
+        assert!(policy.should_retry(&response(502), 1));
+        assert!(!policy.should_retry(&response(502), 2));
+        assert!(!policy.should_retry(&response(400), 0));
+        assert!(policy.should_retry(&reset(), 0));
+        assert!(policy.allows(&http::Method::GET));
+        assert!(!policy.allows(&http::Method::POST));
+        assert!(policy
+            .retry_non_idempotent(true)
+            .allows(&http::Method::POST));
+
+        let policy = RetryPolicy::new(|_| async {})
+            .statuses([http::StatusCode::BAD_REQUEST])
+            .retry_error(|_| false);
+        assert!(policy.should_retry(&response(400), 0));
+        assert!(!policy.should_retry(&response(503), 0));
+        assert!(!policy.should_retry(&reset(), 0));
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::new(|_| async {})
+            .jitter(false)
+            .max_delay(Duration::from_millis(500));
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+        assert_eq!(policy.delay(3), Duration::from_millis(500));
+        assert_eq!(policy.delay(40), Duration::from_millis(500));
+        let policy = policy.jitter(true);
+        for attempt in 0..5 {
+            assert!(policy.delay(attempt) <= Duration::from_millis(500));
+        }
+    }
+
+    #[tokio::test]
+    async fn helix_client_retries() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Flaky(AtomicUsize);
+
+        impl crate::HttpClient for Flaky {
+            type Error = std::io::Error;
+
+            fn req(
+                &self,
+                _: crate::client::Request,
+            ) -> crate::client::BoxedFuture<'_, Result<crate::client::Response, Self::Error>>
+            {
+                let attempt = self.0.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async move {
+                    match attempt {
+                        0 => reset(),
+                        1 => response(503),
+                        _ => response(200),
+                    }
+                })
+            }
+        }
+
+        let token = twitch_oauth2::UserToken::from_existing_unchecked(
+            twitch_oauth2::AccessToken::new("token".to_string()),
+            None,
+            twitch_oauth2::ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        );
+        let client = crate::HelixClient::with_client(Flaky::default())
+            .with_retry_policy(RetryPolicy::new(|_| async {}));
+
+        let response = client
+            .send("users", http::Request::new(Default::default()), &token)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 3);
+
+        // POST is not retried by default
+        let mut req = http::Request::new(Default::default());
+        *req.method_mut() = http::Method::POST;
+        client.get_client().0.store(0, Ordering::SeqCst);
+        assert!(client.send("users", req, &token).await.is_err());
+        assert_eq!(client.get_client().0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,69 @@
+//! Helix endpoints regarding analytics
+//!
+//! The endpoints return URLs to CSV reports, use [`HelixClient::get_analytics_report`](crate::helix::HelixClient::get_analytics_report) to download and parse them.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use twitch_api::helix::{HelixClient, analytics::GetGameAnalyticsRequest};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! let client = HelixClient::new();
+//! # let _: &HelixClient<twitch_api::DummyHttpClient> = &client;
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let req = GetGameAnalyticsRequest::game_id("493057");
+//!
+//! println!("{:?}", &client.req_get(req, &token).await?.data);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Implemented endpoints
+//!
+//! <!-- generate with "cargo xtask overview" (with a nightly toolchain) -->
+//! <!-- BEGIN-OVERVIEW -->
+//! <details open><summary style="cursor: pointer">Analytics 🟢 2/2</summary>
+//!
+//! | Endpoint | Helper | Module |
+//! |---|---|---|
+//! | [Get Extension Analytics](https://dev.twitch.tv/docs/api/reference#get-extension-analytics) | - | [`get_extension_analytics`] |
+//! | [Get Game Analytics](https://dev.twitch.tv/docs/api/reference#get-game-analytics) | - | [`get_game_analytics`] |
+//!
+//! </details>
+//!
+//! <!-- END-OVERVIEW -->
+use crate::{
+    helix::{self, Request},
+    types,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+pub mod get_extension_analytics;
+pub mod get_game_analytics;
+
+#[doc(inline)]
+pub use get_extension_analytics::{ExtensionAnalytics, GetExtensionAnalyticsRequest};
+#[doc(inline)]
+pub use get_game_analytics::{GameAnalytics, GetGameAnalyticsRequest};
+
+/// Type of an analytics report
+#[derive(PartialEq, Eq, Deserialize, Serialize, Copy, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum AnalyticsType {
+    /// Overview report, version 2
+    OverviewV2,
+}
+
+/// Reporting window of an analytics report
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct DateRange {
+    /// The reporting window’s start date.
+    pub started_at: types::Timestamp,
+    /// The reporting window’s end date.
+    pub ended_at: types::Timestamp,
+}
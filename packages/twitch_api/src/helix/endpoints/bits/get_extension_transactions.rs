@@ -0,0 +1,175 @@
+//! Gets an extension’s list of transactions.
+//! [`get-extension-transactions`](https://dev.twitch.tv/docs/api/reference#get-extension-transactions)
+//!
+//! A transaction records the exchange of a currency (for example, Bits) for a digital product.
+//! The client ID in the app access token must be the extension’s client ID.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetExtensionTransactionsRequest]
+//!
+//! To use this endpoint, construct a [`GetExtensionTransactionsRequest`] with the [`GetExtensionTransactionsRequest::extension_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::bits::get_extension_transactions;
+//! let request = get_extension_transactions::GetExtensionTransactionsRequest::extension_id(
+//!     "1234",
+//! );
+//! ```
+//!
+//! ## Response: [BitsTransaction](types::BitsTransaction)
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::{helix::{self, bits::get_extension_transactions}, types};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_extension_transactions::GetExtensionTransactionsRequest::extension_id("1234");
+//! let response: Vec<types::BitsTransaction> = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetExtensionTransactionsRequest::parse_response(None, &request.get_uri(), response)`](GetExtensionTransactionsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Extension Transactions](super::get_extension_transactions)
+///
+/// [`get-extension-transactions`](https://dev.twitch.tv/docs/api/reference#get-extension-transactions)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetExtensionTransactionsRequest<'a> {
+    /// The ID of the extension whose list of transactions you want to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// A transaction ID used to filter the list of transactions. You may specify a maximum of 100 IDs.
+    #[cfg_attr(
+        feature = "typed-builder",
+        builder(default_code = "types::Collection::default()", setter(into))
+    )]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[cfg_attr(not(feature = "deser_borrow"), serde(bound(deserialize = "'de: 'a")))]
+    pub id: types::Collection<'a, types::ExtensionTransactionId>,
+    /// The cursor used to get the next page of results.
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub after: Option<Cow<'a, helix::CursorRef>>,
+    /// The maximum number of items to return per page in the response. The minimum page size is 1 item per page and the maximum is 100 items per page. The default is 20.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub first: Option<usize>,
+}
+
+impl<'a> GetExtensionTransactionsRequest<'a> {
+    /// Get the transactions of the given extension
+    pub fn extension_id(extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+            id: types::Collection::default(),
+            after: None,
+            first: None,
+        }
+    }
+
+    /// Only get the transactions with the given IDs
+    pub fn ids(
+        mut self,
+        ids: impl Into<types::Collection<'a, types::ExtensionTransactionId>>,
+    ) -> Self {
+        self.id = ids.into();
+        self
+    }
+
+    /// Set amount of results returned per page.
+    pub const fn first(mut self, first: usize) -> Self {
+        self.first = Some(first);
+        self
+    }
+}
+
+impl Request for GetExtensionTransactionsRequest<'_> {
+    type Response = Vec<types::BitsTransaction>;
+
+    const PATH: &'static str = "extensions/transactions";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetExtensionTransactionsRequest<'_> {}
+
+impl helix::Paginated for GetExtensionTransactionsRequest<'_> {
+    fn set_pagination(&mut self, cursor: Option<helix::Cursor>) {
+        self.after = cursor.map(|c| c.into_cow())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let ids: &[&types::ExtensionTransactionIdRef] =
+        &["74c52265-e214-48a6-91b9-23b6014e8041".into()];
+    let req = GetExtensionTransactionsRequest::extension_id("1234").ids(ids);
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "id": "74c52265-e214-48a6-91b9-23b6014e8041",
+                "timestamp": "2019-01-28T04:15:17.065Z",
+                "broadcaster_id": "439964613",
+                "broadcaster_login": "chikuseuma",
+                "broadcaster_name": "chikuseuma",
+                "user_id": "424596340",
+                "user_login": "quotrok",
+                "user_name": "quotrok",
+                "product_type": "BITS_IN_EXTENSION",
+                "product_data": {
+                    "domain": "twitch.ext.uo6dggojyb8d6soh92zknwmi5ej1q2",
+                    "sku": "testSku100",
+                    "cost": {
+                        "amount": 100,
+                        "type": "bits"
+                    },
+                    "inDevelopment": false,
+                    "displayName": "Test Product 100",
+                    "expiration": "",
+                    "broadcast": false
+                }
+            }
+        ],
+        "pagination": {
+            "cursor": "cursorString"
+        }
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/transactions?extension_id=1234&id=74c52265-e214-48a6-91b9-23b6014e8041"
+    );
+
+    let response =
+        GetExtensionTransactionsRequest::parse_response(Some(req), &uri, http_response).unwrap();
+    let transaction = &response.data[0];
+    assert_eq!(transaction.broadcaster_user_id.as_str(), "439964613");
+    assert_eq!(transaction.product.name, "Test Product 100");
+    assert_eq!(transaction.product.bits, 100);
+    assert_eq!(transaction.product.expiration, None);
+    crate::tests::roundtrip(&response.data);
+}
@@ -21,13 +21,13 @@
 //!
 //! <!-- generate with "cargo xtask overview" (with a nightly toolchain) -->
 //! <!-- BEGIN-OVERVIEW -->
-//! <details open><summary style="cursor: pointer">Bits 🟡 2/3</summary>
+//! <details open><summary style="cursor: pointer">Bits 🟢 3/3</summary>
 //!
 //! | Endpoint | Helper | Module |
 //! |---|---|---|
 //! | [Get Bits Leaderboard](https://dev.twitch.tv/docs/api/reference#get-bits-leaderboard) | - | [`get_bits_leaderboard`] |
 //! | [Get Cheermotes](https://dev.twitch.tv/docs/api/reference#get-cheermotes) | - | [`get_cheermotes`] |
-//! | [Get Extension Transactions](https://dev.twitch.tv/docs/api/reference#get-extension-transactions) | - | - |
+//! | [Get Extension Transactions](https://dev.twitch.tv/docs/api/reference#get-extension-transactions) | - | [`get_extension_transactions`] |
 //!
 //! </details>
 //!
@@ -42,8 +42,11 @@ use std::borrow::Cow;
 
 pub mod get_bits_leaderboard;
 pub mod get_cheermotes;
+pub mod get_extension_transactions;
 
 #[doc(inline)]
 pub use get_bits_leaderboard::{BitsLeaderboard, GetBitsLeaderboardRequest};
 #[doc(inline)]
 pub use get_cheermotes::{Cheermote, GetCheermotesRequest};
+#[doc(inline)]
+pub use get_extension_transactions::GetExtensionTransactionsRequest;
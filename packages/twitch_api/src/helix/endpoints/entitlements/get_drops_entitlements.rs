@@ -0,0 +1,216 @@
+//! Gets an organization’s list of entitlements that have been granted to a game, a user, or both.
+//! [`get-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#get-drops-entitlements)
+//!
+//! The client ID of the access token must own the game.
+//! With an app access token, entitlements of all users are returned, with a user access token, only the entitlements of that user.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetDropsEntitlementsRequest]
+//!
+//! To use this endpoint, construct a [`GetDropsEntitlementsRequest`] with the [`GetDropsEntitlementsRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::entitlements::{
+//!     get_drops_entitlements, FulfillmentStatus,
+//! };
+//! let request = get_drops_entitlements::GetDropsEntitlementsRequest::new()
+//!     .game_id("33214")
+//!     .fulfillment_status(FulfillmentStatus::Claimed);
+//! ```
+//!
+//! ## Response: [DropsEntitlement]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, entitlements::get_drops_entitlements};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_drops_entitlements::GetDropsEntitlementsRequest::new().user_id("25009227");
+//! let response: Vec<helix::entitlements::DropsEntitlement> = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetDropsEntitlementsRequest::parse_response(None, &request.get_uri(), response)`](GetDropsEntitlementsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Drops Entitlements](super::get_drops_entitlements)
+///
+/// [`get-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#get-drops-entitlements)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetDropsEntitlementsRequest<'a> {
+    /// An ID that identifies the entitlement to get. Multiple IDs can be specified. Limit: 100.
+    #[cfg_attr(
+        feature = "typed-builder",
+        builder(default_code = "types::Collection::default()", setter(into))
+    )]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[cfg_attr(not(feature = "deser_borrow"), serde(bound(deserialize = "'de: 'a")))]
+    pub id: types::Collection<'a, types::EntitlementId>,
+    /// An ID that identifies a user that was granted entitlements.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub user_id: Option<Cow<'a, types::UserIdRef>>,
+    /// An ID that identifies a game that offered entitlements.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub game_id: Option<Cow<'a, types::CategoryIdRef>>,
+    /// The entitlement’s fulfillment status. Used to filter the list to only those with the specified status.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub fulfillment_status: Option<FulfillmentStatus>,
+    /// The cursor used to get the next page of results.
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub after: Option<Cow<'a, helix::CursorRef>>,
+    /// The maximum number of entitlements to return per page in the response. The minimum page size is 1 entitlement per page and the maximum is 1000. The default is 20.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub first: Option<usize>,
+}
+
+impl<'a> GetDropsEntitlementsRequest<'a> {
+    /// Get all entitlements of the organization
+    pub fn new() -> Self {
+        Self {
+            id: types::Collection::default(),
+            user_id: None,
+            game_id: None,
+            fulfillment_status: None,
+            after: None,
+            first: None,
+        }
+    }
+
+    /// Get the entitlements with the given IDs
+    pub fn ids(ids: impl Into<types::Collection<'a, types::EntitlementId>>) -> Self {
+        Self {
+            id: ids.into(),
+            ..Self::new()
+        }
+    }
+
+    /// Only get entitlements granted to this user
+    pub fn user_id(mut self, user_id: impl types::IntoCow<'a, types::UserIdRef> + 'a) -> Self {
+        self.user_id = Some(user_id.into_cow());
+        self
+    }
+
+    /// Only get entitlements offered by this game
+    pub fn game_id(mut self, game_id: impl types::IntoCow<'a, types::CategoryIdRef> + 'a) -> Self {
+        self.game_id = Some(game_id.into_cow());
+        self
+    }
+
+    /// Only get entitlements with this fulfillment status
+    pub const fn fulfillment_status(mut self, fulfillment_status: FulfillmentStatus) -> Self {
+        self.fulfillment_status = Some(fulfillment_status);
+        self
+    }
+
+    /// Set amount of results returned per page.
+    pub const fn first(mut self, first: usize) -> Self {
+        self.first = Some(first);
+        self
+    }
+}
+
+impl Default for GetDropsEntitlementsRequest<'_> {
+    fn default() -> Self { Self::new() }
+}
+
+/// Return Values for [Get Drops Entitlements](super::get_drops_entitlements)
+///
+/// [`get-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#get-drops-entitlements)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct DropsEntitlement {
+    /// An ID that identifies the entitlement.
+    pub id: types::EntitlementId,
+    /// An ID that identifies the benefit (reward).
+    pub benefit_id: types::BenefitId,
+    /// The UTC date and time of when the entitlement was granted.
+    pub timestamp: types::Timestamp,
+    /// An ID that identifies the user who was granted the entitlement.
+    pub user_id: types::UserId,
+    /// An ID that identifies the game the user was playing when the reward was entitled.
+    pub game_id: types::CategoryId,
+    /// The entitlement’s fulfillment status.
+    pub fulfillment_status: FulfillmentStatus,
+    /// The UTC date and time of when the entitlement was last updated.
+    pub last_updated: types::Timestamp,
+}
+
+impl Request for GetDropsEntitlementsRequest<'_> {
+    type Response = Vec<DropsEntitlement>;
+
+    const PATH: &'static str = "entitlements/drops";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetDropsEntitlementsRequest<'_> {}
+
+impl helix::Paginated for GetDropsEntitlementsRequest<'_> {
+    fn set_pagination(&mut self, cursor: Option<helix::Cursor>) {
+        self.after = cursor.map(|c| c.into_cow())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let ids: &[&types::EntitlementIdRef] = &["fb78259e-fb81-4d1b-8333-34a06ffc24c0".into()];
+    let req = GetDropsEntitlementsRequest::ids(ids)
+        .user_id("25009227")
+        .fulfillment_status(FulfillmentStatus::Claimed);
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "id": "fb78259e-fb81-4d1b-8333-34a06ffc24c0",
+                "benefit_id": "74c52265-e214-48a6-91b9-23b6014e8041",
+                "timestamp": "2019-01-28T04:17:53.325Z",
+                "user_id": "25009227",
+                "game_id": "33214",
+                "fulfillment_status": "CLAIMED",
+                "last_updated": "2019-01-28T04:17:53.325Z"
+            }
+        ],
+        "pagination": {
+            "cursor": "eyJiIjpudWxsLCJhIjp7IkN1cnNvciI6ImV5SnBaQ0k2SW1aaU56Z3lOVGxsTFdaaU9ERXROR1F4WWkwNE16TXpMVE0wWVRBMlptWmpNalJqTUNJc0luUnpJam9pTWpBeE9TMHdNUzB5T0ZRd05Eb3hOem8xTXk0ek1qVmFJbjA9In19"
+        }
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/entitlements/drops?id=fb78259e-fb81-4d1b-8333-34a06ffc24c0&user_id=25009227&fulfillment_status=CLAIMED"
+    );
+
+    let response =
+        GetDropsEntitlementsRequest::parse_response(Some(req), &uri, http_response).unwrap();
+    assert_eq!(
+        response.data[0].fulfillment_status,
+        FulfillmentStatus::Claimed
+    );
+    assert!(response.pagination.is_some());
+}
@@ -0,0 +1,65 @@
+//! Helix endpoints regarding Drops entitlements
+//!
+//! See also [EventSub Drops](crate::eventsub::drop)
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use twitch_api::helix::{HelixClient, entitlements::{GetDropsEntitlementsRequest, FulfillmentStatus}};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! let client = HelixClient::new();
+//! # let _: &HelixClient<twitch_api::DummyHttpClient> = &client;
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let req = GetDropsEntitlementsRequest::new()
+//!     .user_id("25009227")
+//!     .fulfillment_status(FulfillmentStatus::Claimed);
+//!
+//! println!("{:?}", &client.req_get(req, &token).await?.data);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Implemented endpoints
+//!
+//! <!-- generate with "cargo xtask overview" (with a nightly toolchain) -->
+//! <!-- BEGIN-OVERVIEW -->
+//! <details open><summary style="cursor: pointer">Entitlements 🟢 2/2</summary>
+//!
+//! | Endpoint | Helper | Module |
+//! |---|---|---|
+//! | [Get Drops Entitlements](https://dev.twitch.tv/docs/api/reference#get-drops-entitlements) | - | [`get_drops_entitlements`] |
+//! | [Update Drops Entitlements](https://dev.twitch.tv/docs/api/reference#update-drops-entitlements) | - | [`update_drops_entitlements`] |
+//!
+//! </details>
+//!
+//! <!-- END-OVERVIEW -->
+use crate::{
+    helix::{self, Request},
+    types,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+pub mod get_drops_entitlements;
+pub mod update_drops_entitlements;
+
+#[doc(inline)]
+pub use get_drops_entitlements::{DropsEntitlement, GetDropsEntitlementsRequest};
+#[doc(inline)]
+pub use update_drops_entitlements::{
+    DropsEntitlementUpdate, UpdateDropsEntitlementsBody, UpdateDropsEntitlementsRequest,
+    UpdateStatus,
+};
+
+/// Fulfillment status of a Drops entitlement
+#[derive(PartialEq, Eq, Deserialize, Serialize, Copy, Clone, Debug)]
+#[serde(rename_all = "UPPERCASE")]
+#[non_exhaustive]
+pub enum FulfillmentStatus {
+    /// The user claimed the benefit, but it has not been granted yet
+    Claimed,
+    /// The benefit was granted to the user
+    Fulfilled,
+}
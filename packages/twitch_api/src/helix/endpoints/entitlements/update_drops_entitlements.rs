@@ -0,0 +1,236 @@
+//! Updates the Drop entitlement’s fulfillment status.
+//! [`update-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#update-drops-entitlements)
+//!
+//! The client ID of the access token must own the game.
+//! With an app access token, entitlements of any user can be updated, with a user access token, only the entitlements of that user.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [UpdateDropsEntitlementsRequest]
+//!
+//! To use this endpoint, construct a [`UpdateDropsEntitlementsRequest`] with the [`UpdateDropsEntitlementsRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::entitlements::update_drops_entitlements;
+//! let request =
+//!     update_drops_entitlements::UpdateDropsEntitlementsRequest::new();
+//! ```
+//!
+//! ## Body: [UpdateDropsEntitlementsBody]
+//!
+//! We also need to provide a body to the request containing the entitlements to update.
+//!
+//! ```
+//! # use twitch_api::{helix::entitlements::{update_drops_entitlements, FulfillmentStatus}, types};
+//! let ids: &[&types::EntitlementIdRef] = &["fb78259e-fb81-4d1b-8333-34a06ffc24c0".into()];
+//! let body = update_drops_entitlements::UpdateDropsEntitlementsBody::new(
+//!     ids,
+//!     FulfillmentStatus::Fulfilled,
+//! );
+//! ```
+//!
+//! ## Response: [DropsEntitlementUpdate]
+//!
+//! Send the request to receive the response with [`HelixClient::req_patch()`](helix::HelixClient::req_patch).
+//!
+//! ```rust, no_run
+//! use twitch_api::{helix::{self, entitlements::{update_drops_entitlements, FulfillmentStatus}}, types};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = update_drops_entitlements::UpdateDropsEntitlementsRequest::new();
+//! let ids: &[&types::EntitlementIdRef] = &["fb78259e-fb81-4d1b-8333-34a06ffc24c0".into()];
+//! let body = update_drops_entitlements::UpdateDropsEntitlementsBody::new(ids, FulfillmentStatus::Fulfilled);
+//! let response: Vec<helix::entitlements::DropsEntitlementUpdate> = client.req_patch(request, body, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(body, &token, &client_id)`](helix::RequestPatch::create_request)
+//! and parse the [`http::Response`] with [`UpdateDropsEntitlementsRequest::parse_response(None, &request.get_uri(), response)`](UpdateDropsEntitlementsRequest::parse_response)
+
+use super::*;
+use helix::RequestPatch;
+
+/// Query Parameters for [Update Drops Entitlements](super::update_drops_entitlements)
+///
+/// [`update-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#update-drops-entitlements)
+#[derive(PartialEq, Eq, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct UpdateDropsEntitlementsRequest<'a> {
+    #[serde(skip)]
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+
+impl UpdateDropsEntitlementsRequest<'_> {
+    /// Update the fulfillment status of entitlements
+    pub fn new() -> Self { Self::default() }
+}
+
+/// Body Parameters for [Update Drops Entitlements](super::update_drops_entitlements)
+///
+/// [`update-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#update-drops-entitlements)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[non_exhaustive]
+pub struct UpdateDropsEntitlementsBody<'a> {
+    /// A list of IDs that identify the entitlements to update. You may specify a maximum of 100 IDs.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[cfg_attr(not(feature = "deser_borrow"), serde(bound(deserialize = "'de: 'a")))]
+    pub entitlement_ids: types::Collection<'a, types::EntitlementId>,
+    /// The fulfillment status to set the entitlements to.
+    pub fulfillment_status: FulfillmentStatus,
+}
+
+impl<'a> UpdateDropsEntitlementsBody<'a> {
+    /// Set the fulfillment status of the given entitlements
+    pub fn new(
+        entitlement_ids: impl Into<types::Collection<'a, types::EntitlementId>>,
+        fulfillment_status: FulfillmentStatus,
+    ) -> Self {
+        Self {
+            entitlement_ids: entitlement_ids.into(),
+            fulfillment_status,
+        }
+    }
+}
+
+impl helix::private::SealedSerialize for UpdateDropsEntitlementsBody<'_> {}
+
+/// Return Values for [Update Drops Entitlements](super::update_drops_entitlements)
+///
+/// [`update-drops-entitlements`](https://dev.twitch.tv/docs/api/reference#update-drops-entitlements)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct DropsEntitlementUpdate {
+    /// A string that indicates whether the status of the entitlements in the `ids` field were successfully updated.
+    pub status: UpdateStatus,
+    /// The list of entitlements that the status applies to.
+    pub ids: Vec<types::EntitlementId>,
+}
+
+/// Outcome of updating the [fulfillment status](FulfillmentStatus) of entitlements
+#[derive(PartialEq, Eq, Deserialize, Serialize, Copy, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
+pub enum UpdateStatus {
+    /// The entitlements were updated.
+    Success,
+    /// The entitlements don’t exist or the format of the ID is not valid.
+    InvalidId,
+    /// The entitlements were not found.
+    NotFound,
+    /// The client ID or user are not authorized to update the entitlements.
+    Unauthorized,
+    /// The update failed. These are considered transient errors and the request should be retried later.
+    UpdateFailed,
+}
+
+impl Request for UpdateDropsEntitlementsRequest<'_> {
+    type Response = Vec<DropsEntitlementUpdate>;
+
+    const PATH: &'static str = "entitlements/drops";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl<'a> RequestPatch for UpdateDropsEntitlementsRequest<'a> {
+    type Body = UpdateDropsEntitlementsBody<'a>;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPatchError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::OK => {
+                let resp: helix::InnerResponse<Vec<DropsEntitlementUpdate>> =
+                    helix::parse_json(response, true).map_err(|e| {
+                        helix::HelixRequestPatchError::DeserializeError(
+                            response.to_string(),
+                            e,
+                            uri.clone(),
+                            status,
+                        )
+                    })?;
+                Ok(helix::Response::with_data(resp.data, request))
+            }
+            _ => Err(helix::HelixRequestPatchError::InvalidResponse {
+                reason: "unexpected status code",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = UpdateDropsEntitlementsRequest::new();
+
+    let ids: &[&types::EntitlementIdRef] = &[
+        "fb78259e-fb81-4d1b-8333-34a06ffc24c0".into(),
+        "862750a5-265e-4ab6-9f0a-c64df3d54dd0".into(),
+        "d8879baa-3966-4d10-8856-15fdd62cce02".into(),
+    ];
+    let body = UpdateDropsEntitlementsBody::new(ids, FulfillmentStatus::Fulfilled);
+
+    assert_eq!(
+        std::str::from_utf8(&body.try_to_body().unwrap()).unwrap(),
+        r#"{"entitlement_ids":["fb78259e-fb81-4d1b-8333-34a06ffc24c0","862750a5-265e-4ab6-9f0a-c64df3d54dd0","d8879baa-3966-4d10-8856-15fdd62cce02"],"fulfillment_status":"FULFILLED"}"#
+    );
+
+    dbg!(req.create_request(body, "token", "clientid").unwrap());
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "status": "SUCCESS",
+                "ids": [
+                    "fb78259e-fb81-4d1b-8333-34a06ffc24c0",
+                    "862750a5-265e-4ab6-9f0a-c64df3d54dd0"
+                ]
+            },
+            {
+                "status": "UNAUTHORIZED",
+                "ids": [
+                    "d8879baa-3966-4d10-8856-15fdd62cce02"
+                ]
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/entitlements/drops?"
+    );
+
+    let updates = UpdateDropsEntitlementsRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert_eq!(updates[0].status, UpdateStatus::Success);
+    assert_eq!(updates[1].status, UpdateStatus::Unauthorized);
+    assert_eq!(updates[1].ids.len(), 1);
+}
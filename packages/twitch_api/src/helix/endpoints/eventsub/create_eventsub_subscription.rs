@@ -66,6 +66,8 @@ impl<E: EventSubscription> helix::HelixRequestBody for CreateEventSubSubscriptio
             version: &'static str,
             condition: serde_json::Value,
             transport: &'a Transport,
+            #[serde(skip_serializing_if = "std::ops::Not::not")]
+            is_batching_enabled: bool,
         }
 
         let b = IEventSubRequestBody {
@@ -73,6 +75,7 @@ impl<E: EventSubscription> helix::HelixRequestBody for CreateEventSubSubscriptio
             version: E::VERSION,
             condition: self.subscription.condition()?,
             transport: &self.transport,
+            is_batching_enabled: E::IS_BATCHING_ENABLED,
         };
         serde_json::to_vec(&b).map_err(Into::into).map(Into::into)
     }
@@ -248,3 +251,21 @@ fn test_request() {
         CreateEventSubSubscriptionRequest::parse_response(Some(req), &uri, http_response).unwrap()
     );
 }
+
+#[cfg(test)]
+#[test]
+fn test_batched_body() {
+    use crate::eventsub::{self, drop::DropEntitlementGrantV1};
+    use helix::*;
+
+    let sub = DropEntitlementGrantV1::organization_id("9001").category_id("9002");
+    let transport =
+        eventsub::Transport::webhook("https://this-is-a-callback.com", "s3cre7".to_string());
+
+    let body = CreateEventSubSubscriptionBody::new(sub, transport);
+
+    assert_eq!(
+        std::str::from_utf8(&body.try_to_body().unwrap()).unwrap(),
+        r#"{"type":"drop.entitlement.grant","version":"1","condition":{"category_id":"9002","organization_id":"9001"},"transport":{"method":"webhook","callback":"https://this-is-a-callback.com","secret":"s3cre7"},"is_batching_enabled":true}"#
+    );
+}
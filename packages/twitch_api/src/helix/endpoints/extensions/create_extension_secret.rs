@@ -0,0 +1,147 @@
+//! Creates a shared secret used to sign and verify JWT tokens.
+//! [`create-extension-secret`](https://dev.twitch.tv/docs/api/reference#create-extension-secret)
+//!
+//! Creating a new secret removes the current secrets from service. Use this function only when you are ready to use the new secret it returns.
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [CreateExtensionSecretRequest]
+//!
+//! To use this endpoint, construct a [`CreateExtensionSecretRequest`] with the [`CreateExtensionSecretRequest::extension_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::create_extension_secret;
+//! let request =
+//!     create_extension_secret::CreateExtensionSecretRequest::extension_id(
+//!         "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     )
+//!     .delay(600);
+//! ```
+//!
+//! ## Response: [ExtensionSecrets]
+//!
+//! Send the request to receive the response with [`HelixClient::req_post_with_jwt()`](helix::HelixClient::req_post_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::create_extension_secret};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let request = create_extension_secret::CreateExtensionSecretRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//! let response: helix::extensions::ExtensionSecrets = client.req_post_with_jwt(request, helix::EmptyBody, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(helix::EmptyBody, &jwt, &client_id)`](helix::RequestPost::create_request)
+//! and parse the [`http::Response`] with [`CreateExtensionSecretRequest::parse_response(None, &request.get_uri(), response)`](CreateExtensionSecretRequest::parse_response)
+
+use super::*;
+use helix::RequestPost;
+
+/// Query Parameters for [Create Extension Secret](super::create_extension_secret)
+///
+/// [`create-extension-secret`](https://dev.twitch.tv/docs/api/reference#create-extension-secret)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct CreateExtensionSecretRequest<'a> {
+    /// The ID of the extension to apply the shared secret to.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The amount of time, in seconds, to delay activating the secret. The delay should provide enough time for instances of the extension to gracefully switch over to the new secret. The minimum delay is 300 seconds (5 minutes). The default is 300 seconds.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub delay: Option<u64>,
+}
+
+impl<'a> CreateExtensionSecretRequest<'a> {
+    /// Create a new shared secret for an extension
+    pub fn extension_id(extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+            delay: None,
+        }
+    }
+
+    /// Set the delay in seconds before the new secret is activated
+    pub const fn delay(mut self, delay: u64) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+impl Request for CreateExtensionSecretRequest<'_> {
+    type Response = ExtensionSecrets;
+
+    const PATH: &'static str = "extensions/jwt/secrets";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestPost for CreateExtensionSecretRequest<'_> {
+    type Body = helix::EmptyBody;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPostError>
+    where
+        Self: Sized,
+    {
+        helix::parse_single_return(request, uri, response, status)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req =
+        CreateExtensionSecretRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2").delay(600);
+
+    dbg!(req.create_request(EmptyBody, "jwt", "clientid").unwrap());
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "format_version": 1,
+                "secrets": [
+                    {
+                        "content": "old-secret",
+                        "active_at": "2021-03-29T06:58:40.858343036Z",
+                        "expires_at": "2021-04-22T05:21:54.99261682Z"
+                    },
+                    {
+                        "content": "new-secret",
+                        "active_at": "2021-04-22T04:16:55.069041079Z",
+                        "expires_at": "2121-03-29T04:16:55.069041079Z"
+                    }
+                ]
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/jwt/secrets?extension_id=uo6dggojyb8d6soh92zknwmi5ej1q2&delay=600"
+    );
+
+    let secrets = CreateExtensionSecretRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert_eq!(secrets.secrets.len(), 2);
+}
@@ -0,0 +1,187 @@
+//! Gets the list of Bits products that belongs to the extension.
+//! [`get-extension-bits-products`](https://dev.twitch.tv/docs/api/reference#get-extension-bits-products)
+//!
+//! The client ID in the app access token must be the extension’s client ID.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetExtensionBitsProductsRequest]
+//!
+//! To use this endpoint, construct a [`GetExtensionBitsProductsRequest`] with the [`GetExtensionBitsProductsRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::get_extension_bits_products;
+//! let request =
+//!     get_extension_bits_products::GetExtensionBitsProductsRequest::new()
+//!         .should_include_all(true);
+//! ```
+//!
+//! ## Response: [BitsProduct]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::get_extension_bits_products};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_extension_bits_products::GetExtensionBitsProductsRequest::new();
+//! let response: Vec<helix::extensions::BitsProduct> = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetExtensionBitsProductsRequest::parse_response(None, &request.get_uri(), response)`](GetExtensionBitsProductsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Extension Bits Products](super::get_extension_bits_products)
+///
+/// [`get-extension-bits-products`](https://dev.twitch.tv/docs/api/reference#get-extension-bits-products)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetExtensionBitsProductsRequest<'a> {
+    /// A Boolean value that determines whether to include disabled or expired Bits products in the response. The default is false.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub should_include_all: Option<bool>,
+    #[serde(skip)]
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+
+impl GetExtensionBitsProductsRequest<'_> {
+    /// Get the Bits products of the extension
+    pub fn new() -> Self { Self::default() }
+
+    /// Include disabled or expired Bits products in the response
+    pub const fn should_include_all(mut self, should_include_all: bool) -> Self {
+        self.should_include_all = Some(should_include_all);
+        self
+    }
+}
+
+/// Return Values for [Get Extension Bits Products](super::get_extension_bits_products)
+///
+/// [`get-extension-bits-products`](https://dev.twitch.tv/docs/api/reference#get-extension-bits-products)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct BitsProduct {
+    /// The product’s SKU. The SKU is unique across an extension’s products.
+    pub sku: String,
+    /// The product’s price.
+    pub cost: BitsProductCost,
+    /// A Boolean value that indicates whether the product is in development.
+    pub in_development: bool,
+    /// The product’s name as displayed in the extension.
+    pub display_name: String,
+    /// The date and time when the product expires. Not set if the product does not expire.
+    #[serde(
+        default,
+        deserialize_with = "helix::deserialize_none_from_empty_or_zero_string"
+    )]
+    pub expiration: Option<types::Timestamp>,
+    /// A Boolean value that determines whether Bits product purchase events are broadcast to all instances of an extension on a channel.
+    pub is_broadcast: bool,
+}
+
+/// The price of a [Bits product](BitsProduct)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct BitsProductCost {
+    /// The product’s price.
+    pub amount: i64,
+    /// The type of currency.
+    #[serde(rename = "type")]
+    pub type_: CostType,
+}
+
+impl BitsProductCost {
+    /// Create a new cost in Bits
+    pub const fn bits(amount: i64) -> Self {
+        Self {
+            amount,
+            type_: CostType::Bits,
+        }
+    }
+}
+
+/// The type of currency of a [Bits product](BitsProduct)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum CostType {
+    /// Bits
+    Bits,
+}
+
+impl Request for GetExtensionBitsProductsRequest<'_> {
+    type Response = Vec<BitsProduct>;
+
+    const PATH: &'static str = "bits/extensions";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetExtensionBitsProductsRequest<'_> {}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetExtensionBitsProductsRequest::new().should_include_all(true);
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "sku": "1010",
+                "cost": {
+                    "amount": 990,
+                    "type": "bits"
+                },
+                "in_development": true,
+                "display_name": "Rusty Crate 2",
+                "expiration": "2021-05-18T09:10:13.397Z",
+                "is_broadcast": false
+            },
+            {
+                "sku": "1011",
+                "cost": {
+                    "amount": 100,
+                    "type": "bits"
+                },
+                "in_development": false,
+                "display_name": "Shiny Crate",
+                "expiration": "",
+                "is_broadcast": true
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/bits/extensions?should_include_all=true"
+    );
+
+    let products = GetExtensionBitsProductsRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert_eq!(products[0].cost, BitsProductCost::bits(990));
+    assert!(products[0].expiration.is_some());
+    assert_eq!(products[1].expiration, None);
+}
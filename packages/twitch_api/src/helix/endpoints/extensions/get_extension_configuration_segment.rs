@@ -0,0 +1,164 @@
+//! Gets the specified configuration segment from the specified extension.
+//! [`get-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#get-extension-configuration-segment)
+//!
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetExtensionConfigurationSegmentRequest]
+//!
+//! To use this endpoint, construct a [`GetExtensionConfigurationSegmentRequest`] with the [`GetExtensionConfigurationSegmentRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::{
+//!     get_extension_configuration_segment, ExtensionSegment,
+//! };
+//! let segments: &[ExtensionSegment] = &[ExtensionSegment::Broadcaster];
+//! let request = get_extension_configuration_segment::GetExtensionConfigurationSegmentRequest::new(
+//!     "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     segments,
+//! )
+//! .broadcaster_id("1234");
+//! ```
+//!
+//! ## Response: [ExtensionConfiguration]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get_with_jwt()`](helix::HelixClient::req_get_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::{get_extension_configuration_segment, ExtensionSegment}};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let segments: &[ExtensionSegment] = &[ExtensionSegment::Global];
+//! let request = get_extension_configuration_segment::GetExtensionConfigurationSegmentRequest::new("uo6dggojyb8d6soh92zknwmi5ej1q2", segments);
+//! let response: Vec<helix::extensions::ExtensionConfiguration> = client.req_get_with_jwt(request, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&jwt, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetExtensionConfigurationSegmentRequest::parse_response(None, &request.get_uri(), response)`](GetExtensionConfigurationSegmentRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Extension Configuration Segment](super::get_extension_configuration_segment)
+///
+/// [`get-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#get-extension-configuration-segment)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetExtensionConfigurationSegmentRequest<'a> {
+    /// The ID of the broadcaster that installed the extension. Required if the segment is [broadcaster](ExtensionSegment::Broadcaster) or [developer](ExtensionSegment::Developer).
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Option<Cow<'a, types::UserIdRef>>,
+    /// The ID of the extension that contains the configuration segment you want to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The types of configuration segments to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[serde(rename = "segment")]
+    pub segments: Cow<'a, [ExtensionSegment]>,
+}
+
+impl<'a> GetExtensionConfigurationSegmentRequest<'a> {
+    /// Get the given configuration segments of an extension
+    pub fn new(
+        extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a,
+        segments: impl Into<Cow<'a, [ExtensionSegment]>>,
+    ) -> Self {
+        Self {
+            broadcaster_id: None,
+            extension_id: extension_id.into_cow(),
+            segments: segments.into(),
+        }
+    }
+
+    /// Set the broadcaster to get the configuration of
+    pub fn broadcaster_id(
+        mut self,
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+    ) -> Self {
+        self.broadcaster_id = Some(broadcaster_id.into_cow());
+        self
+    }
+}
+
+/// Return Values for [Get Extension Configuration Segment](super::get_extension_configuration_segment)
+///
+/// [`get-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#get-extension-configuration-segment)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionConfiguration {
+    /// The type of segment.
+    pub segment: ExtensionSegment,
+    /// The ID of the broadcaster that installed the extension. Not set for the [global](ExtensionSegment::Global) segment.
+    #[serde(default)]
+    pub broadcaster_id: Option<types::UserId>,
+    /// The contents of the segment. This string may be a plain-text string or a string-encoded JSON object.
+    pub content: String,
+    /// The version that identifies the segment’s definition.
+    pub version: String,
+}
+
+impl Request for GetExtensionConfigurationSegmentRequest<'_> {
+    type Response = Vec<ExtensionConfiguration>;
+
+    const PATH: &'static str = "extensions/configurations";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetExtensionConfigurationSegmentRequest<'_> {}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let segments: &[ExtensionSegment] = &[ExtensionSegment::Global, ExtensionSegment::Broadcaster];
+    let req =
+        GetExtensionConfigurationSegmentRequest::new("uo6dggojyb8d6soh92zknwmi5ej1q2", segments)
+            .broadcaster_id("141981764");
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "segment": "global",
+                "content": "hello config!",
+                "version": "0.0.1"
+            },
+            {
+                "segment": "broadcaster",
+                "broadcaster_id": "141981764",
+                "content": "{\"color\":\"blue\"}",
+                "version": "0.0.1"
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/configurations?broadcaster_id=141981764&extension_id=uo6dggojyb8d6soh92zknwmi5ej1q2&segment=global&segment=broadcaster"
+    );
+
+    let segments =
+        GetExtensionConfigurationSegmentRequest::parse_response(Some(req), &uri, http_response)
+            .unwrap()
+            .data;
+    assert_eq!(segments[0].broadcaster_id, None);
+    assert_eq!(segments[1].segment, ExtensionSegment::Broadcaster);
+}
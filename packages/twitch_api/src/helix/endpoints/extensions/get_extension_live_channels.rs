@@ -0,0 +1,226 @@
+//! Gets a list of broadcasters that are streaming live and have installed or activated the extension.
+//! [`get-extension-live-channels`](https://dev.twitch.tv/docs/api/reference#get-extension-live-channels)
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetExtensionLiveChannelsRequest]
+//!
+//! To use this endpoint, construct a [`GetExtensionLiveChannelsRequest`] with the [`GetExtensionLiveChannelsRequest::extension_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::get_extension_live_channels;
+//! let request =
+//!     get_extension_live_channels::GetExtensionLiveChannelsRequest::extension_id(
+//!         "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     );
+//! ```
+//!
+//! ## Response: [ExtensionLiveChannel]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::get_extension_live_channels};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_extension_live_channels::GetExtensionLiveChannelsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//! let response: Vec<helix::extensions::ExtensionLiveChannel> = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetExtensionLiveChannelsRequest::parse_response(None, &request.get_uri(), response)`](GetExtensionLiveChannelsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Extension Live Channels](super::get_extension_live_channels)
+///
+/// [`get-extension-live-channels`](https://dev.twitch.tv/docs/api/reference#get-extension-live-channels)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetExtensionLiveChannelsRequest<'a> {
+    /// The ID of the extension to get. Returns the list of broadcasters that are live and that have installed or activated this extension.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The maximum number of items to return per page in the response. The minimum page size is 1 item per page and the maximum is 100 items per page. The default is 20.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub first: Option<usize>,
+    /// The cursor used to get the next page of results.
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub after: Option<Cow<'a, helix::CursorRef>>,
+}
+
+impl<'a> GetExtensionLiveChannelsRequest<'a> {
+    /// Get the live channels that have the extension installed or activated
+    pub fn extension_id(extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+            first: None,
+            after: None,
+        }
+    }
+
+    /// Set amount of results returned per page.
+    pub const fn first(mut self, first: usize) -> Self {
+        self.first = Some(first);
+        self
+    }
+}
+
+impl helix::Paginated for GetExtensionLiveChannelsRequest<'_> {
+    fn set_pagination(&mut self, cursor: Option<helix::Cursor>) {
+        self.after = cursor.map(|c| c.into_cow())
+    }
+}
+
+/// Return Values for [Get Extension Live Channels](super::get_extension_live_channels)
+///
+/// [`get-extension-live-channels`](https://dev.twitch.tv/docs/api/reference#get-extension-live-channels)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionLiveChannel {
+    /// The ID of the broadcaster that is streaming live and has installed or activated the extension.
+    pub broadcaster_id: types::UserId,
+    /// The broadcaster’s display name.
+    pub broadcaster_name: types::DisplayName,
+    /// The name of the category or game being streamed.
+    pub game_name: String,
+    /// The ID of the category or game being streamed.
+    pub game_id: types::CategoryId,
+    /// The title of the broadcaster’s stream.
+    pub title: String,
+}
+
+impl Request for GetExtensionLiveChannelsRequest<'_> {
+    type Response = Vec<ExtensionLiveChannel>;
+
+    const PATH: &'static str = "extensions/live";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetExtensionLiveChannelsRequest<'_> {
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, <Self as Request>::Response>, helix::HelixRequestGetError>
+    where
+        Self: Sized,
+    {
+        // Unlike other endpoints, the cursor is returned as a plain string in `pagination`
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Pagination {
+            Cursor(helix::Cursor),
+            Object {
+                #[serde(default)]
+                cursor: Option<helix::Cursor>,
+            },
+        }
+
+        #[derive(Deserialize)]
+        struct InnerResponse {
+            data: Vec<ExtensionLiveChannel>,
+            #[serde(default)]
+            pagination: Option<Pagination>,
+        }
+
+        let inner: InnerResponse = crate::parse_json(response, true).map_err(|e| {
+            helix::HelixRequestGetError::DeserializeError(
+                response.to_string(),
+                e,
+                uri.clone(),
+                status,
+            )
+        })?;
+        let cursor = match inner.pagination {
+            Some(Pagination::Cursor(cursor)) => Some(cursor),
+            Some(Pagination::Object { cursor }) => cursor,
+            None => None,
+        }
+        .filter(|cursor| !cursor.as_str().is_empty());
+        Ok(helix::Response::new(
+            inner.data, cursor, request, None, None,
+        ))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetExtensionLiveChannelsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "broadcaster_id": "252766116",
+                "broadcaster_name": "swoosh_xii",
+                "game_name": "Tom Clancy's Rainbow Six Siege",
+                "game_id": "460630",
+                "title": "[PS4] ITA/ENG UNRANKED CHILLIN' (SUB 1/2) - !instagram !donation !sens !team !youtube"
+            },
+            {
+                "broadcaster_id": "136062275",
+                "broadcaster_name": "ShiiSs_",
+                "game_name": "Fortnite",
+                "game_id": "33214",
+                "title": "Fortnite but with friends"
+            }
+        ],
+        "pagination": "YVc1emRHRnNiRjh6TnpBME1qQXpNVjh4TmpBM05qVTBOVE13T1RRMk9UQXdNRFV6T1RjMk9USXdNVGN4TlRrME1USTRNakl5TmpJM01UTXdOemsxTlRZd05qWXdPVGcwTURRMk5ETXpPVEEyTmpJM05qVXpNVFV5TmpRek5qTTNNVGN4TWpBek5UVTVNakl3TkRnd05EZzJOVE00TkRReU5UTTJNakF6TmpVME1UVXlOVFF5T1RNek9EazVOalEwTmpNeU1ESTJPVGs9"
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/live?extension_id=uo6dggojyb8d6soh92zknwmi5ej1q2"
+    );
+
+    let response =
+        GetExtensionLiveChannelsRequest::parse_response(Some(req), &uri, http_response).unwrap();
+    assert_eq!(response.data.len(), 2);
+    assert!(response.pagination.is_some());
+}
+
+#[cfg(test)]
+#[test]
+fn test_request_last_page() {
+    use helix::*;
+    let req = GetExtensionLiveChannelsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+
+    let data = br#"
+    {
+        "data": [],
+        "pagination": ""
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    let response =
+        GetExtensionLiveChannelsRequest::parse_response(Some(req), &uri, http_response).unwrap();
+    assert!(response.data.is_empty());
+    assert_eq!(response.pagination, None);
+}
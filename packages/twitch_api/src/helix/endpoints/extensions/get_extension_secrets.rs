@@ -0,0 +1,151 @@
+//! Gets an extension’s list of shared secrets.
+//! [`get-extension-secrets`](https://dev.twitch.tv/docs/api/reference#get-extension-secrets)
+//!
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetExtensionSecretsRequest]
+//!
+//! To use this endpoint, construct a [`GetExtensionSecretsRequest`] with the [`GetExtensionSecretsRequest::extension_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::get_extension_secrets;
+//! let request =
+//!     get_extension_secrets::GetExtensionSecretsRequest::extension_id(
+//!         "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     );
+//! ```
+//!
+//! ## Response: [ExtensionSecrets]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get_with_jwt()`](helix::HelixClient::req_get_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::get_extension_secrets};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let request = get_extension_secrets::GetExtensionSecretsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//! let response: helix::extensions::ExtensionSecrets = client.req_get_with_jwt(request, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&jwt, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetExtensionSecretsRequest::parse_response(None, &request.get_uri(), response)`](GetExtensionSecretsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Extension Secrets](super::get_extension_secrets)
+///
+/// [`get-extension-secrets`](https://dev.twitch.tv/docs/api/reference#get-extension-secrets)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetExtensionSecretsRequest<'a> {
+    /// The ID of the extension whose shared secrets you want to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+}
+
+impl<'a> GetExtensionSecretsRequest<'a> {
+    /// Get the shared secrets of an extension
+    pub fn extension_id(extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+        }
+    }
+}
+
+/// Return Values for [Get Extension Secrets](super::get_extension_secrets)
+///
+/// [`get-extension-secrets`](https://dev.twitch.tv/docs/api/reference#get-extension-secrets)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionSecrets {
+    /// The version number that identifies this definition of the secret’s data.
+    pub format_version: i64,
+    /// The list of secrets.
+    pub secrets: Vec<ExtensionSecret>,
+}
+
+/// A shared secret of an extension
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionSecret {
+    /// The raw secret that you use with JWT encoding, base64 encoded.
+    pub content: String,
+    /// The UTC date and time that you may begin using this secret to sign a JWT.
+    pub active_at: types::Timestamp,
+    /// The UTC date and time that you must stop using this secret to decode a JWT.
+    pub expires_at: types::Timestamp,
+}
+
+impl Request for GetExtensionSecretsRequest<'_> {
+    type Response = ExtensionSecrets;
+
+    const PATH: &'static str = "extensions/jwt/secrets";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetExtensionSecretsRequest<'_> {
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, <Self as Request>::Response>, helix::HelixRequestGetError>
+    where
+        Self: Sized,
+    {
+        helix::parse_single_return(request, uri, response, status)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetExtensionSecretsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "format_version": 1,
+                "secrets": [
+                    {
+                        "content": "secret",
+                        "active_at": "2021-03-29T06:58:40.858343036Z",
+                        "expires_at": "2121-03-05T06:58:40.858343036Z"
+                    }
+                ]
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/jwt/secrets?extension_id=uo6dggojyb8d6soh92zknwmi5ej1q2"
+    );
+
+    let secrets = GetExtensionSecretsRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert_eq!(secrets.secrets[0].content, "secret");
+}
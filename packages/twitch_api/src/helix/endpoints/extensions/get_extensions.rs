@@ -0,0 +1,383 @@
+//! Gets information about an extension.
+//! [`get-extensions`](https://dev.twitch.tv/docs/api/reference#get-extensions)
+//!
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetExtensionsRequest]
+//!
+//! To use this endpoint, construct a [`GetExtensionsRequest`] with the [`GetExtensionsRequest::extension_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::get_extensions;
+//! let request = get_extensions::GetExtensionsRequest::extension_id(
+//!     "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//! )
+//! .extension_version("0.0.9");
+//! ```
+//!
+//! ## Response: [Extension]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get_with_jwt()`](helix::HelixClient::req_get_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::get_extensions};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let request = get_extensions::GetExtensionsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//! let response: Vec<helix::extensions::Extension> = client.req_get_with_jwt(request, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&jwt, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetExtensionsRequest::parse_response(None, &request.get_uri(), response)`](GetExtensionsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Extensions](super::get_extensions)
+///
+/// [`get-extensions`](https://dev.twitch.tv/docs/api/reference#get-extensions)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetExtensionsRequest<'a> {
+    /// The ID of the extension to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The version of the extension to get. If not specified, it returns the latest version.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_version: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetExtensionsRequest<'a> {
+    /// Get the latest version of an extension
+    pub fn extension_id(extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+            extension_version: None,
+        }
+    }
+
+    /// Get a specific version of the extension
+    pub fn extension_version(mut self, extension_version: impl Into<Cow<'a, str>>) -> Self {
+        self.extension_version = Some(extension_version.into());
+        self
+    }
+}
+
+/// Return Values for [Get Extensions](super::get_extensions) and [Get Released Extensions](super::get_released_extensions)
+///
+/// [`get-extensions`](https://dev.twitch.tv/docs/api/reference#get-extensions)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct Extension {
+    /// The name of the user or organization that owns the extension.
+    pub author_name: String,
+    /// Whether the extension has features that use Bits.
+    pub bits_enabled: bool,
+    /// Whether a user can install the extension on their channel.
+    pub can_install: bool,
+    /// Where the extension’s configuration is stored.
+    pub configuration_location: ConfigurationLocation,
+    /// A longer description of the extension. It appears on the details page.
+    pub description: String,
+    /// A URL to the extension’s Terms of Service.
+    pub eula_tos_url: String,
+    /// Whether the extension can communicate with the installed channel’s chat room.
+    pub has_chat_support: bool,
+    /// A URL to the default icon that’s displayed in the Extensions directory.
+    pub icon_url: String,
+    /// URLs to the icons of the extension in different sizes.
+    pub icon_urls: ExtensionIconUrls,
+    /// The extension’s ID.
+    pub id: types::ExtensionId,
+    /// The extension’s name.
+    pub name: String,
+    /// A URL to the extension’s privacy policy.
+    pub privacy_policy_url: String,
+    /// Whether the extension wants to explicitly ask viewers to link their Twitch identity.
+    pub request_identity_link: bool,
+    /// A list of URLs to screenshots that are shown in the Extensions marketplace.
+    pub screenshot_urls: Vec<String>,
+    /// The state of the extension.
+    pub state: ExtensionState,
+    /// Whether the extension can view the user’s subscription level on the channel that the extension is installed on.
+    pub subscriptions_support_level: SubscriptionsSupportLevel,
+    /// A short description of the extension that streamers see when hovering over the Discover page in the Extensions manager.
+    pub summary: String,
+    /// The email address that users use to get support for the extension.
+    pub support_email: String,
+    /// The extension’s version number.
+    pub version: String,
+    /// A brief description displayed on the channel to explain how the extension works.
+    pub viewer_summary: String,
+    /// Information about the extension’s components.
+    pub views: ExtensionViews,
+    /// Allowlisted configuration URLs for displaying the extension.
+    pub allowlisted_config_urls: Vec<String>,
+    /// Allowlisted panel URLs for displaying the extension.
+    pub allowlisted_panel_urls: Vec<String>,
+}
+
+/// Where the configuration of an [extension](Extension) is stored
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ConfigurationLocation {
+    /// The Extensions Configuration Service hosted by Twitch.
+    Hosted,
+    /// The configuration is stored by the extension developer.
+    Custom,
+    /// The extension doesn’t require configuration.
+    None,
+}
+
+/// The state of an [extension](Extension)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum ExtensionState {
+    /// The extension has been approved.
+    Approved,
+    /// The assets of the extension have been uploaded.
+    AssetsUploaded,
+    /// The extension has been deleted.
+    Deleted,
+    /// The extension has been deprecated.
+    Deprecated,
+    /// The extension is being reviewed.
+    InReview,
+    /// The extension is being tested.
+    InTest,
+    /// The extension needs action from the developer.
+    PendingAction,
+    /// The extension has been rejected.
+    Rejected,
+    /// The extension has been released.
+    Released,
+    /// An unknown state, contains the raw string provided by Twitch.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// Whether an [extension](Extension) can view the subscription level of users
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum SubscriptionsSupportLevel {
+    /// The extension can’t view the user’s subscription level.
+    None,
+    /// The extension can view the user’s subscription level.
+    Optional,
+}
+
+/// URLs to the icons of an [extension](Extension)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionIconUrls {
+    /// A URL to the 100x100 icon.
+    #[serde(rename = "100x100", default)]
+    pub size_100x100: Option<String>,
+    /// A URL to the 24x24 icon.
+    #[serde(rename = "24x24", default)]
+    pub size_24x24: Option<String>,
+    /// A URL to the 300x200 icon.
+    #[serde(rename = "300x200", default)]
+    pub size_300x200: Option<String>,
+}
+
+/// The views of an [extension](Extension), views that the extension does not have are [`None`]
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionViews {
+    /// Describes how the extension is displayed on mobile devices.
+    #[serde(default)]
+    pub mobile: Option<ExtensionView>,
+    /// Describes how the extension is rendered if the extension may be activated as a panel extension.
+    #[serde(default)]
+    pub panel: Option<PanelView>,
+    /// Describes how the extension is rendered if the extension may be activated as a video-overlay extension.
+    #[serde(default)]
+    pub video_overlay: Option<ExtensionView>,
+    /// Describes how the extension is rendered if the extension may be activated as a video-component extension.
+    #[serde(default)]
+    pub component: Option<ComponentView>,
+    /// Describes the view that is shown to broadcasters while they are configuring your extension within the Extension Manager.
+    #[serde(default)]
+    pub config: Option<ExtensionView>,
+}
+
+/// A view of an [extension](Extension)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionView {
+    /// The HTML file that is shown to viewers on the channel page when the extension is in this view.
+    pub viewer_url: String,
+    /// Whether the extension can link to non-Twitch domains.
+    #[serde(default)]
+    pub can_link_external_content: bool,
+}
+
+/// The panel view of an [extension](Extension)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct PanelView {
+    /// The HTML file that is shown to viewers on the channel page when the extension is activated in a Panel slot.
+    pub viewer_url: String,
+    /// The height, in pixels, of the panel component that the extension is rendered in.
+    pub height: i64,
+    /// Whether the extension can link to non-Twitch domains.
+    pub can_link_external_content: bool,
+}
+
+/// The video component view of an [extension](Extension)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ComponentView {
+    /// The HTML file that is shown to viewers on the channel page when the extension is activated in a Component slot.
+    pub viewer_url: String,
+    /// The width value of the ratio (width : height) used to scale the extension’s component.
+    #[serde(default)]
+    pub aspect_width: Option<i64>,
+    /// The height value of the ratio (width : height) used to scale the extension’s component.
+    #[serde(default)]
+    pub aspect_height: Option<i64>,
+    /// The width value of the ratio (width : height) used to scale the extension’s component.
+    pub aspect_ratio_x: i64,
+    /// The height value of the ratio (width : height) used to scale the extension’s component.
+    pub aspect_ratio_y: i64,
+    /// Whether to automatically scale the component to fit the viewer’s screen.
+    pub autoscale: bool,
+    /// The base width, in pixels, of the extension to use when scaling (see autoscale).
+    pub scale_pixels: i64,
+    /// The height as a percent of the maximum height of a video component extension. Values are between 1% - 100%, multiplied by 100.
+    pub target_height: i64,
+    /// The size of the component.
+    #[serde(default)]
+    pub size: Option<i64>,
+    /// Whether the component can be zoomed.
+    #[serde(default)]
+    pub zoom: Option<bool>,
+    /// The zoom of the component in pixels.
+    #[serde(default)]
+    pub zoom_pixels: Option<i64>,
+    /// Whether the extension can link to non-Twitch domains.
+    pub can_link_external_content: bool,
+}
+
+impl Request for GetExtensionsRequest<'_> {
+    type Response = Vec<Extension>;
+
+    const PATH: &'static str = "extensions";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetExtensionsRequest<'_> {}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetExtensionsRequest::extension_id("pgn0bjv51epi7eaekt53tovjnc82qo")
+        .extension_version("0.0.1");
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "author_name": "Twitch Developer Experience",
+                "bits_enabled": true,
+                "can_install": false,
+                "configuration_location": "hosted",
+                "description": "An extension for testing all the features that we add to Twitch Extensions",
+                "eula_tos_url": "",
+                "has_chat_support": true,
+                "icon_url": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/logob11f3c49-ec05-4ea2-b9f7-6a8e9a65e4e1",
+                "icon_urls": {
+                    "100x100": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/logob11f3c49-ec05-4ea2-b9f7-6a8e9a65e4e1",
+                    "24x24": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/taskbar905b19da-e7e8-4d1f-beb7-f543a861ac1e",
+                    "300x200": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/discovery6fe0d75b-3d52-4a26-8e75-8f70e5d3d8a6"
+                },
+                "id": "pgn0bjv51epi7eaekt53tovjnc82qo",
+                "name": "Official Developers Demo",
+                "privacy_policy_url": "",
+                "request_identity_link": true,
+                "screenshot_urls": [
+                    "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/screenshotd5a8b1fa-8a11-4c1d-afa4-3ce8d9fc2b4c"
+                ],
+                "state": "Released",
+                "subscriptions_support_level": "optional",
+                "summary": "Test ALL the extensions features!",
+                "support_email": "dx-extensions-test-dev@justin.tv",
+                "version": "0.0.1",
+                "viewer_summary": "Test ALL the extensions features!",
+                "views": {
+                    "mobile": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html"
+                    },
+                    "panel": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "height": 300,
+                        "can_link_external_content": false
+                    },
+                    "video_overlay": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "can_link_external_content": false
+                    },
+                    "component": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "aspect_width": 0,
+                        "aspect_height": 0,
+                        "aspect_ratio_x": 48000,
+                        "aspect_ratio_y": 36000,
+                        "autoscale": true,
+                        "scale_pixels": 1024,
+                        "target_height": 5333,
+                        "size": 0,
+                        "zoom": false,
+                        "zoom_pixels": 0,
+                        "can_link_external_content": false
+                    },
+                    "config": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "can_link_external_content": false
+                    }
+                },
+                "allowlisted_config_urls": [],
+                "allowlisted_panel_urls": []
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions?extension_id=pgn0bjv51epi7eaekt53tovjnc82qo&extension_version=0.0.1"
+    );
+
+    let extensions = GetExtensionsRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert_eq!(extensions[0].state, ExtensionState::Released);
+    assert_eq!(extensions[0].views.panel.as_ref().unwrap().height, 300);
+}
@@ -0,0 +1,175 @@
+//! Gets information about a released extension.
+//! [`get-released-extensions`](https://dev.twitch.tv/docs/api/reference#get-released-extensions)
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetReleasedExtensionsRequest]
+//!
+//! To use this endpoint, construct a [`GetReleasedExtensionsRequest`] with the [`GetReleasedExtensionsRequest::extension_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::get_released_extensions;
+//! let request =
+//!     get_released_extensions::GetReleasedExtensionsRequest::extension_id(
+//!         "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     );
+//! ```
+//!
+//! ## Response: [Extension]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::get_released_extensions};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_released_extensions::GetReleasedExtensionsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//! let response: Vec<helix::extensions::Extension> = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetReleasedExtensionsRequest::parse_response(None, &request.get_uri(), response)`](GetReleasedExtensionsRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Released Extensions](super::get_released_extensions)
+///
+/// [`get-released-extensions`](https://dev.twitch.tv/docs/api/reference#get-released-extensions)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetReleasedExtensionsRequest<'a> {
+    /// The ID of the extension to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The version of the extension to get. If not specified, it returns the latest version.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_version: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetReleasedExtensionsRequest<'a> {
+    /// Get the latest released version of an extension
+    pub fn extension_id(extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+            extension_version: None,
+        }
+    }
+
+    /// Get a specific released version of the extension
+    pub fn extension_version(mut self, extension_version: impl Into<Cow<'a, str>>) -> Self {
+        self.extension_version = Some(extension_version.into());
+        self
+    }
+}
+
+impl Request for GetReleasedExtensionsRequest<'_> {
+    type Response = Vec<Extension>;
+
+    const PATH: &'static str = "extensions/released";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetReleasedExtensionsRequest<'_> {}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetReleasedExtensionsRequest::extension_id("pgn0bjv51epi7eaekt53tovjnc82qo");
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "author_name": "Twitch Developer Experience",
+                "bits_enabled": true,
+                "can_install": false,
+                "configuration_location": "hosted",
+                "description": "An extension for testing all the features that we add to Twitch Extensions",
+                "eula_tos_url": "",
+                "has_chat_support": true,
+                "icon_url": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/logob11f3c49-ec05-4ea2-b9f7-6a8e9a65e4e1",
+                "icon_urls": {
+                    "100x100": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/logob11f3c49-ec05-4ea2-b9f7-6a8e9a65e4e1",
+                    "24x24": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/taskbar905b19da-e7e8-4d1f-beb7-f543a861ac1e",
+                    "300x200": "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/discovery6fe0d75b-3d52-4a26-8e75-8f70e5d3d8a6"
+                },
+                "id": "pgn0bjv51epi7eaekt53tovjnc82qo",
+                "name": "Official Developers Demo",
+                "privacy_policy_url": "",
+                "request_identity_link": true,
+                "screenshot_urls": [
+                    "https://extensions-discovery-images.twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/screenshotd5a8b1fa-8a11-4c1d-afa4-3ce8d9fc2b4c"
+                ],
+                "state": "Released",
+                "subscriptions_support_level": "optional",
+                "summary": "Test ALL the extensions features!",
+                "support_email": "dx-extensions-test-dev@justin.tv",
+                "version": "0.0.1",
+                "viewer_summary": "Test ALL the extensions features!",
+                "views": {
+                    "mobile": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html"
+                    },
+                    "panel": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "height": 300,
+                        "can_link_external_content": false
+                    },
+                    "video_overlay": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "can_link_external_content": false
+                    },
+                    "component": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "aspect_width": 0,
+                        "aspect_height": 0,
+                        "aspect_ratio_x": 48000,
+                        "aspect_ratio_y": 36000,
+                        "autoscale": true,
+                        "scale_pixels": 1024,
+                        "target_height": 5333,
+                        "size": 0,
+                        "zoom": false,
+                        "zoom_pixels": 0,
+                        "can_link_external_content": false
+                    },
+                    "config": {
+                        "viewer_url": "https://pgn0bjv51epi7eaekt53tovjnc82qo.ext-twitch.tv/pgn0bjv51epi7eaekt53tovjnc82qo/0.0.1/a30ba4aa2e5d5fd3f1adf4bc8ac4d8ad/index.html",
+                        "can_link_external_content": false
+                    }
+                },
+                "allowlisted_config_urls": [],
+                "allowlisted_panel_urls": []
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/released?extension_id=pgn0bjv51epi7eaekt53tovjnc82qo"
+    );
+
+    let extensions = GetReleasedExtensionsRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert_eq!(extensions[0].id.as_str(), "pgn0bjv51epi7eaekt53tovjnc82qo");
+}
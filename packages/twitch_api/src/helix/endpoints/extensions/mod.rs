@@ -0,0 +1,124 @@
+//! Helix endpoints regarding extensions
+//!
+//! Most of these endpoints are authorized with a JWT signed with the secret of the extension instead of an OAuth token,
+//! send them with [`HelixClient::req_get_with_jwt`](crate::helix::HelixClient::req_get_with_jwt) and friends, see [`helix::jwt`](crate::helix::jwt).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use twitch_api::helix::{HelixClient, extensions::GetReleasedExtensionsRequest};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! let client = HelixClient::default();
+//! # let _: &HelixClient<twitch_api::DummyHttpClient> = &client;
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let req = GetReleasedExtensionsRequest::extension_id("uo6dggojyb8d6soh92zknwmi5ej1q2");
+//!
+//! println!("{:?}", &client.req_get(req, &token).await?.data);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Implemented endpoints
+//!
+//! <!-- generate with "cargo xtask overview" (with a nightly toolchain) -->
+//! <!-- BEGIN-OVERVIEW -->
+//! <details open><summary style="cursor: pointer">Extensions 🟢 12/12</summary>
+//!
+//! | Endpoint | Helper | Module |
+//! |---|---|---|
+//! | [Get Extension Configuration Segment](https://dev.twitch.tv/docs/api/reference#get-extension-configuration-segment) | - | [`get_extension_configuration_segment`] |
+//! | [Set Extension Configuration Segment](https://dev.twitch.tv/docs/api/reference#set-extension-configuration-segment) | - | [`set_extension_configuration_segment`] |
+//! | [Set Extension Required Configuration](https://dev.twitch.tv/docs/api/reference#set-extension-required-configuration) | - | [`set_extension_required_configuration`] |
+//! | [Send Extension PubSub Message](https://dev.twitch.tv/docs/api/reference#send-extension-pubsub-message) | - | [`send_extension_pubsub_message`] |
+//! | [Get Extension Live Channels](https://dev.twitch.tv/docs/api/reference#get-extension-live-channels) | - | [`get_extension_live_channels`] |
+//! | [Get Extension Secrets](https://dev.twitch.tv/docs/api/reference#get-extension-secrets) | - | [`get_extension_secrets`] |
+//! | [Create Extension Secret](https://dev.twitch.tv/docs/api/reference#create-extension-secret) | - | [`create_extension_secret`] |
+//! | [Send Extension Chat Message](https://dev.twitch.tv/docs/api/reference#send-extension-chat-message) | - | [`send_extension_chat_message`] |
+//! | [Get Extensions](https://dev.twitch.tv/docs/api/reference#get-extensions) | - | [`get_extensions`] |
+//! | [Get Released Extensions](https://dev.twitch.tv/docs/api/reference#get-released-extensions) | - | [`get_released_extensions`] |
+//! | [Get Extension Bits Products](https://dev.twitch.tv/docs/api/reference#get-extension-bits-products) | - | [`get_extension_bits_products`] |
+//! | [Update Extension Bits Product](https://dev.twitch.tv/docs/api/reference#update-extension-bits-product) | - | [`update_extension_bits_product`] |
+//!
+//! </details>
+//!
+//! <!-- END-OVERVIEW -->
+
+use crate::{
+    helix::{self, Request},
+    types,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+pub mod create_extension_secret;
+pub mod get_extension_bits_products;
+pub mod get_extension_configuration_segment;
+pub mod get_extension_live_channels;
+pub mod get_extension_secrets;
+pub mod get_extensions;
+pub mod get_released_extensions;
+pub mod send_extension_chat_message;
+pub mod send_extension_pubsub_message;
+pub mod set_extension_configuration_segment;
+pub mod set_extension_required_configuration;
+pub mod update_extension_bits_product;
+
+#[doc(inline)]
+pub use create_extension_secret::CreateExtensionSecretRequest;
+#[doc(inline)]
+pub use get_extension_bits_products::{
+    BitsProduct, BitsProductCost, CostType, GetExtensionBitsProductsRequest,
+};
+#[doc(inline)]
+pub use get_extension_configuration_segment::{
+    ExtensionConfiguration, GetExtensionConfigurationSegmentRequest,
+};
+#[doc(inline)]
+pub use get_extension_live_channels::{ExtensionLiveChannel, GetExtensionLiveChannelsRequest};
+#[doc(inline)]
+pub use get_extension_secrets::{ExtensionSecret, ExtensionSecrets, GetExtensionSecretsRequest};
+#[doc(inline)]
+pub use get_extensions::{
+    ComponentView, ConfigurationLocation, Extension, ExtensionIconUrls, ExtensionState,
+    ExtensionView, ExtensionViews, GetExtensionsRequest, PanelView, SubscriptionsSupportLevel,
+};
+#[doc(inline)]
+pub use get_released_extensions::GetReleasedExtensionsRequest;
+#[doc(inline)]
+pub use send_extension_chat_message::{
+    SendExtensionChatMessageBody, SendExtensionChatMessageRequest, SendExtensionChatMessageResponse,
+};
+#[doc(inline)]
+pub use send_extension_pubsub_message::{
+    PubSubTarget, SendExtensionPubSubMessageBody, SendExtensionPubSubMessageRequest,
+    SendExtensionPubSubMessageResponse,
+};
+#[doc(inline)]
+pub use set_extension_configuration_segment::{
+    SetExtensionConfigurationSegmentBody, SetExtensionConfigurationSegmentRequest,
+    SetExtensionConfigurationSegmentResponse,
+};
+#[doc(inline)]
+pub use set_extension_required_configuration::{
+    SetExtensionRequiredConfigurationBody, SetExtensionRequiredConfigurationRequest,
+    SetExtensionRequiredConfigurationResponse,
+};
+#[doc(inline)]
+pub use update_extension_bits_product::{
+    UpdateExtensionBitsProductBody, UpdateExtensionBitsProductRequest,
+};
+
+/// A segment of the configuration of an extension
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ExtensionSegment {
+    /// The configuration of a broadcaster, set by the broadcaster.
+    Broadcaster,
+    /// The configuration of a broadcaster, set by the developer of the extension.
+    Developer,
+    /// The configuration of all channels, set by the developer of the extension.
+    Global,
+}
@@ -0,0 +1,196 @@
+//! Sends a message to the specified broadcaster’s chat room.
+//! [`send-extension-chat-message`](https://dev.twitch.tv/docs/api/reference#send-extension-chat-message)
+//!
+//! The extension’s name is used as the username for the message in the chat room.
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [SendExtensionChatMessageRequest]
+//!
+//! To use this endpoint, construct a [`SendExtensionChatMessageRequest`] with the [`SendExtensionChatMessageRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::send_extension_chat_message;
+//! let request =
+//!     send_extension_chat_message::SendExtensionChatMessageRequest::new(
+//!         "1234",
+//!     );
+//! ```
+//!
+//! ## Body: [SendExtensionChatMessageBody]
+//!
+//! We also need to provide a body to the request containing the message to send.
+//!
+//! ```
+//! # use twitch_api::helix::extensions::send_extension_chat_message;
+//! let body = send_extension_chat_message::SendExtensionChatMessageBody::new(
+//!     "Hello",
+//!     "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     "0.0.9",
+//! );
+//! ```
+//!
+//! ## Response: [SendExtensionChatMessageResponse]
+//!
+//! Send the request to receive the response with [`HelixClient::req_post_with_jwt()`](helix::HelixClient::req_post_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::send_extension_chat_message};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let request = send_extension_chat_message::SendExtensionChatMessageRequest::new("1234");
+//! let body = send_extension_chat_message::SendExtensionChatMessageBody::new(
+//!     "Hello",
+//!     "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     "0.0.9",
+//! );
+//! let response: send_extension_chat_message::SendExtensionChatMessageResponse = client.req_post_with_jwt(request, body, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(body, &jwt, &client_id)`](helix::RequestPost::create_request)
+//! and parse the [`http::Response`] with [`SendExtensionChatMessageRequest::parse_response(None, &request.get_uri(), response)`](SendExtensionChatMessageRequest::parse_response)
+
+use super::*;
+use helix::RequestPost;
+
+/// Query Parameters for [Send Extension Chat Message](super::send_extension_chat_message)
+///
+/// [`send-extension-chat-message`](https://dev.twitch.tv/docs/api/reference#send-extension-chat-message)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct SendExtensionChatMessageRequest<'a> {
+    /// The ID of the broadcaster that has activated the extension.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+}
+
+impl<'a> SendExtensionChatMessageRequest<'a> {
+    /// Send a chat message to the chat room of the broadcaster
+    pub fn new(broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+        }
+    }
+}
+
+/// Body Parameters for [Send Extension Chat Message](super::send_extension_chat_message)
+///
+/// [`send-extension-chat-message`](https://dev.twitch.tv/docs/api/reference#send-extension-chat-message)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[non_exhaustive]
+pub struct SendExtensionChatMessageBody<'a> {
+    /// The message. The message may contain a maximum of 280 characters.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub text: Cow<'a, str>,
+    /// The ID of the extension that’s sending the chat message.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The extension’s version number.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_version: Cow<'a, str>,
+}
+
+impl<'a> SendExtensionChatMessageBody<'a> {
+    /// Send a message as the given extension version
+    pub fn new(
+        text: impl Into<Cow<'a, str>>,
+        extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a,
+        extension_version: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            extension_id: extension_id.into_cow(),
+            extension_version: extension_version.into(),
+        }
+    }
+}
+
+impl helix::private::SealedSerialize for SendExtensionChatMessageBody<'_> {}
+
+/// Return Values for [Send Extension Chat Message](super::send_extension_chat_message)
+///
+/// [`send-extension-chat-message`](https://dev.twitch.tv/docs/api/reference#send-extension-chat-message)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum SendExtensionChatMessageResponse {
+    /// Successfully sent the chat message.
+    Success,
+}
+
+impl Request for SendExtensionChatMessageRequest<'_> {
+    type Response = SendExtensionChatMessageResponse;
+
+    const PATH: &'static str = "extensions/chat";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl<'a> RequestPost for SendExtensionChatMessageRequest<'a> {
+    type Body = SendExtensionChatMessageBody<'a>;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPostError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                SendExtensionChatMessageResponse::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPostError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = SendExtensionChatMessageRequest::new("237757755");
+
+    let body =
+        SendExtensionChatMessageBody::new("Hello", "uo6dggojyb8d6soh92zknwmi5ej1q2", "0.0.9");
+
+    assert_eq!(
+        std::str::from_utf8(&body.try_to_body().unwrap()).unwrap(),
+        r#"{"text":"Hello","extension_id":"uo6dggojyb8d6soh92zknwmi5ej1q2","extension_version":"0.0.9"}"#
+    );
+
+    dbg!(req.create_request(body, "jwt", "clientid").unwrap());
+
+    // From twitch docs
+    let data = vec![];
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/chat?broadcaster_id=237757755"
+    );
+
+    dbg!(SendExtensionChatMessageRequest::parse_response(Some(req), &uri, http_response).unwrap());
+}
@@ -0,0 +1,282 @@
+//! Sends a message to one or more viewers.
+//! [`send-extension-pubsub-message`](https://dev.twitch.tv/docs/api/reference#send-extension-pubsub-message)
+//!
+//! You can send messages to a specific channel or to all channels where your extension is active.
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension, which needs the `pubsub_perms` to send to the targets.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [SendExtensionPubSubMessageRequest]
+//!
+//! To use this endpoint, construct a [`SendExtensionPubSubMessageRequest`] with the [`SendExtensionPubSubMessageRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::send_extension_pubsub_message;
+//! let request =
+//!     send_extension_pubsub_message::SendExtensionPubSubMessageRequest::new();
+//! ```
+//!
+//! ## Body: [SendExtensionPubSubMessageBody]
+//!
+//! We also need to provide a body to the request containing the message and who to send it to.
+//!
+//! ```
+//! # use twitch_api::helix::extensions::send_extension_pubsub_message;
+//! let body = send_extension_pubsub_message::SendExtensionPubSubMessageBody::broadcast(
+//!     "1234",
+//!     "hello world!",
+//! );
+//! ```
+//!
+//! ## Response: [SendExtensionPubSubMessageResponse]
+//!
+//! Send the request to receive the response with [`HelixClient::req_post_with_jwt()`](helix::HelixClient::req_post_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::send_extension_pubsub_message};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let request = send_extension_pubsub_message::SendExtensionPubSubMessageRequest::new();
+//! let body = send_extension_pubsub_message::SendExtensionPubSubMessageBody::broadcast("1234", "hello world!");
+//! let response: send_extension_pubsub_message::SendExtensionPubSubMessageResponse = client.req_post_with_jwt(request, body, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(body, &jwt, &client_id)`](helix::RequestPost::create_request)
+//! and parse the [`http::Response`] with [`SendExtensionPubSubMessageRequest::parse_response(None, &request.get_uri(), response)`](SendExtensionPubSubMessageRequest::parse_response)
+
+use super::*;
+use helix::RequestPost;
+
+/// Query Parameters for [Send Extension PubSub Message](super::send_extension_pubsub_message)
+///
+/// [`send-extension-pubsub-message`](https://dev.twitch.tv/docs/api/reference#send-extension-pubsub-message)
+#[derive(PartialEq, Eq, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct SendExtensionPubSubMessageRequest<'a> {
+    #[serde(skip)]
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+
+impl SendExtensionPubSubMessageRequest<'_> {
+    /// Send a PubSub message
+    pub fn new() -> Self { Self::default() }
+}
+
+/// Who to send a [PubSub message](super::send_extension_pubsub_message) to
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[non_exhaustive]
+pub enum PubSubTarget {
+    /// All viewers of the broadcaster
+    Broadcast,
+    /// All viewers of all channels where the extension is active
+    Global,
+    /// A single viewer
+    Whisper(types::UserId),
+}
+
+impl std::fmt::Display for PubSubTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PubSubTarget::Broadcast => f.write_str("broadcast"),
+            PubSubTarget::Global => f.write_str("global"),
+            PubSubTarget::Whisper(user_id) => write!(f, "whisper-{}", user_id),
+        }
+    }
+}
+
+impl std::str::FromStr for PubSubTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "broadcast" => Ok(PubSubTarget::Broadcast),
+            "global" => Ok(PubSubTarget::Global),
+            _ => match s.strip_prefix("whisper-") {
+                Some(user_id) if !user_id.is_empty() => Ok(PubSubTarget::Whisper(user_id.into())),
+                _ => Err(format!("unknown pubsub target `{}`", s)),
+            },
+        }
+    }
+}
+
+impl serde::Serialize for PubSubTarget {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PubSubTarget {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        let s = <Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Body Parameters for [Send Extension PubSub Message](super::send_extension_pubsub_message)
+///
+/// [`send-extension-pubsub-message`](https://dev.twitch.tv/docs/api/reference#send-extension-pubsub-message)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[non_exhaustive]
+pub struct SendExtensionPubSubMessageBody<'a> {
+    /// The targets to send the message to.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    pub target: Cow<'a, [PubSubTarget]>,
+    /// The ID of the broadcaster to send the message to. Required unless [`is_global_broadcast`](Self::is_global_broadcast) is set.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcaster_id: Option<Cow<'a, types::UserIdRef>>,
+    /// Whether the message should be sent to all channels where the extension is active.
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    #[serde(default)]
+    pub is_global_broadcast: bool,
+    /// The message to send. The message can be a plain-text string or a string-encoded JSON object. The message is limited to a maximum of 5 KB.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub message: Cow<'a, str>,
+}
+
+impl<'a> SendExtensionPubSubMessageBody<'a> {
+    /// Send a message to all viewers of the broadcaster
+    pub fn broadcast(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        message: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            target: Cow::Borrowed(&[PubSubTarget::Broadcast]),
+            broadcaster_id: Some(broadcaster_id.into_cow()),
+            is_global_broadcast: false,
+            message: message.into(),
+        }
+    }
+
+    /// Send a message to all viewers of all channels where the extension is active
+    pub fn global(message: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            target: Cow::Borrowed(&[PubSubTarget::Global]),
+            broadcaster_id: None,
+            is_global_broadcast: true,
+            message: message.into(),
+        }
+    }
+
+    /// Send a message to a single viewer of the broadcaster
+    pub fn whisper(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        user_id: impl Into<types::UserId>,
+        message: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            target: Cow::Owned(vec![PubSubTarget::Whisper(user_id.into())]),
+            broadcaster_id: Some(broadcaster_id.into_cow()),
+            is_global_broadcast: false,
+            message: message.into(),
+        }
+    }
+}
+
+impl helix::private::SealedSerialize for SendExtensionPubSubMessageBody<'_> {}
+
+/// Return Values for [Send Extension PubSub Message](super::send_extension_pubsub_message)
+///
+/// [`send-extension-pubsub-message`](https://dev.twitch.tv/docs/api/reference#send-extension-pubsub-message)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum SendExtensionPubSubMessageResponse {
+    /// Successfully sent the message.
+    Success,
+}
+
+impl Request for SendExtensionPubSubMessageRequest<'_> {
+    type Response = SendExtensionPubSubMessageResponse;
+
+    const PATH: &'static str = "extensions/pubsub";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl<'a> RequestPost for SendExtensionPubSubMessageRequest<'a> {
+    type Body = SendExtensionPubSubMessageBody<'a>;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPostError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                SendExtensionPubSubMessageResponse::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPostError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = SendExtensionPubSubMessageRequest::new();
+
+    let body = SendExtensionPubSubMessageBody::broadcast("141981764", "hello world!");
+
+    assert_eq!(
+        std::str::from_utf8(&body.try_to_body().unwrap()).unwrap(),
+        r#"{"target":["broadcast"],"broadcaster_id":"141981764","is_global_broadcast":false,"message":"hello world!"}"#
+    );
+
+    dbg!(req.create_request(body, "jwt", "clientid").unwrap());
+
+    // From twitch docs
+    let data = vec![];
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/pubsub?"
+    );
+
+    dbg!(
+        SendExtensionPubSubMessageRequest::parse_response(Some(req), &uri, http_response).unwrap()
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_targets() {
+    let body = SendExtensionPubSubMessageBody::whisper("141981764", "1234", "hi");
+    let json = serde_json::to_string(&body).unwrap();
+    assert!(json.contains(r#""target":["whisper-1234"]"#));
+    let body: SendExtensionPubSubMessageBody = serde_json::from_str(&json).unwrap();
+    assert_eq!(body.target[0], PubSubTarget::Whisper("1234".into()));
+
+    let body = SendExtensionPubSubMessageBody::global("hi");
+    assert_eq!(
+        serde_json::to_string(&body).unwrap(),
+        r#"{"target":["global"],"is_global_broadcast":true,"message":"hi"}"#
+    );
+    assert!("whisper-".parse::<PubSubTarget>().is_err());
+}
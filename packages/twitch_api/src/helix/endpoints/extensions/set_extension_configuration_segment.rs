@@ -0,0 +1,227 @@
+//! Updates a configuration segment.
+//! [`set-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#set-extension-configuration-segment)
+//!
+//! This endpoint is authorized with a [signed JWT](crate::helix::jwt) of the extension.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [SetExtensionConfigurationSegmentRequest]
+//!
+//! To use this endpoint, construct a [`SetExtensionConfigurationSegmentRequest`] with the [`SetExtensionConfigurationSegmentRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::extensions::set_extension_configuration_segment;
+//! let request =
+//!     set_extension_configuration_segment::SetExtensionConfigurationSegmentRequest::new();
+//! ```
+//!
+//! ## Body: [SetExtensionConfigurationSegmentBody]
+//!
+//! We also need to provide a body to the request containing the segment to set.
+//!
+//! ```
+//! # use twitch_api::helix::extensions::{set_extension_configuration_segment, ExtensionSegment};
+//! let body = set_extension_configuration_segment::SetExtensionConfigurationSegmentBody::new(
+//!     "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     ExtensionSegment::Global,
+//! )
+//! .content("hello config!")
+//! .version("0.0.1");
+//! ```
+//!
+//! ## Response: [SetExtensionConfigurationSegmentResponse]
+//!
+//! Send the request to receive the response with [`HelixClient::req_put_with_jwt()`](helix::HelixClient::req_put_with_jwt).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, extensions::{set_extension_configuration_segment, ExtensionSegment}};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let jwt = helix::jwt::SignedJwt::new("uo6dggojyb8d6soh92zknwmi5ej1q2", "jwt");
+//! let request = set_extension_configuration_segment::SetExtensionConfigurationSegmentRequest::new();
+//! let body = set_extension_configuration_segment::SetExtensionConfigurationSegmentBody::new(
+//!     "uo6dggojyb8d6soh92zknwmi5ej1q2",
+//!     ExtensionSegment::Global,
+//! )
+//! .content("hello config!");
+//! let response: set_extension_configuration_segment::SetExtensionConfigurationSegmentResponse = client.req_put_with_jwt(request, body, &jwt).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(body, &jwt, &client_id)`](helix::RequestPut::create_request)
+//! and parse the [`http::Response`] with [`SetExtensionConfigurationSegmentRequest::parse_response(None, &request.get_uri(), response)`](SetExtensionConfigurationSegmentRequest::parse_response)
+
+use super::*;
+use helix::RequestPut;
+
+/// Query Parameters for [Set Extension Configuration Segment](super::set_extension_configuration_segment)
+///
+/// [`set-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#set-extension-configuration-segment)
+#[derive(PartialEq, Eq, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct SetExtensionConfigurationSegmentRequest<'a> {
+    #[serde(skip)]
+    #[cfg_attr(feature = "typed-builder", builder(default))]
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+
+impl SetExtensionConfigurationSegmentRequest<'_> {
+    /// Set a configuration segment of an extension
+    pub fn new() -> Self { Self::default() }
+}
+
+/// Body Parameters for [Set Extension Configuration Segment](super::set_extension_configuration_segment)
+///
+/// [`set-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#set-extension-configuration-segment)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[non_exhaustive]
+pub struct SetExtensionConfigurationSegmentBody<'a> {
+    /// The ID of the extension to update.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub extension_id: Cow<'a, types::ExtensionIdRef>,
+    /// The configuration segment to update.
+    pub segment: ExtensionSegment,
+    /// The ID of the broadcaster that installed the extension. Required if the segment is [broadcaster](ExtensionSegment::Broadcaster) or [developer](ExtensionSegment::Developer).
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcaster_id: Option<Cow<'a, types::UserIdRef>>,
+    /// The contents of the segment. This string may be a plain-text string or a string-encoded JSON object.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Cow<'a, str>>,
+    /// The version of the segment’s definition.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<Cow<'a, str>>,
+}
+
+impl<'a> SetExtensionConfigurationSegmentBody<'a> {
+    /// Set a configuration segment of an extension
+    pub fn new(
+        extension_id: impl types::IntoCow<'a, types::ExtensionIdRef> + 'a,
+        segment: ExtensionSegment,
+    ) -> Self {
+        Self {
+            extension_id: extension_id.into_cow(),
+            segment,
+            broadcaster_id: None,
+            content: None,
+            version: None,
+        }
+    }
+
+    /// Set the broadcaster whose configuration to update
+    pub fn broadcaster_id(
+        mut self,
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+    ) -> Self {
+        self.broadcaster_id = Some(broadcaster_id.into_cow());
+        self
+    }
+
+    /// Set the contents of the segment
+    pub fn content(mut self, content: impl Into<Cow<'a, str>>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Set the version of the segment
+    pub fn version(mut self, version: impl Into<Cow<'a, str>>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+}
+
+impl helix::private::SealedSerialize for SetExtensionConfigurationSegmentBody<'_> {}
+
+/// Return Values for [Set Extension Configuration Segment](super::set_extension_configuration_segment)
+///
+/// [`set-extension-configuration-segment`](https://dev.twitch.tv/docs/api/reference#set-extension-configuration-segment)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum SetExtensionConfigurationSegmentResponse {
+    /// Successfully updated the configuration.
+    Success,
+}
+
+impl Request for SetExtensionConfigurationSegmentRequest<'_> {
+    type Response = SetExtensionConfigurationSegmentResponse;
+
+    const PATH: &'static str = "extensions/configurations";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl<'a> RequestPut for SetExtensionConfigurationSegmentRequest<'a> {
+    type Body = SetExtensionConfigurationSegmentBody<'a>;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPutError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                SetExtensionConfigurationSegmentResponse::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPutError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = SetExtensionConfigurationSegmentRequest::new();
+
+    let body = SetExtensionConfigurationSegmentBody::new(
+        "uo6dggojyb8d6soh92zknwmi5ej1q2",
+        ExtensionSegment::Global,
+    )
+    .content("hello config!")
+    .version("0.0.1");
+
+    assert_eq!(
+        std::str::from_utf8(&body.try_to_body().unwrap()).unwrap(),
+        r#"{"extension_id":"uo6dggojyb8d6soh92zknwmi5ej1q2","segment":"global","content":"hello config!","version":"0.0.1"}"#
+    );
+
+    dbg!(req.create_request(body, "jwt", "clientid").unwrap());
+
+    // From twitch docs
+    let data = vec![];
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/extensions/configurations?"
+    );
+
+    dbg!(
+        SetExtensionConfigurationSegmentRequest::parse_response(Some(req), &uri, http_response)
+            .unwrap()
+    );
+}
@@ -0,0 +1,177 @@
+//! Allows a caller to remove a slot assignment from a user participating in an active Guest Star session.
+//! [`delete-guest-star-slot`](https://dev.twitch.tv/docs/api/reference#delete-guest-star-slot)
+//!
+//! This revokes their access to the session immediately and disables their access to publish into the broadcaster’s streaming software.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [DeleteGuestStarSlotRequest]
+//!
+//! To use this endpoint, construct a [`DeleteGuestStarSlotRequest`] with the [`DeleteGuestStarSlotRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::guest_star::delete_guest_star_slot;
+//! let request = delete_guest_star_slot::DeleteGuestStarSlotRequest::new(
+//!     "1234",
+//!     "5678",
+//!     "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+//!     "9876",
+//!     "1",
+//! );
+//! ```
+//!
+//! ## Response: [DeleteGuestStarSlot]
+//!
+//! Send the request to receive the response with [`HelixClient::req_delete()`](helix::HelixClient::req_delete).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, guest_star::delete_guest_star_slot};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = delete_guest_star_slot::DeleteGuestStarSlotRequest::new("1234", "5678", "2KFRQbFtpmfyD3IevNRnCzOPRJI", "9876", "1");
+//! let response: delete_guest_star_slot::DeleteGuestStarSlot = client.req_delete(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestDelete::create_request)
+//! and parse the [`http::Response`] with [`DeleteGuestStarSlotRequest::parse_response(None, &request.get_uri(), response)`](DeleteGuestStarSlotRequest::parse_response)
+
+use super::*;
+use helix::RequestDelete;
+
+/// Query Parameters for [Delete Guest Star Slot](super::delete_guest_star_slot)
+///
+/// [`delete-guest-star-slot`](https://dev.twitch.tv/docs/api/reference#delete-guest-star-slot)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct DeleteGuestStarSlotRequest<'a> {
+    /// The ID of the broadcaster running the Guest Star session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the broadcaster or a user that has permission to moderate the broadcaster’s chat room. This ID must match the user ID in the user access token.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub moderator_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the Guest Star session in which to remove the slot assignment.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub session_id: Cow<'a, types::GuestStarSessionIdRef>,
+    /// The Twitch User ID corresponding to the guest to remove from the session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub guest_id: Cow<'a, types::UserIdRef>,
+    /// The slot ID representing the slot assignment to remove from the session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub slot_id: Cow<'a, types::GuestStarSlotIdRef>,
+    /// Flag signaling that the guest should be reinvited to the session, sending them back to the invite queue.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub should_reinvite_guest: Option<bool>,
+}
+
+impl<'a> DeleteGuestStarSlotRequest<'a> {
+    /// Remove a guest from a slot as the specified moderator
+    pub fn new(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        moderator_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        session_id: impl types::IntoCow<'a, types::GuestStarSessionIdRef> + 'a,
+        guest_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        slot_id: impl types::IntoCow<'a, types::GuestStarSlotIdRef> + 'a,
+    ) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+            moderator_id: moderator_id.into_cow(),
+            session_id: session_id.into_cow(),
+            guest_id: guest_id.into_cow(),
+            slot_id: slot_id.into_cow(),
+            should_reinvite_guest: None,
+        }
+    }
+
+    /// Send the guest back to the invite queue
+    pub const fn should_reinvite_guest(mut self, should_reinvite_guest: bool) -> Self {
+        self.should_reinvite_guest = Some(should_reinvite_guest);
+        self
+    }
+}
+
+/// Return Values for [Delete Guest Star Slot](super::delete_guest_star_slot)
+///
+/// [`delete-guest-star-slot`](https://dev.twitch.tv/docs/api/reference#delete-guest-star-slot)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum DeleteGuestStarSlot {
+    /// 204 - Successfully removed user from slot
+    Success,
+}
+
+impl Request for DeleteGuestStarSlotRequest<'_> {
+    type Response = DeleteGuestStarSlot;
+
+    const PATH: &'static str = "guest_star/slot";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![any(
+        twitch_oauth2::Scope::ChannelManageGuestStar,
+        twitch_oauth2::Scope::ModeratorManageGuestStar,
+    )];
+}
+
+impl RequestDelete for DeleteGuestStarSlotRequest<'_> {
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestDeleteError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                DeleteGuestStarSlot::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestDeleteError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = DeleteGuestStarSlotRequest::new(
+        "9321049",
+        "9321049",
+        "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+        "144601104",
+        "1",
+    )
+    .should_reinvite_guest(true);
+
+    // From twitch docs
+    let data = br#""#.to_vec();
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/guest_star/slot?broadcaster_id=9321049&moderator_id=9321049&session_id=2KFRQbFtpmfyD3IevNRnCzOPRJI&guest_id=144601104&slot_id=1&should_reinvite_guest=true"
+    );
+
+    dbg!(DeleteGuestStarSlotRequest::parse_response(Some(req), &uri, http_response).unwrap());
+}
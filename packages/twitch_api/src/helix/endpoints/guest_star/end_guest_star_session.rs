@@ -0,0 +1,161 @@
+//! Programmatically ends a Guest Star session on behalf of the broadcaster.
+//! [`end-guest-star-session`](https://dev.twitch.tv/docs/api/reference#end-guest-star-session)
+//!
+//! Performs the same action as if the host clicked the “End Call” button in the Guest Star UI.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [EndGuestStarSessionRequest]
+//!
+//! To use this endpoint, construct an [`EndGuestStarSessionRequest`] with the [`EndGuestStarSessionRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::guest_star::end_guest_star_session;
+//! let request = end_guest_star_session::EndGuestStarSessionRequest::new(
+//!     "1234",
+//!     "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+//! );
+//! ```
+//!
+//! ## Response: [GuestStarSession]
+//!
+//! Send the request to receive the response with [`HelixClient::req_delete()`](helix::HelixClient::req_delete).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, guest_star::end_guest_star_session};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = end_guest_star_session::EndGuestStarSessionRequest::new("1234", "2KFRQbFtpmfyD3IevNRnCzOPRJI");
+//! let response: helix::guest_star::GuestStarSession = client.req_delete(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestDelete::create_request)
+//! and parse the [`http::Response`] with [`EndGuestStarSessionRequest::parse_response(None, &request.get_uri(), response)`](EndGuestStarSessionRequest::parse_response)
+
+use super::*;
+use helix::RequestDelete;
+
+/// Query Parameters for [End Guest Star Session](super::end_guest_star_session)
+///
+/// [`end-guest-star-session`](https://dev.twitch.tv/docs/api/reference#end-guest-star-session)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct EndGuestStarSessionRequest<'a> {
+    /// The ID of the broadcaster you want to end a Guest Star session for. Provided broadcaster_id must match the user_id in the auth token.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+    /// ID for the session to end on behalf of the broadcaster.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub session_id: Cow<'a, types::GuestStarSessionIdRef>,
+}
+
+impl<'a> EndGuestStarSessionRequest<'a> {
+    /// End the guest star session of the broadcaster
+    pub fn new(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        session_id: impl types::IntoCow<'a, types::GuestStarSessionIdRef> + 'a,
+    ) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+            session_id: session_id.into_cow(),
+        }
+    }
+}
+
+impl Request for EndGuestStarSessionRequest<'_> {
+    type Response = GuestStarSession;
+
+    const PATH: &'static str = "guest_star/session";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator =
+        twitch_oauth2::validator![twitch_oauth2::Scope::ChannelManageGuestStar];
+}
+
+impl RequestDelete for EndGuestStarSessionRequest<'_> {
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestDeleteError>
+    where
+        Self: Sized,
+    {
+        let inner: helix::InnerResponse<Vec<GuestStarSession>> = helix::parse_json(response, true)
+            .map_err(|_| helix::HelixRequestDeleteError::InvalidResponse {
+                reason: "could not parse response",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            })?;
+        let session = inner.data.into_iter().next().ok_or_else(|| {
+            helix::HelixRequestDeleteError::InvalidResponse {
+                reason: "missing response data",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }
+        })?;
+        Ok(helix::Response::with_data(session, request))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = EndGuestStarSessionRequest::new("9321049", "2KFRQbFtpmfyD3IevNRnCzOPRJI");
+
+    // From twitch docs
+    let data = br#"
+    {
+        "data": [
+            {
+                "id": "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+                "guests": [
+                    {
+                        "slot_id": "0",
+                        "is_live": true,
+                        "user_id": "9321049",
+                        "user_display_name": "Cool_User",
+                        "user_login": "cool_user",
+                        "volume": 100,
+                        "assigned_at": "2023-01-02T04:16:53.325Z",
+                        "audio_settings": {
+                            "is_available": true,
+                            "is_host_enabled": true,
+                            "is_guest_enabled": true
+                        },
+                        "video_settings": {
+                            "is_available": true,
+                            "is_host_enabled": true,
+                            "is_guest_enabled": true
+                        }
+                    }
+                ]
+            }
+        ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/guest_star/session?broadcaster_id=9321049&session_id=2KFRQbFtpmfyD3IevNRnCzOPRJI"
+    );
+
+    dbg!(EndGuestStarSessionRequest::parse_response(Some(req), &uri, http_response).unwrap());
+}
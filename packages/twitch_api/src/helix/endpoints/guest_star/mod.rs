@@ -0,0 +1,98 @@
+//! Helix endpoints regarding Guest Star
+//!
+//! # Implemented endpoints
+//!
+//! <!-- generate with "cargo xtask overview" (with a nightly toolchain) -->
+//! <!-- BEGIN-OVERVIEW -->
+//! <details open><summary style="cursor: pointer">Guest Star 🟢 12/12</summary>
+//!
+//! | Endpoint | Helper | Module |
+//! |---|---|---|
+//! | [Get Channel Guest Star Settings](https://dev.twitch.tv/docs/api/reference#get-channel-guest-star-settings) | [`HelixClient::get_channel_guest_star_settings`](crate::helix::HelixClient::get_channel_guest_star_settings) | [`get_channel_guest_star_settings`] |
+//! | [Update Channel Guest Star Settings](https://dev.twitch.tv/docs/api/reference#update-channel-guest-star-settings) | [`HelixClient::update_channel_guest_star_settings`](crate::helix::HelixClient::update_channel_guest_star_settings) | [`update_channel_guest_star_settings`] |
+//! | [Get Guest Star Session](https://dev.twitch.tv/docs/api/reference#get-guest-star-session) | [`HelixClient::get_guest_star_session`](crate::helix::HelixClient::get_guest_star_session) | [`get_guest_star_session`] |
+//! | [Create Guest Star Session](https://dev.twitch.tv/docs/api/reference#create-guest-star-session) | [`HelixClient::create_guest_star_session`](crate::helix::HelixClient::create_guest_star_session) | [`create_guest_star_session`] |
+//! | [End Guest Star Session](https://dev.twitch.tv/docs/api/reference#end-guest-star-session) | [`HelixClient::end_guest_star_session`](crate::helix::HelixClient::end_guest_star_session) | [`end_guest_star_session`] |
+//! | [Get Guest Star Invites](https://dev.twitch.tv/docs/api/reference#get-guest-star-invites) | [`HelixClient::get_guest_star_invites`](crate::helix::HelixClient::get_guest_star_invites) | [`get_guest_star_invites`] |
+//! | [Send Guest Star Invite](https://dev.twitch.tv/docs/api/reference#send-guest-star-invite) | [`HelixClient::send_guest_star_invite`](crate::helix::HelixClient::send_guest_star_invite) | [`send_guest_star_invite`] |
+//! | [Delete Guest Star Invite](https://dev.twitch.tv/docs/api/reference#delete-guest-star-invite) | [`HelixClient::delete_guest_star_invite`](crate::helix::HelixClient::delete_guest_star_invite) | [`delete_guest_star_invite`] |
+//! | [Assign Guest Star Slot](https://dev.twitch.tv/docs/api/reference#assign-guest-star-slot) | [`HelixClient::assign_guest_star_slot`](crate::helix::HelixClient::assign_guest_star_slot) | [`assign_guest_star_slot`] |
+//! | [Update Guest Star Slot](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot) | [`HelixClient::update_guest_star_slot`](crate::helix::HelixClient::update_guest_star_slot) | [`update_guest_star_slot`] |
+//! | [Delete Guest Star Slot](https://dev.twitch.tv/docs/api/reference#delete-guest-star-slot) | [`HelixClient::delete_guest_star_slot`](crate::helix::HelixClient::delete_guest_star_slot) | [`delete_guest_star_slot`] |
+//! | [Update Guest Star Slot Settings](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot-settings) | [`HelixClient::update_guest_star_slot_settings`](crate::helix::HelixClient::update_guest_star_slot_settings) | [`update_guest_star_slot_settings`] |
+//!
+//! </details>
+//!
+//! <!-- END-OVERVIEW -->
+use crate::{
+    helix::{self, Request},
+    types,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+pub mod assign_guest_star_slot;
+pub mod create_guest_star_session;
+pub mod delete_guest_star_invite;
+pub mod delete_guest_star_slot;
+pub mod end_guest_star_session;
+pub mod get_channel_guest_star_settings;
+pub mod get_guest_star_invites;
+pub mod get_guest_star_session;
+pub mod send_guest_star_invite;
+pub mod update_channel_guest_star_settings;
+pub mod update_guest_star_slot;
+pub mod update_guest_star_slot_settings;
+
+#[doc(inline)]
+pub use assign_guest_star_slot::{AssignGuestStarSlot, AssignGuestStarSlotRequest};
+#[doc(inline)]
+pub use create_guest_star_session::CreateGuestStarSessionRequest;
+#[doc(inline)]
+pub use delete_guest_star_invite::{DeleteGuestStarInvite, DeleteGuestStarInviteRequest};
+#[doc(inline)]
+pub use delete_guest_star_slot::{DeleteGuestStarSlot, DeleteGuestStarSlotRequest};
+#[doc(inline)]
+pub use end_guest_star_session::EndGuestStarSessionRequest;
+#[doc(inline)]
+pub use get_channel_guest_star_settings::{GetChannelGuestStarSettingsRequest, GuestStarSettings};
+#[doc(inline)]
+pub use get_guest_star_invites::{GetGuestStarInvitesRequest, GuestStarInvite, InviteStatus};
+#[doc(inline)]
+pub use get_guest_star_session::{
+    GetGuestStarSessionRequest, Guest, GuestMediaSettings, GuestStarSession,
+};
+#[doc(inline)]
+pub use send_guest_star_invite::{SendGuestStarInvite, SendGuestStarInviteRequest};
+#[doc(inline)]
+pub use update_channel_guest_star_settings::{
+    UpdateChannelGuestStarSettings, UpdateChannelGuestStarSettingsBody,
+    UpdateChannelGuestStarSettingsRequest,
+};
+#[doc(inline)]
+pub use update_guest_star_slot::{UpdateGuestStarSlot, UpdateGuestStarSlotRequest};
+#[doc(inline)]
+pub use update_guest_star_slot_settings::{
+    UpdateGuestStarSlotSettings, UpdateGuestStarSlotSettingsRequest,
+};
+
+/// How guests are laid out in a group browser source
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum GroupLayout {
+    /// All live guests are tiled within the browser source with the same size.
+    #[serde(rename = "TILED_LAYOUT")]
+    Tiled,
+    /// All live guests are tiled within the browser source with the same size. If there is an active screen share, it is sized larger than the other guests.
+    #[serde(rename = "SCREENSHARE_LAYOUT")]
+    Screenshare,
+    /// All live guests are arranged in a horizontal bar within the browser source.
+    #[serde(rename = "HORIZONTAL_LAYOUT")]
+    Horizontal,
+    /// All live guests are arranged in a vertical bar within the browser source.
+    #[serde(rename = "VERTICAL_LAYOUT")]
+    Vertical,
+    /// An unknown group layout, contains the raw string provided by Twitch.
+    #[serde(untagged)]
+    Unknown(String),
+}
@@ -0,0 +1,162 @@
+//! Sends an invite to a specified guest on behalf of the broadcaster for a Guest Star session in progress.
+//! [`send-guest-star-invite`](https://dev.twitch.tv/docs/api/reference#send-guest-star-invite)
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [SendGuestStarInviteRequest]
+//!
+//! To use this endpoint, construct a [`SendGuestStarInviteRequest`] with the [`SendGuestStarInviteRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::guest_star::send_guest_star_invite;
+//! let request = send_guest_star_invite::SendGuestStarInviteRequest::new(
+//!     "1234",
+//!     "5678",
+//!     "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+//!     "9876",
+//! );
+//! ```
+//!
+//! ## Response: [SendGuestStarInvite]
+//!
+//! Send the request to receive the response with [`HelixClient::req_post()`](helix::HelixClient::req_post).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, guest_star::send_guest_star_invite};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = send_guest_star_invite::SendGuestStarInviteRequest::new("1234", "5678", "2KFRQbFtpmfyD3IevNRnCzOPRJI", "9876");
+//! let response: send_guest_star_invite::SendGuestStarInvite = client.req_post(request, helix::EmptyBody, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestPost::create_request)
+//! and parse the [`http::Response`] with [`SendGuestStarInviteRequest::parse_response(None, &request.get_uri(), response)`](SendGuestStarInviteRequest::parse_response)
+
+use super::*;
+use helix::RequestPost;
+
+/// Query Parameters for [Send Guest Star Invite](super::send_guest_star_invite)
+///
+/// [`send-guest-star-invite`](https://dev.twitch.tv/docs/api/reference#send-guest-star-invite)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct SendGuestStarInviteRequest<'a> {
+    /// The ID of the broadcaster running the Guest Star session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the broadcaster or a user that has permission to moderate the broadcaster’s chat room. This ID must match the user ID in the user access token.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub moderator_id: Cow<'a, types::UserIdRef>,
+    /// The session ID for the invite to be sent on behalf of the broadcaster.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub session_id: Cow<'a, types::GuestStarSessionIdRef>,
+    /// Twitch User ID for the guest to invite to the Guest Star session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub guest_id: Cow<'a, types::UserIdRef>,
+}
+
+impl<'a> SendGuestStarInviteRequest<'a> {
+    /// Invite a guest to a guest star session as the specified moderator
+    pub fn new(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        moderator_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        session_id: impl types::IntoCow<'a, types::GuestStarSessionIdRef> + 'a,
+        guest_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+    ) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+            moderator_id: moderator_id.into_cow(),
+            session_id: session_id.into_cow(),
+            guest_id: guest_id.into_cow(),
+        }
+    }
+}
+
+/// Return Values for [Send Guest Star Invite](super::send_guest_star_invite)
+///
+/// [`send-guest-star-invite`](https://dev.twitch.tv/docs/api/reference#send-guest-star-invite)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum SendGuestStarInvite {
+    /// 204 - Successfully sent an invite
+    Success,
+}
+
+impl Request for SendGuestStarInviteRequest<'_> {
+    type Response = SendGuestStarInvite;
+
+    const PATH: &'static str = "guest_star/invites";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![any(
+        twitch_oauth2::Scope::ChannelManageGuestStar,
+        twitch_oauth2::Scope::ModeratorManageGuestStar,
+    )];
+}
+
+impl RequestPost for SendGuestStarInviteRequest<'_> {
+    type Body = helix::EmptyBody;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPostError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                SendGuestStarInvite::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPostError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = SendGuestStarInviteRequest::new(
+        "9321049",
+        "9321049",
+        "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+        "144601104",
+    );
+
+    dbg!(req
+        .create_request(Default::default(), "token", "clientid")
+        .unwrap());
+
+    // From twitch docs
+    let data = br#""#.to_vec();
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/guest_star/invites?broadcaster_id=9321049&moderator_id=9321049&session_id=2KFRQbFtpmfyD3IevNRnCzOPRJI&guest_id=144601104"
+    );
+
+    dbg!(SendGuestStarInviteRequest::parse_response(Some(req), &uri, http_response).unwrap());
+}
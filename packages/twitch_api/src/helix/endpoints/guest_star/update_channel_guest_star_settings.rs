@@ -0,0 +1,187 @@
+//! Mutates the channel settings for configuration of the Guest Star feature for a particular host.
+//! [`update-channel-guest-star-settings`](https://dev.twitch.tv/docs/api/reference#update-channel-guest-star-settings)
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [UpdateChannelGuestStarSettingsRequest]
+//!
+//! To use this endpoint, construct an [`UpdateChannelGuestStarSettingsRequest`] with the [`UpdateChannelGuestStarSettingsRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::guest_star::update_channel_guest_star_settings;
+//! let request =
+//!     update_channel_guest_star_settings::UpdateChannelGuestStarSettingsRequest::new(
+//!         "1234",
+//!     );
+//! ```
+//!
+//! ## Body: [UpdateChannelGuestStarSettingsBody]
+//!
+//! We also need to provide a body to the request containing what we want to change.
+//!
+//! ```
+//! # use twitch_api::helix::guest_star::update_channel_guest_star_settings;
+//! let mut body =
+//!     update_channel_guest_star_settings::UpdateChannelGuestStarSettingsBody::default();
+//! body.slot_count = Some(4);
+//! ```
+//!
+//! ## Response: [UpdateChannelGuestStarSettings]
+//!
+//! Send the request to receive the response with [`HelixClient::req_put()`](helix::HelixClient::req_put).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, guest_star::update_channel_guest_star_settings};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = update_channel_guest_star_settings::UpdateChannelGuestStarSettingsRequest::new("1234");
+//! let mut body = update_channel_guest_star_settings::UpdateChannelGuestStarSettingsBody::default();
+//! body.slot_count = Some(4);
+//! let response: update_channel_guest_star_settings::UpdateChannelGuestStarSettings = client.req_put(request, body, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestPut::create_request)
+//! and parse the [`http::Response`] with [`UpdateChannelGuestStarSettingsRequest::parse_response(None, &request.get_uri(), response)`](UpdateChannelGuestStarSettingsRequest::parse_response)
+
+use super::*;
+use helix::RequestPut;
+
+/// Query Parameters for [Update Channel Guest Star Settings](super::update_channel_guest_star_settings)
+///
+/// [`update-channel-guest-star-settings`](https://dev.twitch.tv/docs/api/reference#update-channel-guest-star-settings)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct UpdateChannelGuestStarSettingsRequest<'a> {
+    /// The ID of the broadcaster you want to update Guest Star settings for.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+}
+
+impl<'a> UpdateChannelGuestStarSettingsRequest<'a> {
+    /// Update the guest star settings of a broadcaster
+    pub fn new(broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+        }
+    }
+}
+
+/// Body Parameters for [Update Channel Guest Star Settings](super::update_channel_guest_star_settings)
+///
+/// [`update-channel-guest-star-settings`](https://dev.twitch.tv/docs/api/reference#update-channel-guest-star-settings)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[non_exhaustive]
+pub struct UpdateChannelGuestStarSettingsBody {
+    /// Flag determining if Guest Star moderators have access to control whether a guest is live once assigned to a slot.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_moderator_send_live_enabled: Option<bool>,
+    /// Number of slots the Guest Star call interface will allow the host to add to a call. Required to be between 1 and 6.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot_count: Option<usize>,
+    /// Flag determining if Browser Sources subscribed to sessions on this channel should output audio.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_browser_source_audio_enabled: Option<bool>,
+    /// This setting determines how the guests within a session should be laid out within the browser source.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_layout: Option<GroupLayout>,
+    /// Flag determining if Guest Star should regenerate the auth token associated with the channel’s browser sources. Providing a true value for this will immediately invalidate all browser sources previously configured in your streaming software.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regenerate_browser_sources: Option<bool>,
+}
+
+impl helix::private::SealedSerialize for UpdateChannelGuestStarSettingsBody {}
+
+/// Return Values for [Update Channel Guest Star Settings](super::update_channel_guest_star_settings)
+///
+/// [`update-channel-guest-star-settings`](https://dev.twitch.tv/docs/api/reference#update-channel-guest-star-settings)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum UpdateChannelGuestStarSettings {
+    /// 204 - Successfully updated channel settings
+    Success,
+}
+
+impl Request for UpdateChannelGuestStarSettingsRequest<'_> {
+    type Response = UpdateChannelGuestStarSettings;
+
+    const PATH: &'static str = "guest_star/channel_settings";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator =
+        twitch_oauth2::validator![twitch_oauth2::Scope::ChannelManageGuestStar];
+}
+
+impl RequestPut for UpdateChannelGuestStarSettingsRequest<'_> {
+    type Body = UpdateChannelGuestStarSettingsBody;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPutError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                UpdateChannelGuestStarSettings::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPutError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+#[allow(clippy::field_reassign_with_default)]
+fn test_request() {
+    use helix::*;
+    let req = UpdateChannelGuestStarSettingsRequest::new("9321049");
+
+    let mut body = UpdateChannelGuestStarSettingsBody::default();
+    body.group_layout = Some(GroupLayout::Screenshare);
+    body.slot_count = Some(6);
+
+    assert_eq!(
+        std::str::from_utf8(&body.try_to_body().unwrap()).unwrap(),
+        r#"{"slot_count":6,"group_layout":"SCREENSHARE_LAYOUT"}"#
+    );
+    dbg!(req.create_request(body, "token", "clientid").unwrap());
+
+    // From twitch docs
+    let data = br#""#.to_vec();
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/guest_star/channel_settings?broadcaster_id=9321049"
+    );
+
+    dbg!(
+        UpdateChannelGuestStarSettingsRequest::parse_response(Some(req), &uri, http_response)
+            .unwrap()
+    );
+}
@@ -0,0 +1,173 @@
+//! Allows a user to update the assigned slot for a particular user within the active Guest Star session.
+//! [`update-guest-star-slot`](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot)
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [UpdateGuestStarSlotRequest]
+//!
+//! To use this endpoint, construct an [`UpdateGuestStarSlotRequest`] with the [`UpdateGuestStarSlotRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::guest_star::update_guest_star_slot;
+//! let request = update_guest_star_slot::UpdateGuestStarSlotRequest::new(
+//!     "1234",
+//!     "5678",
+//!     "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+//!     "1",
+//! );
+//! ```
+//!
+//! ## Response: [UpdateGuestStarSlot]
+//!
+//! Send the request to receive the response with [`HelixClient::req_patch()`](helix::HelixClient::req_patch).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, guest_star::update_guest_star_slot};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = update_guest_star_slot::UpdateGuestStarSlotRequest::new("1234", "5678", "2KFRQbFtpmfyD3IevNRnCzOPRJI", "1");
+//! let response: update_guest_star_slot::UpdateGuestStarSlot = client.req_patch(request, helix::EmptyBody, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestPatch::create_request)
+//! and parse the [`http::Response`] with [`UpdateGuestStarSlotRequest::parse_response(None, &request.get_uri(), response)`](UpdateGuestStarSlotRequest::parse_response)
+
+use super::*;
+use helix::RequestPatch;
+
+/// Query Parameters for [Update Guest Star Slot](super::update_guest_star_slot)
+///
+/// [`update-guest-star-slot`](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct UpdateGuestStarSlotRequest<'a> {
+    /// The ID of the broadcaster running the Guest Star session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the broadcaster or a user that has permission to moderate the broadcaster’s chat room. This ID must match the user ID in the user access token.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub moderator_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the Guest Star session in which to update slot settings.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub session_id: Cow<'a, types::GuestStarSessionIdRef>,
+    /// The slot assignment previously assigned to a user.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub source_slot_id: Cow<'a, types::GuestStarSlotIdRef>,
+    /// The slot to move this user assignment to. If the destination slot is occupied, the user assigned will be swapped into source_slot_id.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub destination_slot_id: Option<Cow<'a, types::GuestStarSlotIdRef>>,
+}
+
+impl<'a> UpdateGuestStarSlotRequest<'a> {
+    /// Move the guest in a slot as the specified moderator
+    pub fn new(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        moderator_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        session_id: impl types::IntoCow<'a, types::GuestStarSessionIdRef> + 'a,
+        source_slot_id: impl types::IntoCow<'a, types::GuestStarSlotIdRef> + 'a,
+    ) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+            moderator_id: moderator_id.into_cow(),
+            session_id: session_id.into_cow(),
+            source_slot_id: source_slot_id.into_cow(),
+            destination_slot_id: None,
+        }
+    }
+
+    /// Set the slot to move the user to
+    pub fn destination_slot_id(
+        mut self,
+        destination_slot_id: impl types::IntoCow<'a, types::GuestStarSlotIdRef> + 'a,
+    ) -> Self {
+        self.destination_slot_id = Some(destination_slot_id.into_cow());
+        self
+    }
+}
+
+/// Return Values for [Update Guest Star Slot](super::update_guest_star_slot)
+///
+/// [`update-guest-star-slot`](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum UpdateGuestStarSlot {
+    /// 204 - Successfully updated slot(s)
+    Success,
+}
+
+impl Request for UpdateGuestStarSlotRequest<'_> {
+    type Response = UpdateGuestStarSlot;
+
+    const PATH: &'static str = "guest_star/slot";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![any(
+        twitch_oauth2::Scope::ChannelManageGuestStar,
+        twitch_oauth2::Scope::ModeratorManageGuestStar,
+    )];
+}
+
+impl RequestPatch for UpdateGuestStarSlotRequest<'_> {
+    type Body = helix::EmptyBody;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPatchError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                UpdateGuestStarSlot::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPatchError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req =
+        UpdateGuestStarSlotRequest::new("9321049", "9321049", "2KFRQbFtpmfyD3IevNRnCzOPRJI", "1")
+            .destination_slot_id("2");
+
+    dbg!(req
+        .create_request(Default::default(), "token", "clientid")
+        .unwrap());
+
+    // From twitch docs
+    let data = br#""#.to_vec();
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/guest_star/slot?broadcaster_id=9321049&moderator_id=9321049&session_id=2KFRQbFtpmfyD3IevNRnCzOPRJI&source_slot_id=1&destination_slot_id=2"
+    );
+
+    dbg!(UpdateGuestStarSlotRequest::parse_response(Some(req), &uri, http_response).unwrap());
+}
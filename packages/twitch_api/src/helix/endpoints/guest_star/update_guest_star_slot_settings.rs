@@ -0,0 +1,205 @@
+//! Allows a user to update slot settings for a particular guest within a Guest Star session, such as allowing the user to share audio or video within the call as a host.
+//! [`update-guest-star-slot-settings`](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot-settings)
+//!
+//! These settings are not broadcast to the session, they are only applied to the views of the host and Guest Star moderators.
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [UpdateGuestStarSlotSettingsRequest]
+//!
+//! To use this endpoint, construct an [`UpdateGuestStarSlotSettingsRequest`] with the [`UpdateGuestStarSlotSettingsRequest::new()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::guest_star::update_guest_star_slot_settings;
+//! let request = update_guest_star_slot_settings::UpdateGuestStarSlotSettingsRequest::new("1234", "5678", "2KFRQbFtpmfyD3IevNRnCzOPRJI", "1");
+//! ```
+//!
+//! ## Response: [UpdateGuestStarSlotSettings]
+//!
+//! Send the request to receive the response with [`HelixClient::req_patch()`](helix::HelixClient::req_patch).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, guest_star::update_guest_star_slot_settings};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = update_guest_star_slot_settings::UpdateGuestStarSlotSettingsRequest::new("1234", "5678", "2KFRQbFtpmfyD3IevNRnCzOPRJI", "1");
+//! let response: update_guest_star_slot_settings::UpdateGuestStarSlotSettings = client.req_patch(request, helix::EmptyBody, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestPatch::create_request)
+//! and parse the [`http::Response`] with [`UpdateGuestStarSlotSettingsRequest::parse_response(None, &request.get_uri(), response)`](UpdateGuestStarSlotSettingsRequest::parse_response)
+
+use super::*;
+use helix::RequestPatch;
+
+/// Query Parameters for [Update Guest Star Slot Settings](super::update_guest_star_slot_settings)
+///
+/// [`update-guest-star-slot-settings`](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot-settings)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct UpdateGuestStarSlotSettingsRequest<'a> {
+    /// The ID of the broadcaster running the Guest Star session.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the broadcaster or a user that has permission to moderate the broadcaster’s chat room. This ID must match the user ID in the user access token.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub moderator_id: Cow<'a, types::UserIdRef>,
+    /// The ID of the Guest Star session in which to update a slot’s settings.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub session_id: Cow<'a, types::GuestStarSessionIdRef>,
+    /// The slot assignment that has previously been assigned to a user.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub slot_id: Cow<'a, types::GuestStarSlotIdRef>,
+    /// Flag indicating whether the slot is allowed to share their audio with the rest of the session. If false, the slot will be muted in any views containing the slot.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub is_audio_enabled: Option<bool>,
+    /// Flag indicating whether the slot is allowed to share their video with the rest of the session. If false, the slot will have no video shared in any views containing the slot.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub is_video_enabled: Option<bool>,
+    /// Flag indicating whether the user assigned to this slot is visible/can be heard from any public subscriptions. Generally, this determines whether or not the slot is enabled in any broadcasting software integrations.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub is_live: Option<bool>,
+    /// Value from 0-100 that controls the audio volume for shared views containing the slot.
+    #[cfg_attr(feature = "typed-builder", builder(default, setter(into)))]
+    pub volume: Option<u8>,
+}
+
+impl<'a> UpdateGuestStarSlotSettingsRequest<'a> {
+    /// Update the settings of a slot as the specified moderator
+    pub fn new(
+        broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        moderator_id: impl types::IntoCow<'a, types::UserIdRef> + 'a,
+        session_id: impl types::IntoCow<'a, types::GuestStarSessionIdRef> + 'a,
+        slot_id: impl types::IntoCow<'a, types::GuestStarSlotIdRef> + 'a,
+    ) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+            moderator_id: moderator_id.into_cow(),
+            session_id: session_id.into_cow(),
+            slot_id: slot_id.into_cow(),
+            is_audio_enabled: None,
+            is_video_enabled: None,
+            is_live: None,
+            volume: None,
+        }
+    }
+
+    /// Allow or disallow the slot to share their audio
+    pub const fn is_audio_enabled(mut self, is_audio_enabled: bool) -> Self {
+        self.is_audio_enabled = Some(is_audio_enabled);
+        self
+    }
+
+    /// Allow or disallow the slot to share their video
+    pub const fn is_video_enabled(mut self, is_video_enabled: bool) -> Self {
+        self.is_video_enabled = Some(is_video_enabled);
+        self
+    }
+
+    /// Set whether the slot is live in the broadcasting software
+    pub const fn is_live(mut self, is_live: bool) -> Self {
+        self.is_live = Some(is_live);
+        self
+    }
+
+    /// Set the volume of the slot, from 0 to 100
+    pub const fn volume(mut self, volume: u8) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+}
+
+/// Return Values for [Update Guest Star Slot Settings](super::update_guest_star_slot_settings)
+///
+/// [`update-guest-star-slot-settings`](https://dev.twitch.tv/docs/api/reference#update-guest-star-slot-settings)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[non_exhaustive]
+pub enum UpdateGuestStarSlotSettings {
+    /// 204 - Successfully updated slot settings
+    Success,
+}
+
+impl Request for UpdateGuestStarSlotSettingsRequest<'_> {
+    type Response = UpdateGuestStarSlotSettings;
+
+    const PATH: &'static str = "guest_star/slot_settings";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![any(
+        twitch_oauth2::Scope::ChannelManageGuestStar,
+        twitch_oauth2::Scope::ModeratorManageGuestStar,
+    )];
+}
+
+impl RequestPatch for UpdateGuestStarSlotSettingsRequest<'_> {
+    type Body = helix::EmptyBody;
+
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestPatchError>
+    where
+        Self: Sized,
+    {
+        match status {
+            http::StatusCode::NO_CONTENT => Ok(helix::Response::with_data(
+                UpdateGuestStarSlotSettings::Success,
+                request,
+            )),
+            _ => Err(helix::HelixRequestPatchError::InvalidResponse {
+                reason: "unexpected status",
+                response: response.to_string(),
+                status,
+                uri: uri.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = UpdateGuestStarSlotSettingsRequest::new(
+        "9321049",
+        "9321049",
+        "2KFRQbFtpmfyD3IevNRnCzOPRJI",
+        "1",
+    )
+    .is_audio_enabled(false)
+    .is_video_enabled(true)
+    .is_live(true)
+    .volume(50);
+
+    dbg!(req
+        .create_request(Default::default(), "token", "clientid")
+        .unwrap());
+
+    // From twitch docs
+    let data = br#""#.to_vec();
+
+    let http_response = http::Response::builder().status(204).body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/guest_star/slot_settings?broadcaster_id=9321049&moderator_id=9321049&session_id=2KFRQbFtpmfyD3IevNRnCzOPRJI&slot_id=1&is_audio_enabled=false&is_video_enabled=true&is_live=true&volume=50"
+    );
+
+    dbg!(
+        UpdateGuestStarSlotSettingsRequest::parse_response(Some(req), &uri, http_response).unwrap()
+    );
+}
@@ -0,0 +1,214 @@
+//! Gets the status of a Hype Train for the specified broadcaster.
+//! [`get-hype-train-status`](https://dev.twitch.tv/docs/api/reference#get-hype-train-status)
+//!
+//! # Accessing the endpoint
+//!
+//! ## Request: [GetHypeTrainStatusRequest]
+//!
+//! To use this endpoint, construct a [`GetHypeTrainStatusRequest`] with the [`GetHypeTrainStatusRequest::broadcaster_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::hypetrain::get_hypetrain_status;
+//! let request =
+//!     get_hypetrain_status::GetHypeTrainStatusRequest::broadcaster_id("4321");
+//! ```
+//!
+//! ## Response: [HypeTrainStatus]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, hypetrain::get_hypetrain_status};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_hypetrain_status::GetHypeTrainStatusRequest::broadcaster_id("4321");
+//! let response: get_hypetrain_status::HypeTrainStatus = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetHypeTrainStatusRequest::parse_response(None, &request.get_uri(), response)`](GetHypeTrainStatusRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Hype Train Status](super::get_hypetrain_status)
+///
+/// [`get-hype-train-status`](https://dev.twitch.tv/docs/api/reference#get-hype-train-status)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetHypeTrainStatusRequest<'a> {
+    /// The User ID of the channel broadcaster. Must match the User ID in the Bearer token.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+}
+
+impl<'a> GetHypeTrainStatusRequest<'a> {
+    /// Get the Hype Train status of a broadcaster
+    pub fn broadcaster_id(broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+        }
+    }
+}
+
+/// Return Values for [Get Hype Train Status](super::get_hypetrain_status)
+///
+/// [`get-hype-train-status`](https://dev.twitch.tv/docs/api/reference#get-hype-train-status)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct HypeTrainStatus {
+    /// The Hype Train that is currently running. [`None`] if there is no active Hype Train.
+    pub current: Option<types::HypeTrain>,
+    /// The highest level and most points the channel has reached in a Hype Train.
+    pub all_time_high: Option<types::HypeTrainRecord>,
+    /// The highest level and most points the channel has reached in a shared Hype Train.
+    pub shared_all_time_high: Option<types::HypeTrainRecord>,
+}
+
+impl Request for GetHypeTrainStatusRequest<'_> {
+    type Response = HypeTrainStatus;
+
+    const PATH: &'static str = "hypetrain/status";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator =
+        twitch_oauth2::validator![twitch_oauth2::Scope::ChannelReadHypeTrain];
+}
+
+impl RequestGet for GetHypeTrainStatusRequest<'_> {
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, <Self as Request>::Response>, helix::HelixRequestGetError>
+    where
+        Self: Sized,
+    {
+        helix::parse_single_return(request, uri, response, status)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetHypeTrainStatusRequest::broadcaster_id("1337");
+
+    // From twitch docs
+    let data = br#"
+    {
+      "data": [
+        {
+          "current": {
+            "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+            "broadcaster_user_id": "1337",
+            "broadcaster_user_login": "cool_user",
+            "broadcaster_user_name": "Cool_User",
+            "level": 2,
+            "total": 700,
+            "progress": 200,
+            "goal": 1000,
+            "top_contributions": [
+              {
+                "user_id": "123",
+                "user_login": "pogchamp",
+                "user_name": "PogChamp",
+                "type": "bits",
+                "total": 50
+              },
+              {
+                "user_id": "456",
+                "user_login": "kappa",
+                "user_name": "Kappa",
+                "type": "subscription",
+                "total": 45
+              }
+            ],
+            "shared_train_participants": [
+              {
+                "broadcaster_user_id": "456",
+                "broadcaster_user_login": "pogchamp",
+                "broadcaster_user_name": "PogChamp"
+              },
+              {
+                "broadcaster_user_id": "321",
+                "broadcaster_user_login": "pogchamp",
+                "broadcaster_user_name": "PogChamp"
+              }
+            ],
+            "started_at": "2020-07-15T17:16:03.17106713Z",
+            "expires_at": "2020-07-15T17:16:11.17106713Z",
+            "type": "golden_kappa",
+            "is_shared_train": true
+          },
+          "all_time_high": {
+            "level": 6,
+            "total": 2850,
+            "achieved_at": "2020-04-24T20:12:21.003802269Z"
+          },
+          "shared_all_time_high": {
+            "level": 16,
+            "total": 23850,
+            "achieved_at": "2020-04-27T20:12:21.003802269Z"
+          }
+        }
+      ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/hypetrain/status?broadcaster_id=1337"
+    );
+
+    let status = GetHypeTrainStatusRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    let current = status.current.unwrap();
+    assert_eq!(current.type_, types::HypeTrainType::GoldenKappa);
+    assert_eq!(current.shared_train_participants.unwrap().len(), 2);
+    assert_eq!(status.all_time_high.unwrap().level, 6);
+    assert_eq!(status.shared_all_time_high.unwrap().total, 23850);
+}
+
+#[cfg(test)]
+#[test]
+fn test_request_no_train() {
+    use helix::*;
+    let req = GetHypeTrainStatusRequest::broadcaster_id("1337");
+
+    let data = br#"
+    {
+      "data": [
+        {
+          "current": null,
+          "all_time_high": null,
+          "shared_all_time_high": null
+        }
+      ]
+    }
+    "#
+    .to_vec();
+
+    let http_response = http::Response::builder().body(data).unwrap();
+
+    let uri = req.get_uri().unwrap();
+    let status = GetHypeTrainStatusRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert!(status.current.is_none());
+}
@@ -6,11 +6,12 @@
 //!
 //! <!-- generate with "cargo xtask overview" (with a nightly toolchain) -->
 //! <!-- BEGIN-OVERVIEW -->
-//! <details open><summary style="cursor: pointer">Hype Train 🟢 1/1</summary>
+//! <details open><summary style="cursor: pointer">Hype Train 🟢 2/2</summary>
 //!
 //! | Endpoint | Helper | Module |
 //! |---|---|---|
 //! | [Get Hype Train Events](https://dev.twitch.tv/docs/api/reference#get-hype-train-events) | - | [`get_hypetrain_events`] |
+//! | [Get Hype Train Status](https://dev.twitch.tv/docs/api/reference#get-hype-train-status) | - | [`get_hypetrain_status`] |
 //!
 //! </details>
 //!
@@ -23,9 +24,13 @@ use serde_derive::{Deserialize, Serialize};
 use std::borrow::Cow;
 
 pub mod get_hypetrain_events;
+pub mod get_hypetrain_status;
 
 #[doc(inline)]
+#[allow(deprecated)]
 pub use get_hypetrain_events::GetHypeTrainEventsRequest;
+#[doc(inline)]
+pub use get_hypetrain_status::{GetHypeTrainStatusRequest, HypeTrainStatus};
 
 /// Type of contribution to a hype train
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
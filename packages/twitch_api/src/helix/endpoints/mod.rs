@@ -1,14 +1,20 @@
+pub mod analytics;
 pub mod bits;
 pub mod ccls;
 pub mod channels;
 pub mod charity;
 pub mod chat;
 pub mod clips;
+pub mod entitlements;
 #[cfg(feature = "eventsub")]
 #[cfg_attr(nightly, doc(cfg(feature = "eventsub")))]
 pub mod eventsub;
+pub mod extensions;
 pub mod games;
 pub mod goals;
+#[cfg(feature = "beta")]
+#[cfg_attr(nightly, doc(cfg(feature = "beta")))]
+pub mod guest_star;
 pub mod hypetrain;
 pub mod moderation;
 pub mod points;
@@ -0,0 +1,476 @@
+//! Gets the broadcaster’s streaming schedule as an [iCalendar](https://datatracker.ietf.org/doc/html/rfc5545).
+//!
+//! [`get-channel-icalendar`](https://dev.twitch.tv/docs/api/reference#get-channel-icalendar)
+//!
+//! ## Notes
+//!
+//! The response is a `text/calendar` body and not JSON, it is returned as an [`ICalendar`].
+//! With feature `icalendar`, its events can be parsed into [`Segment`]s with [`ICalendar::segments()`].
+//!
+//! ## Request: [GetChannelICalendarRequest]
+//!
+//! To use this endpoint, construct a [`GetChannelICalendarRequest`] with the [`GetChannelICalendarRequest::broadcaster_id()`] method.
+//!
+//! ```rust
+//! use twitch_api::helix::schedule::get_channel_icalendar;
+//! let request =
+//!     get_channel_icalendar::GetChannelICalendarRequest::broadcaster_id(
+//!         "141981764",
+//!     );
+//! ```
+//!
+//! ## Response: [ICalendar]
+//!
+//! Send the request to receive the response with [`HelixClient::req_get()`](helix::HelixClient::req_get).
+//!
+//! ```rust, no_run
+//! use twitch_api::helix::{self, schedule::get_channel_icalendar};
+//! # use twitch_api::client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! # let client: helix::HelixClient<'static, client::DummyHttpClient> = helix::HelixClient::default();
+//! # let token = twitch_oauth2::AccessToken::new("validtoken".to_string());
+//! # let token = twitch_oauth2::UserToken::from_existing(&client, token, None, None).await?;
+//! let request = get_channel_icalendar::GetChannelICalendarRequest::broadcaster_id("141981764");
+//! let response: helix::schedule::ICalendar = client.req_get(request, &token).await?.data;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! You can also get the [`http::Request`] with [`request.create_request(&token, &client_id)`](helix::RequestGet::create_request)
+//! and parse the [`http::Response`] with [`GetChannelICalendarRequest::parse_response(None, &request.get_uri(), response)`](GetChannelICalendarRequest::parse_response)
+
+use super::*;
+use helix::RequestGet;
+
+/// Query Parameters for [Get Channel iCalendar](super::get_channel_icalendar)
+///
+/// [`get-channel-icalendar`](https://dev.twitch.tv/docs/api/reference#get-channel-icalendar)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "typed-builder", derive(typed_builder::TypedBuilder))]
+#[must_use]
+#[non_exhaustive]
+pub struct GetChannelICalendarRequest<'a> {
+    /// The ID of the broadcaster that owns the streaming schedule you want to get.
+    #[cfg_attr(feature = "typed-builder", builder(setter(into)))]
+    #[cfg_attr(feature = "deser_borrow", serde(borrow = "'a"))]
+    pub broadcaster_id: Cow<'a, types::UserIdRef>,
+}
+
+impl<'a> GetChannelICalendarRequest<'a> {
+    /// Get a broadcasters schedule as an iCalendar
+    pub fn broadcaster_id(broadcaster_id: impl types::IntoCow<'a, types::UserIdRef> + 'a) -> Self {
+        Self {
+            broadcaster_id: broadcaster_id.into_cow(),
+        }
+    }
+}
+
+/// Return Values for [Get Channel iCalendar](super::get_channel_icalendar)
+///
+/// [`get-channel-icalendar`](https://dev.twitch.tv/docs/api/reference#get-channel-icalendar)
+#[derive(PartialEq, Eq, Deserialize, Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct ICalendar(String);
+
+impl ICalendar {
+    /// The iCalendar as text
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    /// Take the iCalendar text
+    pub fn into_string(self) -> String { self.0 }
+
+    /// Parse the events (`VEVENT`s) of the iCalendar into [`Segment`]s
+    ///
+    /// Every event is parsed on its own, an event that can't be parsed doesn't fail the others.
+    ///
+    /// The iCalendar carries less information than [Get Channel Stream Schedule](super::get_channel_stream_schedule):
+    ///
+    /// * [`id`](Segment::id) is the event `UID`, which identifies the series of broadcasts but not a specific week
+    /// * [`category`](Segment::category) is always `None`, since events only name their category
+    /// * [`canceled_until`](Segment::canceled_until) is always `None`
+    ///
+    /// Events without a `DTEND` end after their `DURATION`, or if they have none, after one day for all-day events and at their start otherwise.
+    #[cfg(feature = "icalendar")]
+    #[cfg_attr(nightly, doc(cfg(feature = "icalendar")))]
+    pub fn segments(&self) -> Vec<Result<Segment, ICalendarError>> {
+        let mut segments = vec![];
+        // the properties of the current event, or the first line of it that could not be read
+        let mut event: Option<Result<Vec<Property>, ICalendarError>> = None;
+        for line in unfold(&self.0) {
+            match content_line(&line) {
+                Ok(property) if property.name == "BEGIN" && property.value == "VEVENT" => {
+                    event = Some(Ok(vec![]))
+                }
+                Ok(property) if property.name == "END" && property.value == "VEVENT" => {
+                    if let Some(properties) = event.take() {
+                        segments.push(properties.and_then(|properties| segment(&properties)));
+                    }
+                }
+                Ok(property) => {
+                    if let Some(Ok(properties)) = &mut event {
+                        properties.push(property);
+                    }
+                }
+                Err(e) => {
+                    if let Some(Ok(_)) = event {
+                        event = Some(Err(e));
+                    }
+                }
+            }
+        }
+        segments
+    }
+}
+
+impl From<String> for ICalendar {
+    fn from(s: String) -> Self { Self(s) }
+}
+
+impl std::fmt::Display for ICalendar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(&self.0) }
+}
+
+/// Errors when parsing an [`ICalendar`] into [`Segment`]s
+#[cfg(feature = "icalendar")]
+#[cfg_attr(nightly, doc(cfg(feature = "icalendar")))]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+pub enum ICalendarError {
+    /// line `{0}` is not a valid content line
+    InvalidLine(String),
+    /// event is missing property `{0}`
+    MissingProperty(&'static str),
+    /// date-time `{0}` could not be converted
+    InvalidDateTime(String, #[source] jiff::Error),
+    /// date-time `{0}` is not a valid timestamp
+    InvalidTimestamp(String, #[source] types::TimestampParseError),
+    /// duration `{0}` could not be converted
+    InvalidDuration(String, #[source] jiff::Error),
+}
+
+/// Unfold long lines, which continue with a leading space or tab
+#[cfg(feature = "icalendar")]
+fn unfold(calendar: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in calendar.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continued), Some(last)) => last.push_str(continued),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    lines
+}
+
+/// A content line of an iCalendar
+#[cfg(feature = "icalendar")]
+struct Property {
+    name: String,
+    /// Parameter names and values, without quotes
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+#[cfg(feature = "icalendar")]
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `true` if the value is a `DATE` and not a `DATE-TIME`
+    fn is_date(&self) -> bool {
+        self.param("VALUE")
+            .is_some_and(|value| value.eq_ignore_ascii_case("DATE"))
+            || !self.value.contains('T')
+    }
+}
+
+/// Split `s` at every `separator` that is not inside a quoted parameter value
+#[cfg(feature = "icalendar")]
+fn split_unquoted(s: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut quoted = false;
+    s.split(move |c| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        c == separator && !quoted
+    })
+}
+
+/// Split a content line into its name, parameters and value
+#[cfg(feature = "icalendar")]
+fn content_line(line: &str) -> Result<Property, ICalendarError> {
+    let invalid = || ICalendarError::InvalidLine(line.to_owned());
+    let head = split_unquoted(line, ':').next().ok_or_else(invalid)?;
+    // the value may contain colons itself
+    let value = line.get(head.len() + 1..).ok_or_else(invalid)?;
+    let mut head = split_unquoted(head, ';');
+    let name = head.next().ok_or_else(invalid)?;
+    let params = head
+        .map(|param| {
+            let (name, value) = param.split_once('=').ok_or_else(invalid)?;
+            Ok((
+                name.to_ascii_uppercase(),
+                value.trim_matches('"').to_owned(),
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Property {
+        name: name.to_ascii_uppercase(),
+        params,
+        value: value.to_owned(),
+    })
+}
+
+/// Make a [`Segment`] out of the properties of a `VEVENT`
+#[cfg(feature = "icalendar")]
+fn segment(properties: &[Property]) -> Result<Segment, ICalendarError> {
+    let property = |name: &'static str| {
+        properties
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or(ICalendarError::MissingProperty(name))
+    };
+    let start = property("DTSTART")?;
+    let start_time = datetime(start)?;
+    let end_time = match (property("DTEND"), property("DURATION")) {
+        (Ok(end), _) => datetime(end)?,
+        (_, Ok(duration)) => {
+            let invalid = |e| ICalendarError::InvalidDuration(duration.value.clone(), e);
+            let span: jiff::Span = duration.value.parse().map_err(invalid)?;
+            start_time.checked_add(span).map_err(invalid)?
+        }
+        // as specified by RFC 5545
+        _ if start.is_date() => start_time
+            .checked_add(jiff::Span::new().days(1))
+            .map_err(|e| ICalendarError::InvalidDateTime(start.value.clone(), e))?,
+        _ => start_time.clone(),
+    };
+    Ok(Segment {
+        id: property("UID")?.value.as_str().into(),
+        start_time: timestamp(&start_time)?,
+        end_time: timestamp(&end_time)?,
+        title: unescape(&property("SUMMARY")?.value),
+        canceled_until: None,
+        category: None,
+        is_recurring: property("RRULE").is_ok(),
+    })
+}
+
+/// Convert a `DATE-TIME` or `DATE` value, either in UTC or in the time zone given by `TZID`
+#[cfg(feature = "icalendar")]
+fn datetime(property: &Property) -> Result<jiff::Zoned, ICalendarError> {
+    let value = property.value.as_str();
+    let invalid = |e| ICalendarError::InvalidDateTime(value.to_owned(), e);
+    let tz = match property.param("TZID") {
+        // Twitch prefixes the time zone with a slash, e.g. `/America/New_York`
+        Some(tzid) => jiff::tz::TimeZone::get(tzid.trim_start_matches('/')).map_err(invalid)?,
+        None => jiff::tz::TimeZone::UTC,
+    };
+    let datetime = if property.is_date() {
+        jiff::civil::Date::strptime("%Y%m%d", value)
+            .map_err(invalid)?
+            .to_datetime(jiff::civil::Time::midnight())
+    } else {
+        jiff::civil::DateTime::strptime("%Y%m%dT%H%M%S", value.trim_end_matches('Z'))
+            .map_err(invalid)?
+    };
+    datetime.to_zoned(tz).map_err(invalid)
+}
+
+/// Convert a date-time into a timestamp in UTC
+#[cfg(feature = "icalendar")]
+fn timestamp(datetime: &jiff::Zoned) -> Result<types::Timestamp, ICalendarError> {
+    let timestamp = datetime.timestamp().to_string();
+    types::Timestamp::new(timestamp.clone())
+        .map_err(|e| ICalendarError::InvalidTimestamp(timestamp, e))
+}
+
+/// Unescape a `TEXT` value
+#[cfg(feature = "icalendar")]
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') | Some('N') => text.push('\n'),
+                Some(escaped) => text.push(escaped),
+                None => text.push('\\'),
+            },
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+impl Request for GetChannelICalendarRequest<'_> {
+    type Response = ICalendar;
+
+    const PATH: &'static str = "schedule/icalendar";
+    #[cfg(feature = "twitch_oauth2")]
+    const SCOPE: twitch_oauth2::Validator = twitch_oauth2::validator![];
+}
+
+impl RequestGet for GetChannelICalendarRequest<'_> {
+    fn parse_inner_response(
+        request: Option<Self>,
+        uri: &http::Uri,
+        response: &str,
+        status: http::StatusCode,
+    ) -> Result<helix::Response<Self, Self::Response>, helix::HelixRequestGetError>
+    where
+        Self: Sized,
+    {
+        helix::parse_text_return(request, uri, response, status)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request() {
+    use helix::*;
+    let req = GetChannelICalendarRequest::broadcaster_id("141981764");
+
+    // From twitch docs
+    let data = "BEGIN:VCALENDAR\r\n\
+        PRODID:-//twitch.tv//StreamSchedule//1.0\r\n\
+        VERSION:2.0\r\n\
+        CALSCALE:GREGORIAN\r\n\
+        REFRESH-INTERVAL;VALUE=DURATION:PT1H\r\n\
+        NAME:TwitchDev\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:e4acc724-371f-402c-81ca-23ada79759d4\r\n\
+        DTSTAMP:20210323T040131Z\r\n\
+        DTSTART;TZID=/America/New_York:20210701T140000\r\n\
+        DTEND;TZID=/America/New_York:20210701T150000\r\n\
+        SUMMARY:TwitchDev Monthly Update // July 1\\, 2021\r\n\
+        DESCRIPTION:Science & Technology.\r\n\
+        CATEGORIES:Science & Technology\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:6ef8d8e4-6a8b-4c9a-9a2a-2f4b1e1c5b1d\r\n\
+        DTSTAMP:20210323T040131Z\r\n\
+        DTSTART:20210705T170000Z\r\n\
+        DTEND:20210705T190000Z\r\n\
+        SUMMARY:Weekly stream with a very long title that is folded onto the \r\n \
+        next line\r\n\
+        RRULE:FREQ=WEEKLY\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n"
+        .as_bytes()
+        .to_vec();
+
+    let http_response = http::Response::builder()
+        .header("Content-Type", "text/calendar")
+        .body(data)
+        .unwrap();
+
+    let uri = req.get_uri().unwrap();
+    assert_eq!(
+        uri.to_string(),
+        "https://api.twitch.tv/helix/schedule/icalendar?broadcaster_id=141981764"
+    );
+
+    let calendar = GetChannelICalendarRequest::parse_response(Some(req), &uri, http_response)
+        .unwrap()
+        .data;
+    assert!(calendar.as_str().starts_with("BEGIN:VCALENDAR"));
+
+    #[cfg(feature = "icalendar")]
+    {
+        let segments = calendar
+            .segments()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start_time.as_str(), "2021-07-01T18:00:00Z");
+        assert_eq!(segments[0].end_time.as_str(), "2021-07-01T19:00:00Z");
+        assert_eq!(
+            segments[0].title,
+            "TwitchDev Monthly Update // July 1, 2021"
+        );
+        assert!(!segments[0].is_recurring);
+        assert_eq!(segments[1].start_time.as_str(), "2021-07-05T17:00:00Z");
+        assert_eq!(
+            segments[1].title,
+            "Weekly stream with a very long title that is folded onto the next line"
+        );
+        assert!(segments[1].is_recurring);
+    }
+}
+
+#[cfg(all(test, feature = "icalendar"))]
+#[test]
+fn parse_segments() {
+    let calendar = ICalendar::from(
+        "BEGIN:VCALENDAR\r\n\
+        VERSION:2.0\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:all-day\r\n\
+        DTSTART;VALUE=DATE:20210701\r\n\
+        SUMMARY:Subathon\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:quoted\r\n\
+        DTSTART;TZID=\"/Europe/Berlin\";X-NOTE=\"starts: at noon\":20210701T120000\r\n\
+        DURATION:PT1H30M\r\n\
+        SUMMARY:Colons: in the title\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:no-end\r\n\
+        DTSTART:20210705T170000Z\r\n\
+        SUMMARY:Quick stream\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:unknown-zone\r\n\
+        DTSTART;TZID=/Nowhere/Special:20210701T120000\r\n\
+        SUMMARY:Lost\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:broken-line\r\n\
+        not a content line\r\n\
+        DTSTART:20210705T170000Z\r\n\
+        SUMMARY:Broken\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:after\r\n\
+        DTSTART:20210706T170000Z\r\n\
+        DTEND:20210706T180000Z\r\n\
+        SUMMARY:Still parsed\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n"
+            .to_owned(),
+    );
+
+    let segments = calendar.segments();
+    assert_eq!(segments.len(), 6);
+
+    let all_day = segments[0].as_ref().unwrap();
+    assert_eq!(all_day.start_time.as_str(), "2021-07-01T00:00:00Z");
+    assert_eq!(all_day.end_time.as_str(), "2021-07-02T00:00:00Z");
+
+    let quoted = segments[1].as_ref().unwrap();
+    assert_eq!(quoted.start_time.as_str(), "2021-07-01T10:00:00Z");
+    assert_eq!(quoted.end_time.as_str(), "2021-07-01T11:30:00Z");
+    assert_eq!(quoted.title, "Colons: in the title");
+
+    let no_end = segments[2].as_ref().unwrap();
+    assert_eq!(no_end.start_time.as_str(), "2021-07-05T17:00:00Z");
+    assert_eq!(no_end.end_time.as_str(), "2021-07-05T17:00:00Z");
+
+    assert!(matches!(
+        segments[3],
+        Err(ICalendarError::InvalidDateTime(..))
+    ));
+    assert!(matches!(segments[4], Err(ICalendarError::InvalidLine(..))));
+
+    let after = segments[5].as_ref().unwrap();
+    assert_eq!(after.id.as_str(), "after");
+    assert_eq!(after.end_time.as_str(), "2021-07-06T18:00:00Z");
+}
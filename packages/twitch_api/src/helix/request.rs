@@ -45,6 +45,8 @@ pub trait RequestPost: Request {
     type Body: HelixRequestBody;
 
     /// Create a [`http::Request`] from this [`Request`] in your client
+    ///
+    /// The `token` is either an OAuth access token or, for endpoints authorized by an extension, a [signed JWT](crate::helix::jwt).
     fn create_request(
         &self,
         body: Self::Body,
@@ -285,6 +287,8 @@ pub trait RequestPut: Request {
     type Body: HelixRequestBody;
 
     /// Create a [`http::Request`] from this [`Request`] in your client
+    ///
+    /// The `token` is either an OAuth access token or, for endpoints authorized by an extension, a [signed JWT](crate::helix::jwt).
     fn create_request(
         &self,
         body: Self::Body,
@@ -359,6 +363,8 @@ pub trait RequestPut: Request {
 /// Helix endpoint GETs information
 pub trait RequestGet: Request {
     /// Create a [`http::Request`] from this [`Request`] in your client
+    ///
+    /// The `token` is either an OAuth access token or, for endpoints authorized by an extension, a [signed JWT](crate::helix::jwt).
     fn create_request(
         &self,
         token: &str,
@@ -469,6 +475,31 @@ where
     Ok(Response::with_data(resp, request))
 }
 
+/// Parses a response where Helix responds with a body that is not JSON.
+///
+/// An example response is a `text/calendar` body, which is returned as is.
+pub(crate) fn parse_text_return<T, E>(
+    request: Option<T>,
+    uri: &http::Uri,
+    response: &str,
+    status: http::StatusCode,
+) -> Result<Response<T, T::Response>, E>
+where
+    T: Request,
+    T::Response: From<String>,
+    E: errors::HelixRequestError,
+{
+    match status {
+        http::StatusCode::OK => Ok(Response::with_data(response.to_owned().into(), request)),
+        _ => Err(E::invalid_response(
+            "unexpected status code",
+            response.to_string(),
+            status,
+            uri.clone(),
+        )),
+    }
+}
+
 /// A helper type to parse responses that contain either zero or one element in an array
 pub(crate) struct ZeroOrOne<T>(pub Option<T>);
 
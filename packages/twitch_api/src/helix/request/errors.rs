@@ -36,6 +36,14 @@ pub enum ClientRequestError<RE: std::error::Error + Send + Sync + 'static> {
     /// Got error from DELETE response
     #[error(transparent)]
     HelixRequestDeleteError(#[from] HelixRequestDeleteError),
+    /// Token is missing scopes required by the endpoint
+    #[error("token is missing scopes required for `{path}`, needs {required:?}")]
+    MissingScope {
+        /// Path of the endpoint
+        path: &'static str,
+        /// Scopes required by the endpoint
+        required: twitch_oauth2::Validator,
+    },
     /// Custom error
     #[error("{0}")]
     Custom(std::borrow::Cow<'static, str>),
@@ -2,7 +2,7 @@
 //!
 //! See https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#authorization-code-grant-flow
 //!
-//! See also the `device_code_flow` example for possibly easier integration.
+//! See also the `device_code_flow` example for possibly easier integration, or the `loopback_flow` example to receive the redirect on `localhost`.
 
 use anyhow::Context;
 use twitch_oauth2::tokens::UserTokenBuilder;
@@ -0,0 +1,57 @@
+//! This is an example of the Authorization code grant flow using `twitch_oauth2`, receiving the redirect on `localhost`
+//!
+//! See https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#authorization-code-grant-flow
+//!
+//! Add `http://localhost:3000/twitch/register` as a redirect URL of your application in the developer console.
+
+use anyhow::Context;
+use twitch_oauth2::tokens::{LoopbackServer, UserTokenBuilder};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _ = dotenv::dotenv(); // Eat error
+    let mut args = std::env::args().skip(1);
+
+    // Setup the http client to use with the library.
+    let reqwest = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+
+    // Grab the client id, convert to a `ClientId` with the `new` method.
+    let client_id = get_env_or_arg("TWITCH_CLIENT_ID", &mut args)
+        .map(twitch_oauth2::ClientId::new)
+        .context("Please set env: TWITCH_CLIENT_ID or pass as first argument")?;
+
+    // Grab the client secret, convert to a `ClientSecret` with the `new` method.
+    let client_secret = get_env_or_arg("TWITCH_CLIENT_SECRET", &mut args)
+        .map(twitch_oauth2::ClientSecret::new)
+        .context("Please set env: TWITCH_CLIENT_SECRET or pass as second argument")?;
+
+    let redirect_url = twitch_oauth2::url::Url::parse("http://localhost:3000/twitch/register")?;
+
+    // Create the builder!
+    let mut builder =
+        UserTokenBuilder::new(client_id, client_secret, redirect_url).force_verify(true);
+
+    // Listen for the redirect before sending the user away.
+    let server = LoopbackServer::bind(&builder).await?;
+
+    // Generate the URL, this is the url that the user should visit to authenticate.
+    let (url, _) = builder.generate_url();
+
+    println!("Go to this page: {}", url);
+
+    // Wait for the user to be redirected back, for at most 5 minutes.
+    let token = tokio::time::timeout(
+        std::time::Duration::from_secs(5 * 60),
+        server.get_user_token(builder, &reqwest),
+    )
+    .await
+    .context("timed out waiting for the redirect")??;
+    println!("Got token: {:?}", token);
+    Ok(())
+}
+
+fn get_env_or_arg(env: &str, args: &mut impl Iterator<Item = String>) -> Option<String> {
+    std::env::var(env).ok().or_else(|| args.next())
+}
@@ -10,7 +10,7 @@ pub static TWITCH_OAUTH2_USER_AGENT: &str =
     concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
 /// A boxed future, mimics `futures::future::BoxFuture`
-type BoxedFuture<'a, T> = std::pin::Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+pub type BoxedFuture<'a, T> = std::pin::Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 /// A client that can do OAUTH2 requests
 pub trait Client: Sync + Send {
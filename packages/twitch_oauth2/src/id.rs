@@ -11,6 +11,7 @@ use std::time::Duration;
 /// * [`UserTokenBuilder::get_user_token_request`](crate::tokens::UserTokenBuilder::get_user_token_request)
 /// * [`AppAccessToken::::get_app_access_token_request`](crate::tokens::AppAccessToken::get_app_access_token_request)
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct TwitchTokenResponse {
     /// Access token
     pub access_token: AccessToken,
@@ -24,6 +25,11 @@ pub struct TwitchTokenResponse {
     #[serde(rename = "scope", deserialize_with = "scope::deserialize")]
     #[serde(default)]
     pub scopes: Option<Vec<crate::Scope>>,
+    /// OpenID Connect ID token, returned when the [`openid`](crate::Scope::OpenId) scope is requested
+    ///
+    /// Verify it and get its claims with [`IdTokenVerifier`](crate::tokens::IdTokenVerifier), enabled with feature `oidc`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
 }
 
 impl TwitchTokenResponse {
@@ -45,6 +51,9 @@ impl TwitchTokenResponse {
 
     /// Get the scopes from this response
     pub fn scopes(&self) -> Option<&[crate::Scope]> { self.scopes.as_deref() }
+
+    /// Get the OpenID Connect ID token from this response
+    pub fn id_token(&self) -> Option<&str> { self.id_token.as_deref() }
 }
 
 /// Twitch's representation of the oauth flow for errors
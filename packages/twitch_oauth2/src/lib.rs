@@ -41,11 +41,24 @@
 //! Things like [`UserTokenBuilder`] can be used to create a token from scratch, via the [OAuth authorization code flow](https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#authorization-code-grant-flow)
 //! You can also use the newer [OAuth device code flow](https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#device-code-grant-flow) with [`DeviceUserTokenBuilder`].
 //!
+//! With feature `loopback`, [`LoopbackServer`](tokens::LoopbackServer) receives the redirect of the authorization code flow on `localhost` and gets the token, which is useful for command line tools.
+//!
 //! ## App access token
 //!
 //! Similar to [`UserToken`], a token with authorization as the twitch application can be created with
 //! [`AppAccessToken::get_app_access_token`].
 //!
+//! ## Extension JWTs
+//!
+//! Extensions authenticate with JWTs signed by the shared secret of the extension instead of OAuth tokens.
+//! With feature `jwt`, [`ExtensionJwt`](tokens::ExtensionJwt) mints these tokens and verifies tokens sent by the extension frontend.
+//!
+//! ## OpenID Connect
+//!
+//! Request the [`openid`](Scope::OpenId) scope with [`UserTokenBuilder`] to get an ID token in [`UserToken::id_token`], or [`TwitchTokenResponse::id_token`](id::TwitchTokenResponse::id_token) when exchanging the code yourself.
+//! Claims about the user are requested with [`UserTokenBuilder::set_claims`], and can also be fetched with [`AccessTokenRef::userinfo`].
+//! With feature `oidc`, [`IdTokenVerifier`](tokens::IdTokenVerifier) verifies ID tokens against the key set of Twitch.
+//!
 //! ## HTTP Requests
 //!
 //! To enable client features with a supported http library, enable the http library feature in `twitch_oauth2`, like `twitch_oauth2 = { features = ["reqwest"], version = "0.15.1" }`.
@@ -59,16 +72,29 @@
 pub mod client;
 pub mod id;
 pub mod scopes;
+pub mod store;
 pub mod tokens;
 pub mod types;
 
 use http::StatusCode;
 use id::TwitchTokenErrorResponse;
 #[cfg(feature = "client")]
-use tokens::errors::{RefreshTokenError, RevokeTokenError, ValidationError};
+use tokens::errors::{RefreshTokenError, RevokeTokenError, UserInfoError, ValidationError};
 
 #[doc(inline)]
 pub use scopes::{Scope, Validator};
+#[cfg(feature = "jwt")]
+#[doc(inline)]
+pub use tokens::ExtensionJwt;
+#[cfg(feature = "client")]
+#[doc(inline)]
+pub use tokens::RefreshingToken;
+#[cfg(feature = "client")]
+#[doc(inline)]
+pub use tokens::TokenRegistry;
+#[cfg(feature = "client")]
+#[doc(inline)]
+pub use tokens::TokenValidator;
 #[doc(inline)]
 pub use tokens::{
     AppAccessToken, DeviceUserTokenBuilder, ImplicitUserTokenBuilder, TwitchToken, UserToken,
@@ -160,6 +186,28 @@ pub static REVOKE_URL: once_cell::sync::Lazy<url::Url> =
         TWITCH_OAUTH2_URL.to_string() + "revoke"
     },);
 
+/// Key set URL (`https://id.twitch.tv/oauth2/keys`) for `id.twitch.tv`
+///
+/// Can be overridden when feature `mock_api` is enabled with environment variable `TWITCH_OAUTH2_URL` to set the root path, or with `TWITCH_OAUTH2_JWKS_URL` to override the base (`https://id.twitch.tv/oauth2/`) url.
+///
+/// # Examples
+///
+/// Set the environment variable `TWITCH_OAUTH2_URL` to `http://localhost:8080/auth/` to use [`twitch-cli` mock](https://github.com/twitchdev/twitch-cli/blob/main/docs/mock-api.md) endpoints.
+pub static JWKS_URL: once_cell::sync::Lazy<url::Url> = mock_env_url!("TWITCH_OAUTH2_JWKS_URL", {
+    TWITCH_OAUTH2_URL.to_string() + "keys"
+},);
+/// UserInfo URL (`https://id.twitch.tv/oauth2/userinfo`) for `id.twitch.tv`
+///
+/// Can be overridden when feature `mock_api` is enabled with environment variable `TWITCH_OAUTH2_URL` to set the root path, or with `TWITCH_OAUTH2_USERINFO_URL` to override the base (`https://id.twitch.tv/oauth2/`) url.
+///
+/// # Examples
+///
+/// Set the environment variable `TWITCH_OAUTH2_URL` to `http://localhost:8080/auth/` to use [`twitch-cli` mock](https://github.com/twitchdev/twitch-cli/blob/main/docs/mock-api.md) endpoints.
+pub static USERINFO_URL: once_cell::sync::Lazy<url::Url> =
+    mock_env_url!("TWITCH_OAUTH2_USERINFO_URL", {
+        TWITCH_OAUTH2_URL.to_string() + "userinfo"
+    },);
+
 impl AccessTokenRef {
     /// Get the request needed to validate this token.
     ///
@@ -205,6 +253,51 @@ impl AccessTokenRef {
         ValidatedToken::from_response(&resp).map_err(|v| v.into_other())
     }
 
+    /// Get the request needed to get the claims about the user of this token.
+    ///
+    /// The token must have the [`openid`](Scope::OpenId) scope. Parse the response from this endpoint with [UserInfo::from_response](crate::tokens::UserInfo::from_response)
+    pub fn userinfo_request(&self) -> http::Request<Vec<u8>> {
+        use http::{header::AUTHORIZATION, HeaderMap, Method};
+
+        let auth_header = format!("Bearer {}", self.secret());
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            auth_header
+                .parse()
+                .expect("Failed to parse header for userinfo"),
+        );
+
+        crate::construct_request::<&[(String, String)], _, _>(
+            &crate::USERINFO_URL,
+            &[],
+            headers,
+            Method::GET,
+            vec![],
+        )
+    }
+
+    /// Get the claims about the user of this token.
+    ///
+    /// Only the claims requested with [`ClaimsRequest::userinfo`](crate::tokens::ClaimsRequest::userinfo) are returned,
+    /// see <https://dev.twitch.tv/docs/authentication/getting-tokens-oidc/#getting-claims-information-from-an-access-token>
+    #[cfg(feature = "client")]
+    pub async fn userinfo<C>(
+        &self,
+        client: &C,
+    ) -> Result<tokens::UserInfo, UserInfoError<<C as Client>::Error>>
+    where
+        C: Client,
+    {
+        let req = self.userinfo_request();
+
+        let resp = client.req(req).await.map_err(UserInfoError::RequestError)?;
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            return Err(UserInfoError::NotAuthorized);
+        }
+        Ok(tokens::UserInfo::from_response(&resp)?)
+    }
+
     /// Get the request needed to revoke this token.
     pub fn revoke_token_request(&self, client_id: &ClientId) -> http::Request<Vec<u8>> {
         use http::{HeaderMap, Method};
@@ -217,6 +217,7 @@ scope_impls!(
     ModeratorReadUnbanRequests,     scope: "moderator:read:unban_requests",     doc: "View a broadcaster’s unban requests.";
     ModeratorReadVips,              scope: "moderator:read:vips",               doc: "Read the list of VIPs in channels where you have the moderator role.";
     ModeratorReadWarnings,          scope: "moderator:read:warnings",           doc: "View a broadcaster’s chat warnings.";
+    OpenId,                         scope: "openid",                            doc: "Use [OpenID Connect](https://dev.twitch.tv/docs/authentication/getting-tokens-oidc/) to get an ID token and claims about the user.";
     UserBot,                        scope: "user:bot",                          doc: "Allows client’s bot to act as this user.";
     UserEdit,                       scope: "user:edit",                         doc: "Manage a user object.";
     UserEditBroadcast,              scope: "user:edit:broadcast",               doc: "Edit your channel's broadcast configuration, including extension configuration. (This scope implies user:read:broadcast capability.)";
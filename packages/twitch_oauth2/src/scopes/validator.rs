@@ -68,6 +68,62 @@ impl Validator {
         }
     }
 
+    /// Get the scopes that need to be added to `scopes` for this validator to match.
+    ///
+    /// Returns an empty list if the scopes already match, and [`None`] if adding scopes can't make them match, like when a scope inside `not(...)` is present.
+    /// For `any(...)`, the alternative that needs the fewest scopes is picked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twitch_oauth2::{validator, Scope, Validator};
+    ///
+    /// const CHAT: Validator = validator!(Scope::ChatEdit, Scope::ChatRead);
+    /// const MODERATION: Validator = validator!(any(
+    ///     Scope::ModeratorReadChatters,
+    ///     Scope::ModeratorManageBannedUsers
+    /// ));
+    /// // combine the validators of all requests a feature needs
+    /// let feature: Validator = [CHAT, MODERATION].into_iter().collect();
+    ///
+    /// let scopes: &[Scope] = &[Scope::ChatRead];
+    /// assert_eq!(
+    ///     feature.missing(scopes),
+    ///     Some(vec![Scope::ChatEdit, Scope::ModeratorReadChatters])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn missing(&self, scopes: &[Scope]) -> Option<Vec<Scope>> {
+        let missing = self.missing_unchecked(scopes)?;
+        // the scopes for each validator in `all(...)` are picked independently, make sure they don't conflict
+        let mut all = scopes.to_vec();
+        all.extend(missing.iter().cloned());
+        self.matches(&all).then_some(missing)
+    }
+
+    fn missing_unchecked(&self, scopes: &[Scope]) -> Option<Vec<Scope>> {
+        match &self {
+            _ if self.matches(scopes) => Some(vec![]),
+            Validator::Scope(scope) => Some(vec![scope.clone()]),
+            Validator::All(Sized(validators)) => {
+                let mut missing: Vec<Scope> = vec![];
+                for validator in validators.iter() {
+                    for scope in validator.missing_unchecked(scopes)? {
+                        if !missing.contains(&scope) {
+                            missing.push(scope);
+                        }
+                    }
+                }
+                Some(missing)
+            }
+            Validator::Any(Sized(validators)) => validators
+                .iter()
+                .filter_map(|v| v.missing_unchecked(scopes))
+                .min_by_key(Vec::len),
+            Validator::Not(_) => None,
+        }
+    }
+
     /// Create a [Validator] which matches if the scope is present.
     pub const fn scope(scope: Scope) -> Self { Validator::Scope(scope) }
 
@@ -105,6 +161,13 @@ impl From<Scope> for Validator {
     fn from(scope: Scope) -> Self { Validator::scope(scope) }
 }
 
+/// Combine validators into one which matches if all of them match
+impl FromIterator<Validator> for Validator {
+    fn from_iter<I: IntoIterator<Item = Validator>>(iter: I) -> Self {
+        Validator::All(Sized(Cow::Owned(iter.into_iter().collect())))
+    }
+}
+
 /// A [validator](Validator) is a way to check if a slice of scopes matches a predicate.
 ///
 /// Uses a functional style to compose the predicate. Can be used in const context.
@@ -361,4 +424,42 @@ mod tests {
         assert!(VALIDATOR.matches(scopes));
         assert!(!VALIDATOR.matches(scopes_1));
     }
+
+    #[test]
+    fn missing() {
+        const VALIDATOR: Validator = validator!(
+            Scope::ChatEdit,
+            any(Scope::ChatRead, all(Scope::ModerationRead, Scope::UserEdit))
+        );
+        assert_eq!(
+            VALIDATOR.missing(&[Scope::ChatEdit, Scope::ChatRead]),
+            Some(vec![])
+        );
+        assert_eq!(
+            VALIDATOR.missing(&[Scope::ModerationRead]),
+            Some(vec![Scope::ChatEdit, Scope::ChatRead])
+        );
+        assert_eq!(
+            VALIDATOR.missing(&[]),
+            Some(vec![Scope::ChatEdit, Scope::ChatRead])
+        );
+
+        const NOT: Validator = validator!(Scope::ChatRead, not(Scope::ChatEdit));
+        assert_eq!(NOT.missing(&[]), Some(vec![Scope::ChatRead]));
+        assert_eq!(NOT.missing(&[Scope::ChatEdit]), None);
+        const CONFLICT: Validator = validator!(Scope::ChatEdit, not(Scope::ChatEdit));
+        assert_eq!(CONFLICT.missing(&[]), None);
+    }
+
+    #[test]
+    fn collect() {
+        const CHAT: Validator = validator!(Scope::ChatEdit, Scope::ChatRead);
+        const USER: Validator = validator!(Scope::UserEdit);
+        let validator: Validator = [CHAT, USER].into_iter().collect();
+        assert!(validator.matches(&[Scope::ChatEdit, Scope::ChatRead, Scope::UserEdit]));
+        assert_eq!(
+            validator.missing(&[Scope::ChatRead]),
+            Some(vec![Scope::ChatEdit, Scope::UserEdit])
+        );
+    }
 }
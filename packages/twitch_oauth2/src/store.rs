@@ -0,0 +1,329 @@
+//! Persistence for user tokens
+//!
+//! A [`TokenStore`] saves the access and refresh token of users, so that a [`UserToken`](crate::UserToken) can be restored after a restart with [`UserToken::from_store`](crate::UserToken::from_store).
+//! A user token with a store attached saves itself after every refresh, so the rotated refresh token is never lost.
+//! If saving fails, the refresh still succeeds and the error is passed to [`UserToken::on_store_error`](crate::UserToken::on_store_error).
+//!
+//! App access tokens are not stored. The client credentials flow doesn't issue refresh tokens,
+//! so a new [`AppAccessToken`](crate::AppAccessToken) can always be requested with the client secret and there is no rotated token to lose.
+//!
+//! This crate ships [`MemoryTokenStore`] and [`FileTokenStore`], other backends can implement [`TokenStore`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use twitch_oauth2::{store::FileTokenStore, UserToken};
+//! # #[tokio::main]
+//! # async fn run() -> Result<(), Box<dyn std::error::Error + 'static>>{
+//! let client = reqwest::Client::builder()
+//!     .redirect(reqwest::redirect::Policy::none())
+//!     .build()?;
+//! let store = Arc::new(FileTokenStore::new("tokens.json"));
+//! let token = UserToken::from_store(&client, store, "1234".into(), None).await?;
+//! # Ok(())}
+//! # fn main() {run();}
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde_derive::{Deserialize, Serialize};
+use twitch_types::{UserId, UserIdRef};
+
+use crate::types::{AccessToken, ClientId, RefreshToken};
+
+/// A token saved in a [`TokenStore`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct StoredToken {
+    /// The access token
+    pub access_token: AccessToken,
+    /// The refresh token
+    pub refresh_token: Option<RefreshToken>,
+    /// Client ID the token was issued to
+    pub client_id: ClientId,
+}
+
+impl StoredToken {
+    /// Create a new stored token
+    pub fn new(
+        access_token: AccessToken,
+        refresh_token: Option<RefreshToken>,
+        client_id: ClientId,
+    ) -> Self {
+        Self {
+            access_token,
+            refresh_token,
+            client_id,
+        }
+    }
+}
+
+/// Errors from a [`TokenStore`]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+pub enum TokenStoreError {
+    /// could not access token storage
+    Io(#[from] std::io::Error),
+    /// could not (de)serialize tokens
+    Json(#[from] serde_json::Error),
+    /// token store failed
+    Other(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+/// Storage for user tokens, keyed by user id
+pub trait TokenStore: Send + Sync {
+    /// Load the token of a user, `None` if there is no token saved for the user
+    fn load(&self, user_id: &UserIdRef) -> Result<Option<StoredToken>, TokenStoreError>;
+    /// Save the token of a user, replacing any previous token
+    fn save(&self, user_id: &UserIdRef, token: &StoredToken) -> Result<(), TokenStoreError>;
+    /// Delete the token of a user
+    fn delete(&self, user_id: &UserIdRef) -> Result<(), TokenStoreError>;
+}
+
+/// A [`TokenStore`] that keeps tokens in memory
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: Mutex<HashMap<UserId, StoredToken>>,
+}
+
+impl MemoryTokenStore {
+    /// Create a new empty store
+    pub fn new() -> Self { Self::default() }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self, user_id: &UserIdRef) -> Result<Option<StoredToken>, TokenStoreError> {
+        Ok(self
+            .tokens
+            .lock()
+            .expect("token store should not be poisoned")
+            .get(user_id)
+            .cloned())
+    }
+
+    fn save(&self, user_id: &UserIdRef, token: &StoredToken) -> Result<(), TokenStoreError> {
+        self.tokens
+            .lock()
+            .expect("token store should not be poisoned")
+            .insert(user_id.to_owned(), token.clone());
+        Ok(())
+    }
+
+    fn delete(&self, user_id: &UserIdRef) -> Result<(), TokenStoreError> {
+        self.tokens
+            .lock()
+            .expect("token store should not be poisoned")
+            .remove(user_id);
+        Ok(())
+    }
+}
+
+/// A [`TokenStore`] that keeps tokens in a JSON file
+///
+/// The file is replaced atomically on every change, so a crash while saving can not corrupt it.
+///
+/// # Notes
+///
+/// The tokens are saved in plain text. On unix, the file is created readable only by its owner.
+#[derive(Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileTokenStore {
+    /// Create a store saving to the file at `path`. The file is created when the first token is saved.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the file tokens are saved in
+    pub fn path(&self) -> &std::path::Path { &self.path }
+
+    fn read(&self) -> Result<BTreeMap<UserId, StoredToken>, TokenStoreError> {
+        match std::fs::read(&self.path) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, tokens: &BTreeMap<UserId, StoredToken>) -> Result<(), TokenStoreError> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let contents = serde_json::to_vec_pretty(tokens)?;
+        {
+            use std::io::Write;
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            // the file contains secrets, only the owner may read it
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let mut file = options.open(&tmp)?;
+            #[cfg(unix)]
+            file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+            file.write_all(&contents)?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    fn modify(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<UserId, StoredToken>),
+    ) -> Result<(), TokenStoreError> {
+        let _guard = self
+            .lock
+            .lock()
+            .expect("token store should not be poisoned");
+        let mut tokens = self.read()?;
+        f(&mut tokens);
+        self.write(&tokens)
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self, user_id: &UserIdRef) -> Result<Option<StoredToken>, TokenStoreError> {
+        let _guard = self
+            .lock
+            .lock()
+            .expect("token store should not be poisoned");
+        Ok(self.read()?.remove(user_id))
+    }
+
+    fn save(&self, user_id: &UserIdRef, token: &StoredToken) -> Result<(), TokenStoreError> {
+        self.modify(|tokens| {
+            tokens.insert(user_id.to_owned(), token.clone());
+        })
+    }
+
+    fn delete(&self, user_id: &UserIdRef) -> Result<(), TokenStoreError> {
+        self.modify(|tokens| {
+            tokens.remove(user_id);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(store: &dyn TokenStore) {
+        let token = StoredToken::new(
+            AccessToken::from("access"),
+            Some(RefreshToken::from("refresh")),
+            ClientId::from("clientid"),
+        );
+        let user: &UserIdRef = "1234".into();
+        assert_eq!(store.load(user).unwrap(), None);
+        store.save(user, &token).unwrap();
+        store.save("5678".into(), &token).unwrap();
+        assert_eq!(store.load(user).unwrap(), Some(token.clone()));
+        store.delete(user).unwrap();
+        assert_eq!(store.load(user).unwrap(), None);
+        assert_eq!(store.load("5678".into()).unwrap(), Some(token));
+    }
+
+    #[test]
+    fn memory_store() { roundtrip(&MemoryTokenStore::new()) }
+
+    #[test]
+    fn file_store() {
+        let path = std::env::temp_dir().join(format!(
+            "twitch_oauth2_store_test_{}.json",
+            std::process::id()
+        ));
+        let store = FileTokenStore::new(&path);
+        roundtrip(&store);
+        let saved: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved["5678"]["refresh_token"], "refresh");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn refresh_saves_token() {
+        use crate::{client::BoxedFuture, TwitchToken, UserToken};
+
+        struct RefreshClient;
+
+        impl crate::client::Client for RefreshClient {
+            type Error = std::convert::Infallible;
+
+            fn req(
+                &self,
+                _: http::Request<Vec<u8>>,
+            ) -> BoxedFuture<'_, Result<http::Response<Vec<u8>>, Self::Error>> {
+                Box::pin(async {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .body(
+                            br#"{"access_token":"newaccess","refresh_token":"newrefresh","expires_in":14400,"token_type":"bearer"}"#
+                                .to_vec(),
+                        )
+                        .unwrap())
+                })
+            }
+        }
+
+        let store = std::sync::Arc::new(MemoryTokenStore::new());
+        let mut token = UserToken::from_existing_unchecked(
+            AccessToken::from("access"),
+            RefreshToken::from("refresh"),
+            ClientId::from("clientid"),
+            crate::ClientSecret::from("secret"),
+            "justintv".into(),
+            "1234".into(),
+            None,
+            None,
+        );
+        token.set_store(Some(store.clone()));
+        token.refresh_token(&RefreshClient).await.unwrap();
+        let stored = store.load("1234".into()).unwrap().unwrap();
+        assert_eq!(stored.access_token.secret(), "newaccess");
+        assert_eq!(stored.refresh_token.unwrap().secret(), "newrefresh");
+
+        /// Fails to save
+        struct BrokenStore;
+
+        impl TokenStore for BrokenStore {
+            fn load(&self, _: &UserIdRef) -> Result<Option<StoredToken>, TokenStoreError> {
+                Ok(None)
+            }
+
+            fn save(&self, _: &UserIdRef, _: &StoredToken) -> Result<(), TokenStoreError> {
+                Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied).into())
+            }
+
+            fn delete(&self, _: &UserIdRef) -> Result<(), TokenStoreError> { Ok(()) }
+        }
+
+        // a failed save doesn't fail the refresh
+        let failed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        token.set_store(Some(std::sync::Arc::new(BrokenStore)));
+        token.on_store_error({
+            let failed = failed.clone();
+            move |token, _| {
+                assert_eq!(token.refresh_token.as_ref().unwrap().secret(), "newrefresh");
+                failed.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        });
+        token.refresh_token(&RefreshClient).await.unwrap();
+        assert!(failed.load(std::sync::atomic::Ordering::SeqCst));
+    }
+}
@@ -2,22 +2,48 @@
 
 mod app_access_token;
 pub mod errors;
+#[cfg(feature = "jwt")]
+mod extension_jwt;
+#[cfg(feature = "loopback")]
+mod loopback;
+mod oidc;
+#[cfg(feature = "client")]
+mod refreshing_token;
+#[cfg(feature = "client")]
+mod registry;
 mod user_token;
+#[cfg(feature = "client")]
+mod validation;
 
 pub use app_access_token::AppAccessToken;
+#[cfg(feature = "jwt")]
+pub use extension_jwt::{ExtensionClaims, ExtensionJwt, ExtensionRole, PubSubPerms};
+#[cfg(feature = "loopback")]
+pub use loopback::LoopbackServer;
+pub use oidc::{Claim, ClaimsRequest, UserInfo};
+#[cfg(feature = "oidc")]
+pub use oidc::{IdTokenClaims, IdTokenVerifier, Jwk, Jwks};
+#[cfg(feature = "client")]
+pub use refreshing_token::RefreshingToken;
+#[cfg(feature = "client")]
+pub use registry::{LeasedToken, TokenHealth, TokenRegistry};
 use twitch_types::{UserId, UserIdRef, UserName, UserNameRef};
 pub use user_token::{
     DeviceUserTokenBuilder, ImplicitUserTokenBuilder, UserToken, UserTokenBuilder,
 };
+#[cfg(feature = "client")]
+pub use validation::{TokenValidator, ValidationEvent};
 
 #[cfg(feature = "client")]
-use crate::client::Client;
+use crate::client::{BoxedFuture, Client};
 use crate::{id::TwitchTokenErrorResponse, scopes::Scope, RequestParseError};
 
 use errors::ValidationError;
 #[cfg(feature = "client")]
 use errors::{RefreshTokenError, RevokeTokenError};
 
+#[cfg(feature = "client")]
+use crate::types::AccessTokenRef;
 use crate::types::{AccessToken, ClientId};
 use serde_derive::Deserialize;
 
@@ -106,6 +132,11 @@ pub trait TwitchToken {
         token.validate_token(http_client).await
     }
 
+    /// Update the [lifetime](TwitchToken::expires_in) and [scopes](TwitchToken::scopes) of this token from a [validation](TwitchToken::validate_token) of it.
+    ///
+    /// Tokens that can't be updated ignore this.
+    fn set_validated(&mut self, validated: &ValidatedToken) { let _ = validated; }
+
     /// Revoke the token. See <https://dev.twitch.tv/docs/authentication/revoke-tokens>
     #[cfg(feature = "client")]
     async fn revoke_token<'a, C>(
@@ -120,6 +151,30 @@ pub trait TwitchToken {
         let client_id = self.client_id();
         token.revoke_token(http_client, client_id).await
     }
+
+    /// Get the latest [AccessToken], for authorizing requests.
+    ///
+    /// This is the same as [`token`](TwitchToken::token), except for tokens that are refreshed through a shared reference like [`RefreshingToken`].
+    #[cfg(feature = "client")]
+    fn latest_token(&self) -> std::borrow::Cow<'_, AccessTokenRef> {
+        std::borrow::Cow::Borrowed(self.token())
+    }
+
+    /// Refresh the token if it's about to expire, for tokens that can refresh themselves like [`RefreshingToken`].
+    ///
+    /// API clients call this before making a request with the token. Returns `None` if there is nothing to do.
+    #[cfg(feature = "client")]
+    fn refresh_expiring(&self) -> Option<BoxedFuture<'_, ()>> { None }
+
+    /// Refresh the token after `rejected` was rejected by Twitch, for tokens that can refresh themselves like [`RefreshingToken`].
+    ///
+    /// API clients call this when a request fails with `401 Unauthorized`.
+    /// The future resolves to `true` if there is a new token and the request should be retried.
+    #[cfg(feature = "client")]
+    fn refresh_rejected(&self, rejected: &AccessTokenRef) -> Option<BoxedFuture<'_, bool>> {
+        let _ = rejected;
+        None
+    }
 }
 
 #[cfg_attr(feature = "client", async_trait::async_trait)]
@@ -149,6 +204,19 @@ impl<T: TwitchToken + Send> TwitchToken for Box<T> {
     fn expires_in(&self) -> std::time::Duration { (**self).expires_in() }
 
     fn scopes(&self) -> &[Scope] { (**self).scopes() }
+
+    fn set_validated(&mut self, validated: &ValidatedToken) { (**self).set_validated(validated) }
+
+    #[cfg(feature = "client")]
+    fn latest_token(&self) -> std::borrow::Cow<'_, AccessTokenRef> { (**self).latest_token() }
+
+    #[cfg(feature = "client")]
+    fn refresh_expiring(&self) -> Option<BoxedFuture<'_, ()>> { (**self).refresh_expiring() }
+
+    #[cfg(feature = "client")]
+    fn refresh_rejected(&self, rejected: &AccessTokenRef) -> Option<BoxedFuture<'_, bool>> {
+        (**self).refresh_rejected(rejected)
+    }
 }
 
 /// Token validation returned from `https://id.twitch.tv/oauth2/validate`
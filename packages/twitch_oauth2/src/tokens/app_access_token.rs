@@ -88,6 +88,16 @@ impl TwitchToken for AppAccessToken {
     }
 
     fn scopes(&self) -> &[Scope] { self.scopes.as_slice() }
+
+    fn set_validated(&mut self, validated: &super::ValidatedToken) {
+        if let Some(expires_in) = validated.expires_in {
+            self.expires_in = expires_in;
+            self.struct_created = std::time::Instant::now();
+        }
+        if let Some(scopes) = &validated.scopes {
+            self.scopes = scopes.clone();
+        }
+    }
 }
 
 impl AppAccessToken {
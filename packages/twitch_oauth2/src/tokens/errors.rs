@@ -70,6 +70,19 @@ pub enum RefreshTokenError<RE: std::error::Error + Send + Sync + 'static> {
     NoExpiration,
 }
 
+/// Errors for [`UserToken::from_store`](crate::tokens::UserToken::from_store)
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+#[cfg(feature = "client")]
+pub enum FromStoreError<RE: std::error::Error + Send + Sync + 'static> {
+    /// could not load or save token
+    StoreError(#[from] crate::store::TokenStoreError),
+    /// could not validate stored token
+    ValidationError(#[from] ValidationError<RE>),
+    /// could not refresh stored token
+    RefreshTokenError(#[from] RefreshTokenError<RE>),
+}
+
 /// Errors for [`UserTokenBuilder::get_user_token`](crate::tokens::UserTokenBuilder::get_user_token) and [`UserToken::mock_token`](crate::tokens::UserToken::mock_token)
 #[derive(thiserror::Error, Debug, displaydoc::Display)]
 #[non_exhaustive]
@@ -136,3 +149,96 @@ impl<RE: std::error::Error + Send + Sync + 'static> DeviceUserTokenExchangeError
             ) if message == "authorization_pending")
     }
 }
+
+/// Errors for [`ExtensionJwt`][crate::tokens::ExtensionJwt]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+#[cfg(feature = "jwt")]
+pub enum ExtensionJwtError {
+    /// extension secret is not valid base64
+    InvalidSecret(#[source] base64::DecodeError),
+    /// token is not a valid JWT
+    Malformed,
+    /// token is not signed with any of the extension secrets
+    InvalidSignature,
+    /// token is signed with unsupported algorithm `{0}`
+    UnsupportedAlgorithm(String),
+    /// could not parse claims of token
+    InvalidClaims(#[source] serde_json::Error),
+    /// token has expired
+    Expired,
+}
+
+/// Errors for [`IdTokenVerifier::verify`][crate::tokens::IdTokenVerifier::verify]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+#[cfg(feature = "oidc")]
+pub enum IdTokenError {
+    /// token is not a valid JWT
+    Malformed,
+    /// token is signed with unsupported algorithm `{0}`
+    UnsupportedAlgorithm(String),
+    /// token is signed with unknown key `{0}`
+    UnknownKey(String),
+    /// token is not signed with any of the keys
+    InvalidSignature,
+    /// could not parse claims of token
+    InvalidClaims(#[source] serde_json::Error),
+    /// token is issued by `{0}`, not Twitch
+    InvalidIssuer(String),
+    /// token is issued to another client: {0}
+    InvalidAudience(crate::types::ClientId),
+    /// token has expired
+    Expired,
+    /// nonce of token does not match
+    NonceMismatch,
+}
+
+/// Errors for getting the key set of Twitch with [`Jwks::get`][crate::tokens::Jwks::get] and [`IdTokenVerifier::verify_refreshing`][crate::tokens::IdTokenVerifier::verify_refreshing]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+#[cfg(all(feature = "oidc", feature = "client"))]
+pub enum JwksError<RE: std::error::Error + Send + Sync + 'static> {
+    /// request for key set failed
+    RequestError(#[source] RE),
+    /// could not parse response when getting key set
+    RequestParseError(#[from] crate::RequestParseError),
+    /// could not verify ID token
+    IdTokenError(#[from] IdTokenError),
+}
+
+/// Errors for [AccessToken::userinfo][crate::AccessTokenRef::userinfo]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+#[cfg(feature = "client")]
+pub enum UserInfoError<RE: std::error::Error + Send + Sync + 'static> {
+    /// token is not authorized for use
+    NotAuthorized,
+    /// request for user info failed
+    RequestError(#[source] RE),
+    /// could not parse response when getting user info
+    RequestParseError(#[from] crate::RequestParseError),
+}
+
+/// Errors for [`LoopbackServer::get_user_token`][crate::tokens::LoopbackServer::get_user_token]
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+#[non_exhaustive]
+#[cfg(feature = "loopback")]
+pub enum LoopbackError<RE: std::error::Error + Send + Sync + 'static> {
+    /// could not receive redirect
+    Io(#[from] std::io::Error),
+    /// the user denied access: {description:?}
+    AccessDenied {
+        /// Description of error
+        description: Option<String>,
+    },
+    /// twitch returned an error: {error} - {description:?}
+    TwitchError {
+        /// Error type
+        error: String,
+        /// Description of error
+        description: Option<String>,
+    },
+    /// could not exchange code for user token
+    ExchangeError(#[from] UserTokenExchangeError<RE>),
+}
@@ -0,0 +1,313 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use futures_util::future::{self, Either};
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::errors::LoopbackError;
+use super::{UserToken, UserTokenBuilder};
+use crate::client::Client;
+
+/// A listener on the loopback interface that receives the redirect of the [OAuth authorization code flow](https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#authorization-code-grant-flow)
+///
+/// Command line tools and desktop applications can't receive the redirect on a server,
+/// so they use a redirect URL on `localhost`, like `http://localhost:3000/twitch/register`, and listen on that port.
+/// Bind the listener with [`LoopbackServer::bind`] before directing the user to the URL from [`UserTokenBuilder::generate_url`],
+/// then wait for the redirect with [`LoopbackServer::get_user_token`].
+///
+/// The redirect URL must use `http` and the host `localhost`, `127.0.0.1` or `[::1]`.
+/// If no port is given, port 80 is used.
+/// For `localhost` only `127.0.0.1` is listened on, browsers that try `[::1]` first fall back to it when the connection is refused.
+/// Use `[::1]` in the redirect URL to listen on IPv6 instead.
+///
+/// # Examples
+///
+/// ```rust, no_run
+/// use twitch_oauth2::{tokens::LoopbackServer, Scope, UserTokenBuilder};
+/// # async {
+/// # let client = twitch_oauth2::client::DummyClient; stringify!(
+/// let client = reqwest::Client::builder()
+///     .redirect(reqwest::redirect::Policy::none())
+///     .build()?;
+/// # );
+/// let mut builder = UserTokenBuilder::new(
+///     "myclientid",
+///     "myclientsecret",
+///     url::Url::parse("http://localhost:3000/twitch/register")?,
+/// )
+/// .set_scopes(vec![Scope::ChatRead]);
+/// let server = LoopbackServer::bind(&builder).await?;
+///
+/// let (url, _) = builder.generate_url();
+/// println!("Go to this page: {url}");
+///
+/// let token = server.get_user_token(builder, &client).await?;
+/// println!("Got token: {token:?}");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # };
+/// ```
+#[derive(Debug)]
+pub struct LoopbackServer {
+    listener: TcpListener,
+    redirect_url: url::Url,
+}
+
+impl LoopbackServer {
+    /// Listen on the port of the redirect URL of `builder`.
+    ///
+    /// Fails with [`std::io::ErrorKind::InvalidInput`] if the redirect URL is not a `http` URL on the loopback interface.
+    pub async fn bind(builder: &UserTokenBuilder) -> Result<Self, std::io::Error> {
+        let redirect_url = builder.redirect_url.clone();
+        let ip = match redirect_url.host() {
+            _ if redirect_url.scheme() != "http" => None,
+            Some(url::Host::Domain("localhost")) => Some(Ipv4Addr::LOCALHOST.into()),
+            Some(url::Host::Ipv4(ip)) if ip.is_loopback() => Some(ip.into()),
+            Some(url::Host::Ipv6(ip)) if ip.is_loopback() => Some(ip.into()),
+            _ => None,
+        };
+        let Some(ip) = ip else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "redirect url `{redirect_url}` is not a http url on the loopback interface"
+                ),
+            ));
+        };
+        let port = redirect_url.port().unwrap_or(80);
+        let listener = TcpListener::bind(SocketAddr::new(ip, port)).await?;
+        Ok(Self {
+            listener,
+            redirect_url,
+        })
+    }
+
+    /// The address the server listens on
+    pub fn local_addr(&self) -> Result<SocketAddr, std::io::Error> { self.listener.local_addr() }
+
+    /// Wait for the user to be redirected, and exchange the code for a [`UserToken`] with [`UserTokenBuilder::get_user_token`].
+    ///
+    /// Requests to other paths than the redirect URL are answered with `404 Not Found`.
+    /// This waits until the user is redirected, wrap it in a timeout like [`tokio::time::timeout`] to stop waiting.
+    pub async fn get_user_token<C: Client>(
+        self,
+        builder: UserTokenBuilder,
+        http_client: &C,
+    ) -> Result<UserToken, LoopbackError<C::Error>> {
+        let (mut stream, query) = self.redirect().await?;
+        let get = |key| query.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+
+        let result = match (get("error"), get("state"), get("code")) {
+            (Some(error), ..) if error == "access_denied" => Err(LoopbackError::AccessDenied {
+                description: get("error_description"),
+            }),
+            (Some(error), ..) => Err(LoopbackError::TwitchError {
+                error,
+                description: get("error_description"),
+            }),
+            (None, Some(state), Some(code)) => builder
+                .get_user_token(http_client, &state, &code)
+                .await
+                .map_err(Into::into),
+            _ => Err(LoopbackError::TwitchError {
+                error: "invalid_request".to_owned(),
+                description: Some("missing code or state in redirect".to_owned()),
+            }),
+        };
+
+        let body = match &result {
+            Ok(_) => "Authorization complete, you can close this window.".to_owned(),
+            Err(e) => format!("Authorization failed: {e}"),
+        };
+        respond(&mut stream, "200 OK", &body).await?;
+        result
+    }
+
+    /// Accept connections until a request to the redirect URL is made, returning the connection and the query of the request
+    async fn redirect(&self) -> Result<(TcpStream, Vec<(String, String)>), std::io::Error> {
+        // browsers open connections they send no request on, so they're all read at the same time
+        let mut connections = FuturesUnordered::new();
+        loop {
+            let accepted = if connections.is_empty() {
+                self.listener.accept().await
+            } else {
+                let accept = std::pin::pin!(self.listener.accept());
+                match future::select(accept, connections.next()).await {
+                    Either::Left((accepted, _)) => accepted,
+                    Either::Right((Some(Some(redirect)), _)) => return Ok(redirect),
+                    Either::Right(_) => continue,
+                }
+            };
+            let (stream, _) = accepted?;
+            connections.push(self.request(stream));
+        }
+    }
+
+    /// Read a request, returning the connection and the query of the request if it's to the redirect URL
+    async fn request(&self, mut stream: TcpStream) -> Option<(TcpStream, Vec<(String, String)>)> {
+        // failing to answer other requests doesn't matter
+        let target = match read_target(&mut stream).await {
+            Ok(Some(target)) => target,
+            Ok(None) => {
+                let _ = respond(&mut stream, "400 Bad Request", "Bad Request").await;
+                return None;
+            }
+            Err(_) => return None,
+        };
+        match self.redirect_url.join(&target) {
+            Ok(url) if url.path() == self.redirect_url.path() => {
+                let query = url.query_pairs().into_owned().collect();
+                Some((stream, query))
+            }
+            _ => {
+                let _ = respond(&mut stream, "404 Not Found", "Not Found").await;
+                None
+            }
+        }
+    }
+}
+
+/// Read the head of a HTTP request, returning the target of a `GET` request
+async fn read_target(stream: &mut TcpStream) -> Result<Option<String>, std::io::Error> {
+    let mut reader = BufReader::new(stream.take(8 * 1024));
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let mut parts = line.split_whitespace();
+    let target = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Some(target.to_owned()),
+        _ => None,
+    };
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+    }
+    Ok(target)
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<(), std::io::Error> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers token and validation requests
+    struct Twitch;
+
+    impl Client for Twitch {
+        type Error = std::convert::Infallible;
+
+        fn req(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> crate::client::BoxedFuture<'_, Result<http::Response<Vec<u8>>, Self::Error>> {
+            let body: &[u8] = match request.uri().path() {
+                "/oauth2/token" => br#"{"access_token":"rfx2uswqe8l4g1mkagrvg5tv0ks3","expires_in":14124,"refresh_token":"5b93chm6hdve3mycz05zfzatkfdenfspp1h1ar2xxdalen01","scope":["chat:read"],"token_type":"bearer"}"#,
+                "/oauth2/validate" => br#"{"client_id":"clientid","login":"twitchdev","scopes":["chat:read"],"user_id":"141981764","expires_in":14124}"#,
+                path => panic!("unexpected request to {path}"),
+            };
+            Box::pin(async move { Ok(http::Response::new(body.to_vec())) })
+        }
+    }
+
+    async fn get(addr: SocketAddr, target: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    fn builder() -> UserTokenBuilder {
+        UserTokenBuilder::new(
+            "clientid",
+            "secret",
+            url::Url::parse("http://127.0.0.1:0/twitch/register").unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn get_user_token() {
+        let mut builder = builder();
+        let server = LoopbackServer::bind(&builder).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (_, csrf) = builder.generate_url();
+
+        let token = tokio::spawn(async move { server.get_user_token(builder, &Twitch).await });
+        // a preconnected socket that never sends a request doesn't block other requests
+        let _idle = TcpStream::connect(addr).await.unwrap();
+        assert!(get(addr, "/favicon.ico")
+            .await
+            .starts_with("HTTP/1.1 404 Not Found"));
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("code", "code")
+            .append_pair("scope", "chat:read")
+            .append_pair("state", csrf.secret())
+            .finish();
+        let response = get(addr, &format!("/twitch/register?{query}")).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("Authorization complete, you can close this window."));
+
+        let token = token.await.unwrap().unwrap();
+        assert_eq!(token.login.as_str(), "twitchdev");
+    }
+
+    #[tokio::test]
+    async fn access_denied() {
+        let mut builder = builder();
+        let server = LoopbackServer::bind(&builder).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        builder.generate_url();
+
+        let token = tokio::spawn(async move { server.get_user_token(builder, &Twitch).await });
+        let response = get(
+            addr,
+            "/twitch/register?error=access_denied&error_description=The+user+denied+you+access",
+        )
+        .await;
+        assert!(response.contains("Authorization failed"));
+        assert!(matches!(
+            token.await.unwrap(),
+            Err(LoopbackError::AccessDenied { description: Some(d) }) if d == "The user denied you access"
+        ));
+    }
+
+    #[tokio::test]
+    async fn state_mismatch() {
+        let mut builder = builder();
+        let server = LoopbackServer::bind(&builder).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        builder.generate_url();
+
+        let token = tokio::spawn(async move { server.get_user_token(builder, &Twitch).await });
+        get(addr, "/twitch/register?code=code&state=forged").await;
+        assert!(matches!(
+            token.await.unwrap(),
+            Err(LoopbackError::ExchangeError(
+                crate::tokens::errors::UserTokenExchangeError::StateMismatch
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn not_loopback() {
+        let builder = UserTokenBuilder::new(
+            "clientid",
+            "secret",
+            url::Url::parse("https://example.com/twitch/register").unwrap(),
+        );
+        let error = LoopbackServer::bind(&builder).await.unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
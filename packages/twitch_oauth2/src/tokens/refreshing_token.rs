@@ -0,0 +1,360 @@
+use std::borrow::Cow;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use twitch_types::{UserIdRef, UserNameRef};
+
+use super::errors::RefreshTokenError;
+use super::{BearerTokenType, TwitchToken, ValidatedToken};
+use crate::client::{BoxedFuture, Client};
+use crate::types::{AccessToken, AccessTokenRef, ClientId};
+use crate::Scope;
+
+/// A shared token that refreshes itself
+///
+/// Wraps a [`TwitchToken`], like a [`UserToken`](super::UserToken) or [`AppAccessToken`](super::AppAccessToken), and refreshes it when it's about to expire or has been rejected by Twitch.
+/// The handle is cheap to clone, all clones share the same token and only one refresh will run at a time.
+///
+/// API clients, like `twitch_api`'s `HelixClient`, use [`TwitchToken::refresh_expiring`] before requests and [`TwitchToken::refresh_rejected`] when a request fails with `401 Unauthorized`,
+/// and authorize requests with [`TwitchToken::latest_token`].
+/// The other accessors of a handle keep returning the token it was created with, get the latest token with [`RefreshingToken::get`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twitch_oauth2::{AccessToken, RefreshingToken, TwitchToken, UserToken};
+/// # #[tokio::main]
+/// # async fn run() -> Result<(), Box<dyn std::error::Error + 'static>>{
+/// let client = reqwest::Client::builder()
+///     .redirect(reqwest::redirect::Policy::none())
+///     .build()?;
+/// let token = UserToken::from_existing(
+///     &client,
+///     AccessToken::from("my_access_token"),
+///     None, // Refresh Token
+///     None, // Client Secret
+/// )
+/// .await?;
+/// let token = RefreshingToken::new(token, client);
+/// // share the token between tasks
+/// let other = token.clone();
+/// println!("token: {}", other.latest_token().secret());
+/// # Ok(())}
+/// # fn main() {run();}
+/// ```
+pub struct RefreshingToken<T, C> {
+    shared: Arc<Shared<T, C>>,
+    /// The token when this handle was created or last refreshed through `&mut self`, the [`TwitchToken`] accessors borrow from it.
+    snapshot: Arc<T>,
+    refresh_before: Duration,
+}
+
+struct Shared<T, C> {
+    http_client: C,
+    latest: RwLock<Latest<T>>,
+    refreshing: futures_util::lock::Mutex<()>,
+}
+
+/// The latest token, and how many times it has been refreshed
+struct Latest<T> {
+    token: Arc<T>,
+    generation: u64,
+}
+
+impl<T> Clone for Latest<T> {
+    fn clone(&self) -> Self {
+        Self {
+            token: self.token.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T, C> Clone for RefreshingToken<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            snapshot: self.shared.latest().token,
+            refresh_before: self.refresh_before,
+        }
+    }
+}
+
+impl<T, C> Shared<T, C> {
+    fn latest(&self) -> Latest<T> {
+        self.latest
+            .read()
+            .expect("refreshing token should not be poisoned")
+            .clone()
+    }
+}
+
+impl<T: std::fmt::Debug, C> std::fmt::Debug for RefreshingToken<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshingToken")
+            .field("token", &self.shared.latest().token)
+            .field("refresh_before", &self.refresh_before)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, C> RefreshingToken<T, C>
+where
+    T: TwitchToken + Clone + Send + Sync,
+    C: Client,
+{
+    /// Create a new refreshing token, using `http_client` to refresh it.
+    pub fn new(token: T, http_client: C) -> Self {
+        let token = Arc::new(token);
+        Self {
+            shared: Arc::new(Shared {
+                http_client,
+                latest: RwLock::new(Latest {
+                    token: token.clone(),
+                    generation: 0,
+                }),
+                refreshing: futures_util::lock::Mutex::new(()),
+            }),
+            snapshot: token,
+            refresh_before: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// Set how long before expiry the token should be refreshed. Defaults to 5 minutes.
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.refresh_before = refresh_before;
+        self
+    }
+
+    /// Get the latest token
+    ///
+    /// The [`TwitchToken`] accessors of this handle, like [`token`](TwitchToken::token), return the token from when the handle was created, cloned or refreshed through [`TwitchToken::refresh_token`].
+    /// Refreshes through other handles or through [`TwitchToken::refresh_expiring`] and [`TwitchToken::refresh_rejected`] are only seen here and in [`TwitchToken::latest_token`].
+    pub fn get(&self) -> Arc<T> { self.shared.latest().token }
+
+    /// Get the http client used for refreshing
+    pub fn http_client(&self) -> &C { &self.shared.http_client }
+
+    /// Refresh the token now, for all handles sharing it.
+    pub async fn refresh(&self) -> Result<(), RefreshTokenError<<C as Client>::Error>> {
+        self.refresh_with(&self.shared.http_client, self.shared.latest().generation)
+            .await
+    }
+
+    /// Refresh the token, unless the token of generation `stale` has already been replaced by another refresh.
+    async fn refresh_with<HC: Client>(
+        &self,
+        http_client: &HC,
+        stale: u64,
+    ) -> Result<(), RefreshTokenError<<HC as Client>::Error>> {
+        let _guard = self.shared.refreshing.lock().await;
+        let latest = self.shared.latest();
+        if latest.generation != stale {
+            return Ok(());
+        }
+        let mut token = T::clone(&latest.token);
+        token.refresh_token(http_client).await?;
+        // we hold the lock, so nobody else can have refreshed the token
+        *self
+            .shared
+            .latest
+            .write()
+            .expect("refreshing token should not be poisoned") = Latest {
+            token: Arc::new(token),
+            generation: stale + 1,
+        };
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, C> TwitchToken for RefreshingToken<T, C>
+where
+    T: TwitchToken + Clone + Send + Sync,
+    C: Client,
+{
+    fn token_type() -> BearerTokenType { T::token_type() }
+
+    fn client_id(&self) -> &ClientId { self.snapshot.client_id() }
+
+    fn token(&self) -> &AccessToken { self.snapshot.token() }
+
+    fn login(&self) -> Option<&UserNameRef> { self.snapshot.login() }
+
+    fn user_id(&self) -> Option<&UserIdRef> { self.snapshot.user_id() }
+
+    async fn refresh_token<'a, HC>(
+        &mut self,
+        http_client: &'a HC,
+    ) -> Result<(), RefreshTokenError<<HC as Client>::Error>>
+    where
+        Self: Sized,
+        HC: Client,
+    {
+        self.refresh_with(http_client, self.shared.latest().generation)
+            .await?;
+        self.snapshot = self.shared.latest().token;
+        Ok(())
+    }
+
+    fn expires_in(&self) -> Duration { self.snapshot.expires_in() }
+
+    fn scopes(&self) -> &[Scope] { self.snapshot.scopes() }
+
+    fn set_validated(&mut self, validated: &ValidatedToken) {
+        let mut latest = self
+            .shared
+            .latest
+            .write()
+            .expect("refreshing token should not be poisoned");
+        Arc::make_mut(&mut latest.token).set_validated(validated);
+        self.snapshot = latest.token.clone();
+    }
+
+    fn latest_token(&self) -> Cow<'_, AccessTokenRef> {
+        Cow::Owned(self.shared.latest().token.token().clone())
+    }
+
+    fn refresh_expiring(&self) -> Option<BoxedFuture<'_, ()>> {
+        let latest = self.shared.latest();
+        if latest.token.expires_in() > self.refresh_before {
+            return None;
+        }
+        Some(Box::pin(async move {
+            // if this fails, the request will be rejected and we try again then
+            let _ = self
+                .refresh_with(&self.shared.http_client, latest.generation)
+                .await;
+        }))
+    }
+
+    fn refresh_rejected(&self, rejected: &AccessTokenRef) -> Option<BoxedFuture<'_, bool>> {
+        let latest = self.shared.latest();
+        if latest.token.token().secret() != rejected.secret() {
+            // already refreshed
+            return Some(Box::pin(async { true }));
+        }
+        Some(Box::pin(async move {
+            self.refresh_with(&self.shared.http_client, latest.generation)
+                .await
+                .is_ok()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{ClientSecret, RefreshToken, UserToken};
+
+    /// Responds to all requests with a refreshed token
+    #[derive(Default)]
+    struct RefreshClient(AtomicUsize);
+
+    impl Client for RefreshClient {
+        type Error = std::convert::Infallible;
+
+        fn req(
+            &self,
+            _: http::Request<Vec<u8>>,
+        ) -> BoxedFuture<'_, Result<http::Response<Vec<u8>>, Self::Error>> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                let body = format!(
+                    r#"{{"access_token":"token{n}","refresh_token":"refresh{n}","expires_in":14400,"scope":[],"token_type":"bearer"}}"#
+                );
+                Ok(http::Response::builder()
+                    .status(200)
+                    .body(body.into_bytes())
+                    .unwrap())
+            })
+        }
+    }
+
+    fn token(expires_in: u64) -> UserToken {
+        UserToken::from_existing_unchecked(
+            AccessToken::from("token"),
+            RefreshToken::from("refresh"),
+            ClientId::from("clientid"),
+            ClientSecret::from("secret"),
+            "justintv".into(),
+            "1234".into(),
+            None,
+            Some(Duration::from_secs(expires_in)),
+        )
+    }
+
+    #[tokio::test]
+    async fn refreshes_once() {
+        let token = RefreshingToken::new(token(60), RefreshClient::default());
+        let other = token.clone();
+
+        // about to expire, both handles try to refresh but only one refresh happens
+        let (a, b) = (
+            token.refresh_expiring().unwrap(),
+            other.refresh_expiring().unwrap(),
+        );
+        a.await;
+        b.await;
+        assert_eq!(token.http_client().0.load(Ordering::SeqCst), 1);
+        assert_eq!(token.latest_token().secret(), "token0");
+        assert_eq!(other.get().token().secret(), "token0");
+        assert!(token.refresh_expiring().is_none());
+
+        // rejecting an old token doesn't refresh again
+        assert!(
+            other
+                .refresh_rejected(&AccessToken::from("token"))
+                .unwrap()
+                .await
+        );
+        assert_eq!(token.http_client().0.load(Ordering::SeqCst), 1);
+        assert!(
+            other
+                .refresh_rejected(&AccessToken::from("token0"))
+                .unwrap()
+                .await
+        );
+        assert_eq!(token.http_client().0.load(Ordering::SeqCst), 2);
+        assert_eq!(token.latest_token().secret(), "token1");
+        assert_eq!(token.clone().token().secret(), "token1");
+    }
+
+    #[tokio::test]
+    async fn frees_old_tokens() {
+        let mut token = RefreshingToken::new(token(14400), RefreshClient::default());
+        let first = Arc::downgrade(&token.get());
+        token.refresh().await.unwrap();
+        let second = Arc::downgrade(&token.get());
+        // the handle still lends out the first token
+        assert_eq!(token.token().secret(), "token");
+        assert!(first.upgrade().is_some());
+
+        token
+            .refresh_token(&RefreshClient(AtomicUsize::new(5)))
+            .await
+            .unwrap();
+        assert_eq!(token.token().secret(), "token5");
+        assert!(first.upgrade().is_none());
+        assert!(second.upgrade().is_none());
+    }
+
+    #[test]
+    fn set_validated() {
+        let token = RefreshingToken::new(token(60), RefreshClient::default());
+        let mut other = token.clone();
+        // a refresh is running
+        let _refreshing = token.shared.refreshing.try_lock().unwrap();
+
+        let validated: ValidatedToken = serde_json::from_str(
+            r#"{"client_id":"clientid","login":"justintv","scopes":["chat:read"],"user_id":"1234","expires_in":3600}"#,
+        )
+        .unwrap();
+        other.set_validated(&validated);
+        assert!(other.expires_in() > Duration::from_secs(60));
+        assert_eq!(token.get().scopes(), [Scope::ChatRead]);
+        assert!(token.refresh_expiring().is_none());
+    }
+}
@@ -0,0 +1,429 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use twitch_types::{UserId, UserIdRef, UserName, UserNameRef};
+
+use super::errors::RefreshTokenError;
+use super::{BearerTokenType, RefreshingToken, TwitchToken, UserToken, ValidatedToken};
+use crate::client::{BoxedFuture, Client};
+use crate::types::{AccessToken, AccessTokenRef, ClientId};
+use crate::Scope;
+
+/// A registry of [user tokens](UserToken) for many users, keyed by their [user id](UserToken::user_id)
+///
+/// Services that act on behalf of many broadcasters keep a token for each of them.
+/// The registry keeps every token in a [`RefreshingToken`], so each token is refreshed on its own when it's about to expire or is rejected,
+/// and a failure to refresh one token doesn't affect the others.
+///
+/// Get a token for a user with [`TokenRegistry::lease`] and pass it to API calls, like those of `twitch_api`'s `HelixClient`.
+/// The outcome of refreshes is tracked and reported by [`TokenRegistry::health`].
+///
+/// When a user revokes the authorization of the application, Twitch sends the `user.authorization.revoke` EventSub notification.
+/// Evict their token with [`TokenRegistry::remove`] when receiving it, like `registry.remove(&payload.user_id)`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twitch_oauth2::{tokens::TokenRegistry, TwitchToken, UserToken};
+/// use twitch_types::UserIdRef;
+/// # #[tokio::main]
+/// # async fn run() -> Result<(), Box<dyn std::error::Error + 'static>>{
+/// # fn tokens() -> Vec<UserToken> { todo!() }
+/// let client = reqwest::Client::builder()
+///     .redirect(reqwest::redirect::Policy::none())
+///     .build()?;
+/// let registry = TokenRegistry::new(client);
+/// for token in tokens() {
+///     registry.insert(token);
+/// }
+///
+/// // get the token of a broadcaster for a request
+/// let broadcaster = UserIdRef::from_static("141981764");
+/// if let Some(token) = registry.lease(broadcaster) {
+///     println!(
+///         "token for {:?}: {}",
+///         token.login(),
+///         token.latest_token().secret()
+///     );
+/// }
+///
+/// // the broadcaster revoked the authorization
+/// registry.remove(broadcaster);
+///
+/// for (user_id, health) in registry.health_all() {
+///     if !health.is_healthy() {
+///         println!("{user_id} needs to authorize again: {health:?}");
+///     }
+/// }
+/// # Ok(())}
+/// # fn main() {run();}
+/// ```
+pub struct TokenRegistry<C> {
+    http_client: C,
+    entries: RwLock<HashMap<UserId, Entry<C>>>,
+    refresh_before: Duration,
+}
+
+struct Entry<C> {
+    token: RefreshingToken<UserToken, C>,
+    health: Arc<Mutex<Health>>,
+}
+
+impl<C> Clone for Entry<C> {
+    fn clone(&self) -> Self {
+        Self {
+            token: self.token.clone(),
+            health: self.health.clone(),
+        }
+    }
+}
+
+/// Outcome of refreshes of a token
+#[derive(Debug, Default, Clone)]
+struct Health {
+    last_refresh: Option<Instant>,
+    failed_refreshes: u32,
+}
+
+impl Health {
+    fn record(&mut self, refreshed: bool) {
+        if refreshed {
+            self.last_refresh = Some(Instant::now());
+            self.failed_refreshes = 0;
+        } else {
+            self.failed_refreshes += 1;
+        }
+    }
+}
+
+/// The health of a token in a [`TokenRegistry`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TokenHealth {
+    /// Login of the user
+    pub login: UserName,
+    /// Time until the token expires
+    pub expires_in: Duration,
+    /// Scopes of the token
+    pub scopes: Vec<Scope>,
+    /// When the token was last refreshed, [`None`] if it has not been refreshed since it was added
+    pub last_refresh: Option<Instant>,
+    /// How many refreshes have failed since the last successful one
+    pub failed_refreshes: u32,
+}
+
+impl TokenHealth {
+    /// Returns `true` if the token has not expired and the last refresh didn't fail
+    pub fn is_healthy(&self) -> bool { self.failed_refreshes == 0 && !self.expires_in.is_zero() }
+}
+
+impl<C> std::fmt::Debug for TokenRegistry<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenRegistry")
+            .field("users", &self.entries.read().unwrap().len())
+            .field("refresh_before", &self.refresh_before)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Client + Clone> TokenRegistry<C> {
+    /// Create an empty registry, using `http_client` to refresh tokens.
+    pub fn new(http_client: C) -> Self {
+        Self {
+            http_client,
+            entries: RwLock::new(HashMap::new()),
+            refresh_before: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// Set how long before expiry tokens should be refreshed. Defaults to 5 minutes.
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.refresh_before = refresh_before;
+        self
+    }
+
+    /// Get the http client used for refreshing
+    pub fn http_client(&self) -> &C { &self.http_client }
+
+    /// Add the token of a user, replacing any previous token of the same user.
+    ///
+    /// Returns `true` if the user already had a token.
+    pub fn insert(&self, token: UserToken) -> bool {
+        let user_id = token.user_id.clone();
+        let entry = Entry {
+            token: RefreshingToken::new(token, self.http_client.clone())
+                .refresh_before(self.refresh_before),
+            health: Arc::default(),
+        };
+        self.entries
+            .write()
+            .unwrap()
+            .insert(user_id, entry)
+            .is_some()
+    }
+
+    /// Remove the token of a user, for example after they revoked the authorization of the application.
+    ///
+    /// Leased tokens stay usable until they are dropped. Returns `true` if the user had a token.
+    pub fn remove(&self, user_id: &UserIdRef) -> bool {
+        self.entries.write().unwrap().remove(user_id).is_some()
+    }
+
+    /// Returns `true` if there is a token for the user
+    pub fn contains(&self, user_id: &UserIdRef) -> bool {
+        self.entries.read().unwrap().contains_key(user_id)
+    }
+
+    /// The users with a token
+    pub fn user_ids(&self) -> Vec<UserId> { self.entries.read().unwrap().keys().cloned().collect() }
+
+    /// Get the token of a user, to be used for requests on their behalf.
+    ///
+    /// The leased token shares its refreshes with the registry and all other leases of the same token.
+    pub fn lease(&self, user_id: &UserIdRef) -> Option<LeasedToken<C>> {
+        let entry = self.entries.read().unwrap().get(user_id)?.clone();
+        Some(LeasedToken {
+            token: entry.token,
+            health: entry.health,
+            refresh_before: self.refresh_before,
+        })
+    }
+
+    /// Refresh the token of a user now.
+    ///
+    /// Returns [`None`] if there is no token for the user.
+    pub async fn refresh(
+        &self,
+        user_id: &UserIdRef,
+    ) -> Option<Result<(), RefreshTokenError<<C as Client>::Error>>> {
+        let lease = self.lease(user_id)?;
+        let result = lease.token.refresh().await;
+        lease.health.lock().unwrap().record(result.is_ok());
+        Some(result)
+    }
+
+    /// Get the health of the token of a user
+    pub fn health(&self, user_id: &UserIdRef) -> Option<TokenHealth> {
+        self.entries.read().unwrap().get(user_id).map(Entry::health)
+    }
+
+    /// Get the health of the tokens of all users
+    pub fn health_all(&self) -> Vec<(UserId, TokenHealth)> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(user_id, entry)| (user_id.clone(), entry.health()))
+            .collect()
+    }
+}
+
+impl<C: Client> Entry<C> {
+    fn health(&self) -> TokenHealth {
+        let health = self.health.lock().unwrap().clone();
+        let token = self.token.get();
+        TokenHealth {
+            login: token.login.clone(),
+            expires_in: token.expires_in(),
+            scopes: token.scopes().to_vec(),
+            last_refresh: health.last_refresh,
+            failed_refreshes: health.failed_refreshes,
+        }
+    }
+}
+
+/// A token leased from a [`TokenRegistry`], see [`TokenRegistry::lease`]
+///
+/// Refreshes are shared with the registry, and their outcome is reported in [`TokenRegistry::health`].
+pub struct LeasedToken<C> {
+    token: RefreshingToken<UserToken, C>,
+    health: Arc<Mutex<Health>>,
+    refresh_before: Duration,
+}
+
+impl<C> Clone for LeasedToken<C> {
+    fn clone(&self) -> Self {
+        Self {
+            token: self.token.clone(),
+            health: self.health.clone(),
+            refresh_before: self.refresh_before,
+        }
+    }
+}
+
+impl<C> std::fmt::Debug for LeasedToken<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LeasedToken").field(&self.token).finish()
+    }
+}
+
+impl<C: Client> LeasedToken<C> {
+    /// Get the latest token
+    pub fn get(&self) -> Arc<UserToken> { self.token.get() }
+}
+
+#[async_trait::async_trait]
+impl<C: Client> TwitchToken for LeasedToken<C> {
+    fn token_type() -> BearerTokenType { BearerTokenType::UserToken }
+
+    fn client_id(&self) -> &ClientId { self.token.client_id() }
+
+    fn token(&self) -> &AccessToken { self.token.token() }
+
+    fn login(&self) -> Option<&UserNameRef> { self.token.login() }
+
+    fn user_id(&self) -> Option<&UserIdRef> { self.token.user_id() }
+
+    async fn refresh_token<'a, HC>(
+        &mut self,
+        http_client: &'a HC,
+    ) -> Result<(), RefreshTokenError<<HC as Client>::Error>>
+    where
+        Self: Sized,
+        HC: Client,
+    {
+        let result = self.token.refresh_token(http_client).await;
+        self.health.lock().unwrap().record(result.is_ok());
+        result
+    }
+
+    fn expires_in(&self) -> Duration { self.token.expires_in() }
+
+    fn scopes(&self) -> &[Scope] { self.token.scopes() }
+
+    fn set_validated(&mut self, validated: &ValidatedToken) { self.token.set_validated(validated) }
+
+    fn latest_token(&self) -> Cow<'_, AccessTokenRef> { self.token.latest_token() }
+
+    fn refresh_expiring(&self) -> Option<BoxedFuture<'_, ()>> {
+        let refresh = self.token.refresh_expiring()?;
+        Some(Box::pin(async move {
+            refresh.await;
+            let refreshed = self.token.get().expires_in() > self.refresh_before;
+            self.health.lock().unwrap().record(refreshed);
+        }))
+    }
+
+    fn refresh_rejected(&self, rejected: &AccessTokenRef) -> Option<BoxedFuture<'_, bool>> {
+        let refresh = self.token.refresh_rejected(rejected)?;
+        Some(Box::pin(async move {
+            let refreshed = refresh.await;
+            self.health.lock().unwrap().record(refreshed);
+            refreshed
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{ClientSecret, RefreshToken};
+
+    /// Responds to refreshes with a new token, except for the refresh token `revoked`
+    #[derive(Clone, Default)]
+    struct RefreshClient(Arc<AtomicUsize>);
+
+    impl Client for RefreshClient {
+        type Error = std::convert::Infallible;
+
+        fn req(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> BoxedFuture<'_, Result<http::Response<Vec<u8>>, Self::Error>> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst);
+            let revoked = request
+                .uri()
+                .query()
+                .is_some_and(|q| q.contains("refresh_token=revoked"));
+            Box::pin(async move {
+                let (status, body) = if revoked {
+                    (
+                        400,
+                        r#"{"status":400,"message":"Invalid refresh token"}"#.to_owned(),
+                    )
+                } else {
+                    (
+                        200,
+                        format!(
+                            r#"{{"access_token":"token{n}","refresh_token":"refresh{n}","expires_in":14400,"scope":[],"token_type":"bearer"}}"#
+                        ),
+                    )
+                };
+                Ok(http::Response::builder()
+                    .status(status)
+                    .body(body.into_bytes())
+                    .unwrap())
+            })
+        }
+    }
+
+    fn token(user_id: &str, refresh_token: &str, expires_in: u64) -> UserToken {
+        UserToken::from_existing_unchecked(
+            AccessToken::from(format!("token_{user_id}")),
+            RefreshToken::from(refresh_token),
+            ClientId::from("clientid"),
+            ClientSecret::from("secret"),
+            format!("user{user_id}").into(),
+            user_id.into(),
+            None,
+            Some(Duration::from_secs(expires_in)),
+        )
+    }
+
+    fn id(id: &str) -> &UserIdRef { id.into() }
+
+    #[tokio::test]
+    async fn refreshes_independently() {
+        let registry = TokenRegistry::new(RefreshClient::default());
+        assert!(!registry.insert(token("1", "refresh", 60)));
+        assert!(!registry.insert(token("2", "refresh", 14400)));
+        assert!(!registry.insert(token("3", "revoked", 60)));
+
+        let one = registry.lease(id("1")).unwrap();
+        one.refresh_expiring().unwrap().await;
+        assert_eq!(one.latest_token().secret(), "token0");
+        assert!(registry
+            .lease(id("2"))
+            .unwrap()
+            .refresh_expiring()
+            .is_none());
+        let three = registry.lease(id("3")).unwrap();
+        three.refresh_expiring().unwrap().await;
+        assert_eq!(three.latest_token().secret(), "token_3");
+
+        // other leases see the refreshed token
+        assert_eq!(registry.lease(id("1")).unwrap().token().secret(), "token0");
+
+        let one = registry.health(id("1")).unwrap();
+        assert!(one.is_healthy());
+        assert!(one.last_refresh.is_some());
+        let two = registry.health(id("2")).unwrap();
+        assert!(two.is_healthy());
+        assert!(two.last_refresh.is_none());
+        let three = registry.health(id("3")).unwrap();
+        assert!(!three.is_healthy());
+        assert_eq!(three.failed_refreshes, 1);
+        assert_eq!(registry.health_all().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn evicts() {
+        let registry = TokenRegistry::new(RefreshClient::default());
+        registry.insert(token("1", "refresh", 14400));
+        let lease = registry.lease(id("1")).unwrap();
+
+        assert!(registry.remove(id("1")));
+        assert!(!registry.remove(id("1")));
+        assert!(registry.lease(id("1")).is_none());
+        assert!(registry.health(id("1")).is_none());
+        assert!(registry.user_ids().is_empty());
+        // existing leases stay usable
+        assert_eq!(lease.token().secret(), "token_1");
+
+        assert!(registry.refresh(id("1")).await.is_none());
+    }
+}
@@ -3,12 +3,13 @@ use twitch_types::{UserId, UserIdRef, UserName, UserNameRef};
 use super::errors::ValidationError;
 #[cfg(feature = "client")]
 use super::errors::{
-    DeviceUserTokenExchangeError, ImplicitUserTokenExchangeError, RefreshTokenError,
-    UserTokenExchangeError,
+    DeviceUserTokenExchangeError, FromStoreError, ImplicitUserTokenExchangeError,
+    RefreshTokenError, UserTokenExchangeError,
 };
 #[cfg(feature = "client")]
 use crate::client::Client;
 
+use crate::store::{StoredToken, TokenStore, TokenStoreError};
 use crate::tokens::{Scope, TwitchToken};
 use crate::{ClientSecret, ValidatedToken};
 
@@ -41,8 +42,14 @@ pub struct UserToken {
     ///
     /// This is only true for old client IDs, like <https://twitchapps.com/tmi> and others
     pub never_expiring: bool,
+    store: Option<std::sync::Arc<dyn TokenStore>>,
+    on_store_error: Option<std::sync::Arc<StoreErrorHandler>>,
+    id_token: Option<String>,
 }
 
+/// Called when saving a refreshed [`UserToken`] to its store fails, see [`UserToken::on_store_error`]
+type StoreErrorHandler = dyn Fn(&UserToken, &TokenStoreError) + Send + Sync;
+
 impl std::fmt::Debug for UserToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UserToken")
@@ -150,6 +157,74 @@ impl UserToken {
             .map_err(|e| e.into_other())
     }
 
+    /// Restore a [UserToken] for a user from a [`TokenStore`], returns `None` if there is no token saved for the user.
+    ///
+    /// The stored token is checked with [`UserToken::from_existing`]. If the access token has expired, it's refreshed and the new token is saved.
+    /// The store is attached to the returned token, see [`UserToken::set_store`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use twitch_oauth2::{store::MemoryTokenStore, ClientSecret, UserToken};
+    /// // Make sure you enable the feature "reqwest" for twitch_oauth2 if you want to use reqwest
+    /// # async {let client = twitch_oauth2::client::DummyClient; stringify!(
+    /// let client = reqwest::Client::builder()
+    ///     .redirect(reqwest::redirect::Policy::none())
+    ///     .build()?;
+    /// # );
+    /// let store = Arc::new(MemoryTokenStore::new());
+    /// let token = UserToken::from_store(
+    ///     &client,
+    ///     store,
+    ///     "1234".into(),
+    ///     ClientSecret::from("my_client_secret"),
+    /// )
+    /// .await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())};
+    /// ```
+    #[cfg(feature = "client")]
+    pub async fn from_store<C>(
+        http_client: &C,
+        store: std::sync::Arc<dyn TokenStore>,
+        user_id: UserId,
+        client_secret: impl Into<Option<ClientSecret>>,
+    ) -> Result<Option<UserToken>, FromStoreError<<C as Client>::Error>>
+    where
+        C: Client,
+    {
+        let Some(stored) = store.load(&user_id)? else {
+            return Ok(None);
+        };
+        let client_secret = client_secret.into();
+        let (mut token, refreshed) = match Self::from_existing(
+            http_client,
+            stored.access_token,
+            stored.refresh_token.clone(),
+            client_secret.clone(),
+        )
+        .await
+        {
+            Ok(token) => (token, false),
+            Err(ValidationError::NotAuthorized) if stored.refresh_token.is_some() => {
+                let refresh_token = stored.refresh_token.expect("refresh token should be set");
+                let (access_token, _, refresh_token) = refresh_token
+                    .refresh_token(http_client, &stored.client_id, client_secret.as_ref())
+                    .await?;
+                let token =
+                    Self::from_existing(http_client, access_token, refresh_token, client_secret)
+                        .await?;
+                (token, true)
+            }
+            Err(e) => return Err(e.into()),
+        };
+        token.store = Some(store);
+        if refreshed {
+            token.save()?;
+        }
+        Ok(Some(token))
+    }
+
     /// Assemble token without checks.
     ///
     /// # Notes
@@ -177,23 +252,36 @@ impl UserToken {
             struct_created: std::time::Instant::now(),
             scopes: scopes.unwrap_or_default(),
             never_expiring: expires_in.is_none(),
+            store: None,
+            on_store_error: None,
+            id_token: None,
         }
     }
 
     /// Assemble token from twitch responses.
+    ///
+    /// The [ID token](UserToken::id_token) of the response is kept.
     pub fn from_response(
         response: crate::id::TwitchTokenResponse,
         validated: ValidatedToken,
         client_secret: impl Into<Option<ClientSecret>>,
     ) -> Result<UserToken, ValidationError<std::convert::Infallible>> {
-        Self::new(
+        let mut token = Self::new(
             response.access_token,
             response.refresh_token,
             validated,
             client_secret,
-        )
+        )?;
+        token.id_token = response.id_token;
+        Ok(token)
     }
 
+    /// The OpenID Connect ID token issued together with this token, when the [`openid`](Scope::OpenId) scope was requested
+    ///
+    /// Verify it and get its claims with [`IdTokenVerifier`](crate::tokens::IdTokenVerifier), enabled with feature `oidc`.
+    /// Refreshing the token doesn't issue a new ID token.
+    pub fn id_token(&self) -> Option<&str> { self.id_token.as_deref() }
+
     #[doc(hidden)]
     /// Returns true if this token is never expiring.
     ///
@@ -210,6 +298,60 @@ impl UserToken {
         UserTokenBuilder::new(client_id, client_secret, redirect_url)
     }
 
+    /// Get the scopes this token is missing for `validator` to match, see [`Validator::missing`](crate::Validator::missing)
+    pub fn missing_scopes(&self, validator: &crate::Validator) -> Option<Vec<Scope>> {
+        validator.missing(&self.scopes)
+    }
+
+    /// Create a [`UserTokenBuilder`] that asks the user for the scopes this token is missing for `validator` to match.
+    ///
+    /// Twitch issues tokens with exactly the scopes that were requested, so the builder requests the scopes of this token together with the [missing ones](UserToken::missing_scopes),
+    /// and the new token replaces this one. [`force_verify`](UserTokenBuilder::force_verify) is disabled, the user is already logged in and only needs to approve the scopes.
+    /// Check that the new token is for the same [user](UserToken::user_id), in case the user switched accounts.
+    ///
+    /// Returns [`None`] if this token already matches, or if adding scopes can't make it match.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// use twitch_oauth2::{validator, Scope, UserToken, Validator};
+    /// # fn token() -> UserToken { todo!() }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token: UserToken = token();
+    /// const CLIPS: Validator = validator!(Scope::ClipsEdit);
+    ///
+    /// if let Some(missing) = token.missing_scopes(&CLIPS).filter(|m| !m.is_empty()) {
+    ///     let mut builder = token
+    ///         .reauthorize(
+    ///             &CLIPS,
+    ///             "myclientsecret".into(),
+    ///             url::Url::parse("http://localhost/twitch/register")?,
+    ///         )
+    ///         .unwrap();
+    ///     let (url, _csrf) = builder.generate_url();
+    ///     println!("To create clips, grant {} at {url}", missing.join(", "));
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn reauthorize(
+        &self,
+        validator: &crate::Validator,
+        client_secret: ClientSecret,
+        redirect_url: url::Url,
+    ) -> Option<UserTokenBuilder> {
+        let missing = self.missing_scopes(validator)?;
+        if missing.is_empty() {
+            return None;
+        }
+        let mut scopes = self.scopes.clone();
+        scopes.extend(missing);
+        Some(
+            UserTokenBuilder::new(self.client_id.clone(), client_secret, redirect_url)
+                .set_scopes(scopes)
+                .force_verify(false),
+        )
+    }
+
     /// Generate a user token from [mock-api](https://github.com/twitchdev/twitch-cli/blob/main/docs/mock-api.md#auth-namespace)
     ///
     /// # Examples
@@ -279,6 +421,37 @@ impl UserToken {
 
     /// Set the client secret
     pub fn set_secret(&mut self, secret: Option<ClientSecret>) { self.client_secret = secret }
+
+    /// Set the [`TokenStore`] this token is saved to after it's refreshed
+    pub fn set_store(&mut self, store: Option<std::sync::Arc<dyn TokenStore>>) {
+        self.store = store
+    }
+
+    /// Set a function that is called when saving this token to its [`TokenStore`] after a refresh fails.
+    ///
+    /// The refresh has succeeded at that point, so [`refresh_token`](TwitchToken::refresh_token) still returns `Ok` and the new token is only kept in memory.
+    /// Use this to log the error, or to try again with [`UserToken::save`].
+    pub fn on_store_error(
+        &mut self,
+        f: impl Fn(&UserToken, &TokenStoreError) + Send + Sync + 'static,
+    ) {
+        self.on_store_error = Some(std::sync::Arc::new(f))
+    }
+
+    /// Save this token to its [`TokenStore`], does nothing if there is no store set.
+    pub fn save(&self) -> Result<(), TokenStoreError> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        store.save(
+            &self.user_id,
+            &StoredToken::new(
+                self.access_token.clone(),
+                self.refresh_token.clone(),
+                self.client_id.clone(),
+            ),
+        )
+    }
 }
 
 #[cfg_attr(feature = "client", async_trait::async_trait)]
@@ -314,6 +487,10 @@ impl TwitchToken for UserToken {
         self.expires_in = expires;
         self.refresh_token = refresh_token;
         self.struct_created = std::time::Instant::now();
+        // the old refresh token is gone, so report the error without failing the refresh
+        if let (Err(e), Some(on_store_error)) = (self.save(), &self.on_store_error) {
+            on_store_error(self, &e);
+        }
         Ok(())
     }
 
@@ -329,6 +506,15 @@ impl TwitchToken for UserToken {
     }
 
     fn scopes(&self) -> &[Scope] { self.scopes.as_slice() }
+
+    fn set_validated(&mut self, validated: &super::ValidatedToken) {
+        self.expires_in = validated.expires_in.unwrap_or(std::time::Duration::MAX);
+        self.never_expiring = validated.expires_in.is_none();
+        self.struct_created = std::time::Instant::now();
+        if let Some(scopes) = &validated.scopes {
+            self.scopes = scopes.clone();
+        }
+    }
 }
 
 /// Builder for [OAuth authorization code flow](https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#authorization-code-grant-flow)
@@ -437,6 +623,8 @@ pub struct UserTokenBuilder {
     pub(crate) csrf: Option<crate::types::CsrfToken>,
     pub(crate) force_verify: bool,
     pub(crate) redirect_url: url::Url,
+    pub(crate) claims: Option<super::ClaimsRequest>,
+    pub(crate) nonce: Option<String>,
     client_id: ClientId,
     client_secret: ClientSecret,
 }
@@ -462,6 +650,8 @@ impl UserTokenBuilder {
             csrf: None,
             force_verify: false,
             redirect_url,
+            claims: None,
+            nonce: None,
             client_id: client_id.into(),
             client_secret: client_secret.into(),
         }
@@ -482,6 +672,23 @@ impl UserTokenBuilder {
         self
     }
 
+    /// Request [OpenID Connect](https://dev.twitch.tv/docs/authentication/getting-tokens-oidc/) claims about the user.
+    ///
+    /// Claims in the ID token need the [`openid`](Scope::OpenId) scope.
+    pub fn set_claims(mut self, claims: super::ClaimsRequest) -> Self {
+        self.claims = Some(claims);
+        self
+    }
+
+    /// Set the nonce to include in the ID token, see [`IdTokenVerifier::verify`](crate::tokens::IdTokenVerifier::verify).
+    pub fn set_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Get the nonce to include in the ID token
+    pub fn nonce(&self) -> Option<&str> { self.nonce.as_deref() }
+
     /// Generate the URL to request a code.
     ///
     /// First step in the [guide](https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#get-the-user-to-authorize-your-app)
@@ -508,6 +715,15 @@ impl UserTokenBuilder {
             url.query_pairs_mut().append_pair("force_verify", "true");
         };
 
+        if let Some(claims) = self.claims.as_ref().filter(|c| !c.is_empty()) {
+            url.query_pairs_mut()
+                .append_pair("claims", &claims.to_json());
+        }
+
+        if let Some(nonce) = &self.nonce {
+            url.query_pairs_mut().append_pair("nonce", nonce);
+        }
+
         (url, csrf)
     }
 
@@ -1088,13 +1304,18 @@ mod tests {
             "scope": [
                 "channel:read:subscriptions"
             ],
-            "token_type": "bearer"
+            "token_type": "bearer",
+            "id_token": "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9"
           }
         "#;
         let response = http::Response::builder().status(200).body(body).unwrap();
         let response = TwitchTokenResponse::from_response(&response).unwrap();
 
-        UserToken::from_response(response, validated, None).unwrap();
+        let token = UserToken::from_response(response, validated, None).unwrap();
+        assert_eq!(
+            token.id_token(),
+            Some("eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9")
+        );
     }
 
     #[test]
@@ -1110,6 +1331,46 @@ mod tests {
         .to_string();
     }
 
+    #[test]
+    fn reauthorize() {
+        let token = UserToken::from_existing_unchecked(
+            AccessToken::new("token".to_string()),
+            None,
+            ClientId::new("clientid".to_string()),
+            None,
+            "justintv".into(),
+            "1234".into(),
+            Some(vec![Scope::ChatRead]),
+            None,
+        );
+        const VALIDATOR: crate::Validator =
+            crate::validator!(Scope::ChatRead, any(Scope::ChatEdit, Scope::UserWriteChat));
+        assert_eq!(
+            token.missing_scopes(&VALIDATOR),
+            Some(vec![Scope::ChatEdit])
+        );
+
+        let (url, _) = token
+            .reauthorize(
+                &VALIDATOR,
+                "secret".into(),
+                url::Url::parse("https://localhost/twitch/register").unwrap(),
+            )
+            .unwrap()
+            .generate_url();
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["scope"], "chat:read chat:edit");
+        assert!(!query.contains_key("force_verify"));
+
+        assert!(token
+            .reauthorize(
+                &crate::validator!(Scope::ChatRead),
+                "secret".into(),
+                url::Url::parse("https://localhost/twitch/register").unwrap(),
+            )
+            .is_none());
+    }
+
     #[tokio::test]
     #[ignore]
     #[cfg(feature = "surf")]
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream, StreamExt};
+
+use super::errors::ValidationError;
+use super::TwitchToken;
+use crate::client::Client;
+use crate::types::AccessTokenRef;
+use crate::Scope;
+
+/// Validates a set of tokens on a schedule
+///
+/// Twitch requires applications to [validate their tokens every hour](https://dev.twitch.tv/docs/authentication/validate-tokens/#how-to-validate-a-token),
+/// and to stop using tokens that are no longer valid, for example because the user disconnected the application.
+/// The validator keeps the tokens in use under a key, validates all of them with [`TokenValidator::validate`] and reports what changed as [`ValidationEvent`]s.
+/// Invalid and expired tokens are removed, and the [lifetime](TwitchToken::expires_in) and scopes of valid tokens are updated with [`TwitchToken::set_validated`].
+///
+/// [`TokenValidator::events`] validates the tokens every [interval](TokenValidator::interval), waiting with the given `sleep` function so it works with any runtime.
+/// Use a [`RefreshingToken`](super::RefreshingToken) to share the tokens with the API clients using them, so refreshes are seen by the validator.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use futures_util::StreamExt;
+/// use twitch_oauth2::{
+///     tokens::ValidationEvent, RefreshingToken, TokenValidator, TwitchToken, UserToken,
+/// };
+/// # #[tokio::main]
+/// # async fn run() -> Result<(), Box<dyn std::error::Error + 'static>>{
+/// # fn token() -> UserToken { todo!() }
+/// let client = reqwest::Client::builder()
+///     .redirect(reqwest::redirect::Policy::none())
+///     .build()?;
+/// let validator = TokenValidator::new(client.clone());
+/// let token = RefreshingToken::new(token(), client);
+/// validator.insert(token.get().user_id.clone(), token);
+///
+/// let mut events = std::pin::pin!(validator.events(tokio::time::sleep));
+/// while let Some(event) = events.next().await {
+///     match event {
+///         ValidationEvent::Invalid { key, .. } | ValidationEvent::Expired { key, .. } => {
+///             println!("stopped using the token of {key}")
+///         }
+///         ValidationEvent::ScopesChanged { key, token, .. } => {
+///             println!("{key} now has the scopes {:?}", token.scopes())
+///         }
+///         event => println!("{event:?}"),
+///     }
+/// }
+/// # Ok(())}
+/// # fn main() {run();}
+/// ```
+pub struct TokenValidator<K, T, C> {
+    http_client: C,
+    tokens: Mutex<HashMap<K, T>>,
+    interval: Duration,
+}
+
+/// An event from validating the tokens of a [`TokenValidator`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValidationEvent<K, T, RE: std::error::Error + Send + Sync + 'static> {
+    /// Twitch rejected the token, it has been removed from the validator.
+    Invalid {
+        /// Key of the token
+        key: K,
+        /// The rejected token
+        token: T,
+    },
+    /// The token expired before it was validated, it has been removed from the validator.
+    Expired {
+        /// Key of the token
+        key: K,
+        /// The expired token
+        token: T,
+    },
+    /// The scopes of the token changed.
+    ScopesChanged {
+        /// Key of the token
+        key: K,
+        /// The token with the new scopes
+        token: T,
+        /// The scopes the token had before
+        previous: Vec<Scope>,
+    },
+    /// The token could not be validated, it will be validated again at the next interval.
+    Failed {
+        /// Key of the token
+        key: K,
+        /// The error
+        error: ValidationError<RE>,
+    },
+}
+
+impl<K, T, C> std::fmt::Debug for TokenValidator<K, T, C>
+where
+    K: std::fmt::Debug,
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenValidator")
+            .field("tokens", &self.tokens)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, T, C> TokenValidator<K, T, C>
+where
+    K: Eq + Hash + Clone,
+    T: TwitchToken + Clone,
+    C: Client,
+{
+    /// Create a validator without tokens, using `http_client` to validate them.
+    pub fn new(http_client: C) -> Self {
+        Self {
+            http_client,
+            tokens: Mutex::new(HashMap::new()),
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+
+    /// Set how often tokens are validated by [`TokenValidator::events`]. Defaults to one hour.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Get the http client used for validating
+    pub fn http_client(&self) -> &C { &self.http_client }
+
+    /// Add a token to validate, returning the token previously added with the same key.
+    pub fn insert(&self, key: K, token: T) -> Option<T> {
+        self.tokens.lock().unwrap().insert(key, token)
+    }
+
+    /// Stop validating a token, returning it.
+    pub fn remove(&self, key: &K) -> Option<T> { self.tokens.lock().unwrap().remove(key) }
+
+    /// Get a token
+    pub fn get(&self, key: &K) -> Option<T> { self.tokens.lock().unwrap().get(key).cloned() }
+
+    /// The keys of all tokens
+    pub fn keys(&self) -> Vec<K> { self.tokens.lock().unwrap().keys().cloned().collect() }
+
+    /// Validate all tokens once.
+    ///
+    /// Tokens that are valid and whose scopes didn't change produce no event.
+    pub async fn validate(&self) -> Vec<ValidationEvent<K, T, C::Error>> {
+        let tokens: Vec<(K, T)> = self
+            .tokens
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(k, t)| (k.clone(), t.clone()))
+            .collect();
+        let mut events = vec![];
+        for (key, token) in tokens {
+            if let Some(event) = self.validate_one(key, token).await {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    async fn validate_one(&self, key: K, token: T) -> Option<ValidationEvent<K, T, C::Error>> {
+        // the token may be refreshed, replaced or removed while validating
+        let secret = token.latest_token().into_owned();
+        if token.is_elapsed() {
+            return self
+                .remove_if_current(&key, &secret)
+                .map(|token| ValidationEvent::Expired { key, token });
+        }
+        let validated = match secret.validate_token(&self.http_client).await {
+            Ok(validated) => validated,
+            Err(ValidationError::NotAuthorized) => {
+                return self
+                    .remove_if_current(&key, &secret)
+                    .map(|token| ValidationEvent::Invalid { key, token });
+            }
+            Err(error) => return Some(ValidationEvent::Failed { key, error }),
+        };
+
+        let mut tokens = self.tokens.lock().unwrap();
+        let current = tokens
+            .get_mut(&key)
+            .filter(|t| t.latest_token().secret() == secret.secret())?;
+        let previous = current.scopes().to_vec();
+        current.set_validated(&validated);
+        let changed = validated.scopes.as_ref().is_some_and(|scopes| {
+            scopes.len() != previous.len() || scopes.iter().any(|s| !previous.contains(s))
+        });
+        changed.then(|| ValidationEvent::ScopesChanged {
+            key,
+            token: current.clone(),
+            previous,
+        })
+    }
+
+    /// Remove the token under `key` if it's still `secret`
+    fn remove_if_current(&self, key: &K, secret: &AccessTokenRef) -> Option<T> {
+        let mut tokens = self.tokens.lock().unwrap();
+        if tokens.get(key)?.latest_token().secret() != secret.secret() {
+            return None;
+        }
+        tokens.remove(key)
+    }
+
+    /// Validate all tokens now and then every [interval](TokenValidator::interval), yielding the events.
+    ///
+    /// `sleep` is called with the interval to wait between validations, like [`tokio::time::sleep`](https://docs.rs/tokio/*/tokio/time/fn.sleep.html).
+    /// The stream never ends, drop it to stop validating.
+    pub fn events<'a, S, F>(
+        &'a self,
+        sleep: S,
+    ) -> impl Stream<Item = ValidationEvent<K, T, C::Error>> + 'a
+    where
+        S: Fn(Duration) -> F + 'a,
+        F: Future<Output = ()> + 'a,
+    {
+        stream::unfold((sleep, false), move |(sleep, wait)| async move {
+            if wait {
+                sleep(self.interval).await;
+            }
+            let events = self.validate().await;
+            Some((stream::iter(events), (sleep, true)))
+        })
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::client::BoxedFuture;
+    use crate::{AccessToken, ClientId, UserToken};
+
+    /// Validates tokens by their secret
+    struct Twitch;
+
+    impl Client for Twitch {
+        type Error = std::convert::Infallible;
+
+        fn req(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> BoxedFuture<'_, Result<http::Response<Vec<u8>>, Self::Error>> {
+            let auth = request.headers()[http::header::AUTHORIZATION]
+                .to_str()
+                .unwrap();
+            let (status, body) = match auth.trim_start_matches("OAuth ") {
+                "valid" => (
+                    200,
+                    r#"{"client_id":"clientid","login":"twitchdev","scopes":["chat:read"],"user_id":"1","expires_in":3600}"#,
+                ),
+                "changed" => (
+                    200,
+                    r#"{"client_id":"clientid","login":"twitchdev","scopes":["chat:read","chat:edit"],"user_id":"2","expires_in":3600}"#,
+                ),
+                _ => (401, r#"{"status":401,"message":"invalid access token"}"#),
+            };
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(status)
+                    .body(body.as_bytes().to_vec())
+                    .unwrap())
+            })
+        }
+    }
+
+    fn token(secret: &str, expires_in: u64) -> UserToken {
+        UserToken::from_existing_unchecked(
+            AccessToken::from(secret),
+            None,
+            ClientId::from("clientid"),
+            None,
+            "twitchdev".into(),
+            "1".into(),
+            Some(vec![Scope::ChatRead]),
+            Some(Duration::from_secs(expires_in)),
+        )
+    }
+
+    #[tokio::test]
+    async fn validate() {
+        let validator = TokenValidator::new(Twitch);
+        validator.insert(1, token("valid", 60));
+        validator.insert(2, token("changed", 60));
+        validator.insert(3, token("revoked", 60));
+        validator.insert(4, token("valid", 0));
+
+        let mut events = validator.validate().await;
+        events.sort_by_key(|e| match e {
+            ValidationEvent::ScopesChanged { key, .. }
+            | ValidationEvent::Invalid { key, .. }
+            | ValidationEvent::Expired { key, .. }
+            | ValidationEvent::Failed { key, .. } => *key,
+        });
+        assert!(matches!(
+            &events[..],
+            [
+                ValidationEvent::ScopesChanged { key: 2, token, previous },
+                ValidationEvent::Invalid { key: 3, .. },
+                ValidationEvent::Expired { key: 4, .. },
+            ] if token.scopes() == [Scope::ChatRead, Scope::ChatEdit] && previous == &[Scope::ChatRead]
+        ));
+
+        let mut keys = validator.keys();
+        keys.sort();
+        assert_eq!(keys, [1, 2]);
+        // the lifetime was updated
+        assert!(validator.get(&1).unwrap().expires_in() > Duration::from_secs(60));
+        assert!(validator.validate().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn events() {
+        let validator = TokenValidator::new(Twitch).interval(Duration::from_secs(10));
+        validator.insert(1, token("valid", 60));
+        let sleeps = AtomicUsize::new(0);
+
+        let events = validator.events(|interval| {
+            assert_eq!(interval, Duration::from_secs(10));
+            let n = sleeps.fetch_add(1, Ordering::SeqCst);
+            if n == 1 {
+                validator.insert(2, token("revoked", 60));
+            }
+            async {}
+        });
+        let events: Vec<_> = events.take(1).collect().await;
+        assert!(matches!(
+            &events[..],
+            [ValidationEvent::Invalid { key: 2, .. }]
+        ));
+        assert_eq!(sleeps.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn refreshing() {
+        let token = crate::RefreshingToken::new(token("valid", 60), Twitch);
+        let validator = TokenValidator::new(Twitch);
+        validator.insert(1, token.clone());
+
+        assert!(validator.validate().await.is_empty());
+        // the lifetime is updated for all handles
+        assert!(token.get().expires_in() > Duration::from_secs(60));
+        assert!(token.refresh_expiring().is_none());
+    }
+}
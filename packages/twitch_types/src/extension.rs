@@ -1,6 +1,155 @@
+use crate::{DisplayName, Timestamp, UserId, UserName};
+
 manual_braid! {
     /// An Extension ID
     pub struct ExtensionId;
     pub struct ExtensionIdRef;
 }
 impl_extra!(ExtensionId, ExtensionIdRef);
+
+manual_braid! {
+    /// An Extension Bits transaction ID
+    pub struct ExtensionTransactionId;
+    pub struct ExtensionTransactionIdRef;
+}
+impl_extra!(ExtensionTransactionId, ExtensionTransactionIdRef);
+
+/// A Bits transaction made in an extension
+///
+/// Transactions polled from Helix and transactions pushed by EventSub are both deserialized into this type.
+/// Fields that are only sent by one of them are optional.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct BitsTransaction {
+    /// An ID that identifies the transaction.
+    pub id: ExtensionTransactionId,
+    /// The client ID of the extension. Only sent by EventSub.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub extension_client_id: Option<String>,
+    /// The UTC date and time of the transaction. Only sent by Helix.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub timestamp: Option<Timestamp>,
+    /// The ID of the broadcaster that owns the channel where the transaction occurred.
+    #[cfg_attr(feature = "serde", serde(alias = "broadcaster_id"))]
+    pub broadcaster_user_id: UserId,
+    /// The broadcaster’s login name.
+    #[cfg_attr(feature = "serde", serde(alias = "broadcaster_login"))]
+    pub broadcaster_user_login: UserName,
+    /// The broadcaster’s display name.
+    #[cfg_attr(feature = "serde", serde(alias = "broadcaster_name"))]
+    pub broadcaster_user_name: DisplayName,
+    /// The ID of the user that purchased the digital product.
+    pub user_id: UserId,
+    /// The user’s login name.
+    pub user_login: UserName,
+    /// The user’s display name.
+    pub user_name: DisplayName,
+    /// The type of transaction. Only sent by Helix.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub product_type: Option<ProductType>,
+    /// The digital product that was purchased.
+    #[cfg_attr(feature = "serde", serde(alias = "product_data"))]
+    pub product: ExtensionProduct,
+}
+
+/// The type of a [Bits transaction](BitsTransaction)
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[non_exhaustive]
+pub enum ProductType {
+    /// A product bought with Bits in an extension
+    BitsInExtension,
+}
+
+/// A digital product bought in a [Bits transaction](BitsTransaction)
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct ExtensionProduct {
+    /// An ID that identifies the digital product.
+    pub sku: String,
+    /// The product’s name as displayed in the extension.
+    #[cfg_attr(feature = "serde", serde(alias = "displayName"))]
+    pub name: String,
+    /// The amount of Bits the product costs.
+    #[cfg_attr(
+        feature = "serde",
+        serde(alias = "cost", deserialize_with = "deserialize_bits")
+    )]
+    pub bits: i64,
+    /// A Boolean value that determines whether the product is in development.
+    #[cfg_attr(feature = "serde", serde(alias = "inDevelopment"))]
+    pub in_development: bool,
+    /// The extension’s domain, in the form `twitch.ext.<client_id>`. Only sent by Helix.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub domain: Option<String>,
+    /// The date and time when the product expires. Only sent by Helix, and only if the product expires.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "deserialize_expiration"
+        )
+    )]
+    pub expiration: Option<Timestamp>,
+    /// A Boolean value that determines whether the purchase is broadcast to all instances of the extension on the channel. Only sent by Helix.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub broadcast: Option<bool>,
+}
+
+/// Deserialize the Bits of a product from either an amount or a cost object
+#[cfg(feature = "serde")]
+fn deserialize_bits<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where D: serde::Deserializer<'de> {
+    #[derive(serde_derive::Deserialize)]
+    #[serde(untagged)]
+    enum Bits {
+        Amount(i64),
+        Cost { amount: i64 },
+    }
+
+    match <Bits as serde::Deserialize>::deserialize(deserializer)? {
+        Bits::Amount(amount) | Bits::Cost { amount } => Ok(amount),
+    }
+}
+
+/// Deserialize "" as [`None`]
+#[cfg(feature = "serde")]
+fn deserialize_expiration<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+where D: serde::Deserializer<'de> {
+    match <Option<String> as serde::Deserialize>::deserialize(deserializer)? {
+        Some(s) if !s.is_empty() => Timestamp::new(s)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
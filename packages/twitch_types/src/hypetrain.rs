@@ -0,0 +1,150 @@
+use crate::{DisplayName, HypeTrainId, Timestamp, UserId, UserName};
+
+/// A Hype Train
+///
+/// The current Hype Train returned by Helix and the Hype Train sent by EventSub are both deserialized into this type.
+/// Fields that are only sent by some of them are optional.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct HypeTrain {
+    /// The Hype Train ID.
+    pub id: HypeTrainId,
+    /// The ID of the broadcaster.
+    pub broadcaster_user_id: UserId,
+    /// The login of the broadcaster.
+    pub broadcaster_user_login: UserName,
+    /// The display name of the broadcaster.
+    pub broadcaster_user_name: DisplayName,
+    /// The current level of the Hype Train.
+    pub level: i64,
+    /// Total points contributed to the Hype Train.
+    pub total: i64,
+    /// The number of points contributed to the Hype Train at the current level.
+    pub progress: i64,
+    /// The number of points required to reach the next level.
+    pub goal: i64,
+    /// The contributors with the most points contributed.
+    pub top_contributions: Vec<HypeTrainContribution>,
+    /// The broadcasters participating in the shared Hype Train. [`None`] if the Hype Train is not shared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shared_train_participants: Option<Vec<SharedTrainParticipant>>,
+    /// The time when the Hype Train started.
+    pub started_at: Timestamp,
+    /// The time when the Hype Train expires. The expiration is extended when the Hype Train reaches a new level.
+    pub expires_at: Timestamp,
+    /// The type of the Hype Train.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: HypeTrainType,
+    /// Indicates if the Hype Train is shared between multiple channels.
+    pub is_shared_train: bool,
+    /// The highest level the channel has ever reached in a Hype Train. Only sent when a Hype Train begins.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub all_time_high_level: Option<i64>,
+    /// The most points the channel has ever gathered in a Hype Train. Only sent when a Hype Train begins.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub all_time_high_total: Option<i64>,
+}
+
+/// The type of a [Hype Train](HypeTrain)
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum HypeTrainType {
+    /// A regular Hype Train
+    Regular,
+    /// A Golden Kappa Train
+    GoldenKappa,
+    /// A Treasure Train
+    Treasure,
+    /// An unknown Hype Train type, contains the raw string provided by Twitch.
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Unknown(String),
+}
+
+/// A contribution to a [Hype Train](HypeTrain)
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct HypeTrainContribution {
+    /// The ID of the user that contributed.
+    pub user_id: UserId,
+    /// The login of the user that contributed.
+    pub user_login: UserName,
+    /// The display name of the user that contributed.
+    pub user_name: DisplayName,
+    /// The type of the contribution.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: HypeTrainContributionType,
+    /// The total contributed. Bits are counted as points, subscriptions are counted as 500, 1000 and 2500 points for tier 1, 2 and 3 respectively.
+    pub total: i64,
+}
+
+/// The type of a [Hype Train contribution](HypeTrainContribution)
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
+pub enum HypeTrainContributionType {
+    /// Bits
+    Bits,
+    /// Channel Subscriptions. Either gifted or not.
+    Subscription,
+    /// Covers other contribution methods not listed.
+    Other,
+}
+
+/// A broadcaster participating in a shared [Hype Train](HypeTrain)
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct SharedTrainParticipant {
+    /// The ID of the broadcaster.
+    pub broadcaster_user_id: UserId,
+    /// The login of the broadcaster.
+    pub broadcaster_user_login: UserName,
+    /// The display name of the broadcaster.
+    pub broadcaster_user_name: DisplayName,
+}
+
+/// A record reached by a channel in a [Hype Train](HypeTrain)
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
+#[non_exhaustive]
+pub struct HypeTrainRecord {
+    /// The level of the record Hype Train.
+    pub level: i64,
+    /// Total points contributed to the record Hype Train.
+    pub total: i64,
+    /// The time when the record was achieved.
+    pub achieved_at: Timestamp,
+}
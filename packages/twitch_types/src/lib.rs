@@ -81,6 +81,9 @@ pub mod extension;
 #[cfg(feature = "goal")]
 /// types for goals
 pub mod goal;
+#[cfg(feature = "hypetrain")]
+/// types for hype trains
+pub mod hypetrain;
 #[cfg(feature = "moderation")]
 /// types for moderation
 pub mod moderation;
@@ -116,6 +119,8 @@ pub use crate::eventsub::*;
 pub use crate::extension::*;
 #[cfg(feature = "goal")]
 pub use crate::goal::*;
+#[cfg(feature = "hypetrain")]
+pub use crate::hypetrain::*;
 #[cfg(feature = "moderation")]
 pub use crate::moderation::*;
 #[cfg(feature = "points")]
@@ -201,6 +201,7 @@ fn item_override(i: String) -> String {
         "update_conduits" => "update_conduit".to_owned(),
         "resolve_unban_requests" => "resolve_unban_request".to_owned(),
         "get_hype_train_events" => "get_hypetrain_events".to_owned(),
+        "get_hype_train_status" => "get_hypetrain_status".to_owned(),
         _ => i,
     }
 }